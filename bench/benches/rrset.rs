@@ -1,5 +1,8 @@
 use criterion::{criterion_group, criterion_main, Criterion};
-use rsdns::records::{data::*, RecordSet};
+use rsdns::{
+    message::reader::MessageReader,
+    records::{data::*, RecordSet},
+};
 
 // ; <<>> ch4 0.5.0 git:e46d6f4 <<>> --read docs.rs.ch4
 // ;; ->>HEADER<<- opcode: QUERY, status: NOERROR, id: 25405
@@ -87,6 +90,12 @@ pub fn rrset_benchmark(c: &mut Criterion) {
     c.bench_function("aaaa_miss_4", |b| {
         b.iter(|| RecordSet::<Aaaa>::from_msg(&A_DOCS_RS[..]))
     });
+    c.bench_function("collect_addresses_a_4", |b| {
+        b.iter(|| MessageReader::new(&A_DOCS_RS[..])?.collect_addresses())
+    });
+    c.bench_function("collect_addresses_aaaa_8", |b| {
+        b.iter(|| MessageReader::new(&AAAA_DOCS_RS[..])?.collect_addresses())
+    });
 }
 
 criterion_group!(benches, rrset_benchmark);
@@ -42,7 +42,7 @@ impl<'s, 'a: 's> Cursor<'a> {
                 self.buf = unsafe { self.buf.get_unchecked(..self.pos + size) };
                 Ok(())
             } else {
-                Err(Error::EndOfBuffer)
+                Err(Error::EndOfBuffer { offset: self.pos })
             }
         } else {
             Err(Error::CursorAlreadyInWindow)
@@ -85,12 +85,7 @@ impl<'s, 'a: 's> Cursor<'a> {
     }
 
     pub fn len(&self) -> usize {
-        let capacity = self.capacity();
-        if self.pos < capacity {
-            capacity - self.pos
-        } else {
-            0
-        }
+        self.capacity().saturating_sub(self.pos)
     }
 
     #[inline]
@@ -129,6 +124,11 @@ impl<'s, 'a: 's> Cursor<'a> {
         }
     }
 
+    #[inline]
+    pub fn remaining(&self) -> &'a [u8] {
+        self.buf.get(self.pos..).unwrap_or_default()
+    }
+
     pub fn slice(&'s mut self, size: usize) -> Result<&'a [u8]> {
         if self.len() >= size {
             let pos = self.pos;
@@ -142,9 +142,9 @@ impl<'s, 'a: 's> Cursor<'a> {
     #[inline]
     fn bound_error(&self) -> Error {
         if self.orig.is_none() {
-            Error::EndOfBuffer
+            Error::EndOfBuffer { offset: self.pos }
         } else {
-            Error::EndOfWindow
+            Error::EndOfWindow { offset: self.pos }
         }
     }
 }
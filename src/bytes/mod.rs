@@ -7,10 +7,8 @@ pub use cursor::*;
 mod reader;
 pub use reader::*;
 
-cfg_any_client! {
-    mod wcursor;
-    pub use wcursor::*;
+mod wcursor;
+pub use wcursor::*;
 
-    mod writer;
-    pub use writer::*;
-}
+mod writer;
+pub use writer::*;
@@ -24,14 +24,15 @@ impl<'a> WCursor<'a> {
         new_pos
     }
 
+    #[inline]
+    pub fn set_pos(&mut self, pos: usize) {
+        debug_assert!(pos <= self.capacity());
+        self.pos = pos;
+    }
+
     #[inline]
     pub fn len(&self) -> usize {
-        let capacity = self.capacity();
-        if self.pos < capacity {
-            capacity - self.pos
-        } else {
-            0
-        }
+        self.capacity().saturating_sub(self.pos)
     }
 
     #[inline]
@@ -39,6 +40,12 @@ impl<'a> WCursor<'a> {
         self.buf.len()
     }
 
+    /// Returns the bytes written from position `start` up to the current position.
+    #[inline]
+    pub fn written_from(&mut self, start: usize) -> &mut [u8] {
+        &mut self.buf[start..self.pos]
+    }
+
     #[inline]
     pub fn slice(&mut self, size: usize) -> Result<&mut [u8]> {
         if self.len() >= size {
@@ -76,6 +83,13 @@ impl<'a> WCursor<'a> {
         self.pos += 1;
     }
 
+    #[inline]
+    pub fn bytes(&mut self, buf: &[u8]) -> Result<()> {
+        self.slice(buf.len())?.copy_from_slice(buf);
+        self.pos += buf.len();
+        Ok(())
+    }
+
     #[inline]
     pub unsafe fn bytes_unchecked(&mut self, buf: &[u8]) {
         self.buf
@@ -1,3 +1,8 @@
 pub trait Writer<T> {
     fn write(&mut self, o: &T) -> crate::Result<usize>;
 }
+
+pub trait RrDataWriter<T> {
+    fn rr_data_len(rd: &T) -> usize;
+    fn write_rr_data(&mut self, rd: &T) -> crate::Result<()>;
+}
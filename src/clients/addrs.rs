@@ -0,0 +1,73 @@
+use crate::records::{
+    data::{Aaaa, A},
+    RecordSet,
+};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// The `A` and `AAAA` record sets of a host, as returned by `Client::lookup_host_rrsets`.
+///
+/// A record set which is missing, or was not queried, is `None`.
+pub type HostRecordSets = (Option<RecordSet<A>>, Option<RecordSet<Aaaa>>);
+
+/// Splits a list of IP addresses by address family.
+///
+/// This is useful with the result of `Client::lookup_host`, which merges the addresses of both
+/// families into a single list. The relative order of the addresses of each family is kept.
+///
+/// The addresses carry no TTL. To cache the addresses of each family with its own expiry,
+/// use `Client::lookup_host_rrsets`, which returns the record sets of both families.
+///
+/// # Examples
+///
+/// ```
+/// # use rsdns::clients::partition_by_family;
+/// # use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+/// #
+/// let addrs = vec![
+///     IpAddr::from(Ipv6Addr::LOCALHOST),
+///     IpAddr::from(Ipv4Addr::new(192, 0, 2, 1)),
+///     IpAddr::from(Ipv4Addr::new(192, 0, 2, 2)),
+/// ];
+///
+/// let (v4, v6) = partition_by_family(addrs);
+/// assert_eq!(v4, [Ipv4Addr::new(192, 0, 2, 1), Ipv4Addr::new(192, 0, 2, 2)]);
+/// assert_eq!(v6, [Ipv6Addr::LOCALHOST]);
+/// ```
+pub fn partition_by_family<I>(addrs: I) -> (Vec<Ipv4Addr>, Vec<Ipv6Addr>)
+where
+    I: IntoIterator<Item = IpAddr>,
+{
+    let mut v4 = Vec::new();
+    let mut v6 = Vec::new();
+    for addr in addrs {
+        match addr {
+            IpAddr::V4(a) => v4.push(a),
+            IpAddr::V6(a) => v6.push(a),
+        }
+    }
+    (v4, v6)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partition_by_family() {
+        let (v4, v6) = partition_by_family(Vec::new());
+        assert!(v4.is_empty());
+        assert!(v6.is_empty());
+
+        let a1 = Ipv4Addr::new(192, 0, 2, 1);
+        let a2 = Ipv4Addr::new(192, 0, 2, 2);
+        let b1 = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+        let b2 = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 2);
+        let addrs = [
+            IpAddr::from(b1),
+            IpAddr::from(a2),
+            IpAddr::from(b2),
+            IpAddr::from(a1),
+        ];
+        assert_eq!(partition_by_family(addrs), (vec![a2, a1], vec![b1, b2]));
+    }
+}
@@ -0,0 +1,32 @@
+/// IP address families of host lookups.
+///
+/// Defines which address record types are queried by host lookups, e.g. `Client::lookup_host`.
+/// Restricting lookups to a single address family saves a query on networks where the other
+/// family is not routable, or where `AAAA` queries are mishandled.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum AddressFamily {
+    /// Both `A` and `AAAA` records are queried.
+    ///
+    /// This is the default behavior.
+    Both,
+
+    /// Only `A` records are queried.
+    Ipv4Only,
+
+    /// Only `AAAA` records are queried.
+    Ipv6Only,
+}
+
+impl AddressFamily {
+    /// Checks if IPv4 addresses are looked up.
+    #[inline]
+    pub fn has_ipv4(self) -> bool {
+        self != Self::Ipv6Only
+    }
+
+    /// Checks if IPv6 addresses are looked up.
+    #[inline]
+    pub fn has_ipv6(self) -> bool {
+        self != Self::Ipv4Only
+    }
+}
@@ -1,14 +1,46 @@
 //! Defines configuration for clients.
+#[cfg(feature = "socks5")]
+use crate::clients::Socks5Proxy;
 use crate::{
-    clients::{EDns, ProtocolStrategy, Recursion},
-    constants::{DNS_MESSAGE_BUFFER_MIN_LENGTH, DNS_MESSAGE_MAX_LENGTH},
+    clients::{
+        lookup::check_answer_owners, AddressFamily, EDns, HostnameCheck, IpPreference,
+        ProtocolStrategy, Recursion,
+    },
+    constants::{DNS_MESSAGE_BUFFER_MIN_LENGTH, DNS_MESSAGE_MAX_LENGTH, DOMAIN_NAME_MAX_LENGTH},
+    message::{
+        reader::{MessageIterator, MessageReader},
+        RCode,
+    },
+    names::{is_valid_hostname, Name},
+    records::{EdnsOption, Opt},
     Error, Result,
 };
 use std::{
     net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
+/// The maximum length of the `OPT` record built by [`ClientConfig::opt`].
+#[allow(dead_code)] // clients module may be disabled
+pub(crate) const QUERY_OPT_MAX_LENGTH: usize = 11 // the record without options
+    + 4 // NSID
+    + 4 // EXPIRE
+    + 4 + DOMAIN_NAME_MAX_LENGTH // CHAIN
+    + 3 * (4 + UNDERSTOOD_ALGORITHMS_MAX_COUNT); // DAU, DHU and N3U
+
+/// The maximum number of algorithms in each of the `DAU`, `DHU` and `N3U` options,
+/// i.e. the number of distinct algorithm numbers.
+const UNDERSTOOD_ALGORITHMS_MAX_COUNT: usize = 256;
+
+fn check_understood_algorithms(algorithms: &Option<Vec<u8>>) -> Result<()> {
+    match algorithms {
+        Some(a) if a.len() > UNDERSTOOD_ALGORITHMS_MAX_COUNT => {
+            Err(Error::BadParam("too many understood algorithms"))
+        }
+        _ => Ok(()),
+    }
+}
+
 #[cfg(all(target_os = "linux", feature = "net-tokio", feature = "socket2"))]
 const INTERFACE_NAME_MAX_LENGTH: usize = 16; // socket(7), IFNAMSIZ
 
@@ -16,6 +48,27 @@ const INTERFACE_NAME_MAX_LENGTH: usize = 16; // socket(7), IFNAMSIZ
 type InterfaceName = arrayvec::ArrayString<INTERFACE_NAME_MAX_LENGTH>;
 
 /// Configuration for clients.
+///
+/// A configuration is built by chaining its setters, starting from [`ClientConfig::new`] or
+/// [`ClientConfig::with_nameserver`]. Every `set_*` method takes the configuration by value and
+/// returns the updated configuration. The few setters validating their input return
+/// [`Result`], and are chained with the `?` operator.
+///
+/// # Examples
+///
+/// ```rust
+/// # use rsdns::clients::{ClientConfig, ProtocolStrategy, Recursion};
+/// # use std::{net::{Ipv4Addr, SocketAddr}, time::Duration};
+/// let nameserver = SocketAddr::from((Ipv4Addr::new(192, 0, 2, 53), 53));
+/// let conf = ClientConfig::with_nameserver(nameserver)
+///     .set_query_lifetime(Duration::from_secs(5))
+///     .set_query_timeout(Some(Duration::from_millis(500)))
+///     .set_protocol_strategy(ProtocolStrategy::Udp)
+///     .set_recursion(Recursion::On)
+///     .set_buffer_size(4096)
+///     .set_edns_fallback(true);
+/// assert_eq!(conf.query_lifetime(), Duration::from_secs(5));
+/// ```
 #[derive(Clone, Eq, PartialEq, Debug)]
 #[must_use]
 pub struct ClientConfig {
@@ -23,12 +76,41 @@ pub struct ClientConfig {
     pub(crate) bind_addr_: SocketAddr,
     #[cfg(all(target_os = "linux", feature = "net-tokio", feature = "socket2"))]
     pub(crate) interface_: InterfaceName,
+    #[cfg(all(target_os = "linux", feature = "socket2"))]
+    pub(crate) socket_recv_buffer_: Option<usize>,
+    #[cfg(all(target_os = "linux", feature = "socket2"))]
+    pub(crate) socket_send_buffer_: Option<usize>,
+    #[cfg(all(target_os = "linux", feature = "net-tokio", feature = "socket2"))]
+    pub(crate) tcp_fast_open_: bool,
+    pub(crate) connect_udp_: bool,
+    pub(crate) id_check_: bool,
     pub(crate) query_lifetime_: Duration,
     pub(crate) query_timeout_: Option<Duration>,
+    pub(crate) retransmit_jitter_: bool,
     pub(crate) protocol_strategy_: ProtocolStrategy,
     pub(crate) recursion_: Recursion,
     pub(crate) buffer_size_: usize,
+    pub(crate) max_tcp_response_size_: usize,
     pub(crate) edns_: EDns,
+    pub(crate) request_nsid_: bool,
+    pub(crate) request_expire_: bool,
+    pub(crate) request_chain_: Option<Name>,
+    pub(crate) dau_: Option<Vec<u8>>,
+    pub(crate) dhu_: Option<Vec<u8>>,
+    pub(crate) n3u_: Option<Vec<u8>>,
+    pub(crate) edns_fallback_: bool,
+    pub(crate) strict_edns_: bool,
+    pub(crate) lowercase_qname_: bool,
+    pub(crate) require_fqdn_: bool,
+    pub(crate) hostname_check_: HostnameCheck,
+    pub(crate) follow_cname_: bool,
+    pub(crate) max_cname_hops_: u8,
+    pub(crate) strict_owner_check_: bool,
+    pub(crate) strict_trailing_bytes_: bool,
+    pub(crate) ip_preference_: IpPreference,
+    pub(crate) address_family_: AddressFamily,
+    #[cfg(feature = "socks5")]
+    pub(crate) socks5_proxy_: Option<Socks5Proxy>,
 }
 
 impl ClientConfig {
@@ -51,6 +133,49 @@ impl ClientConfig {
         }
     }
 
+    /// Creates a minimal configuration for a local stub resolver.
+    ///
+    /// This is a preset for querying a local caching resolver, e.g. `systemd-resolved` or
+    /// `dnsmasq`, which is expected to answer quickly and reliably. It is equivalent to
+    /// [`with_nameserver`] followed by:
+    ///
+    /// - [`set_protocol_strategy`]`(`[`ProtocolStrategy::NoTcp`]`)` - UDP only
+    /// - [`set_recursion`]`(`[`Recursion::On`]`)` - recursion desired
+    /// - [`set_edns`]`(`[`EDns::Off`]`)` - no `OPT` pseudo-record
+    /// - [`set_query_timeout`]`(None)` - a single attempt, without retransmission
+    /// - [`set_query_lifetime`]`(2 sec)` - a short timeout
+    ///
+    /// All other settings keep their defaults. Any of them may be customized by chaining
+    /// setters on the returned configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use rsdns::clients::{ClientConfig, EDns, ProtocolStrategy};
+    /// # use std::{net::{Ipv4Addr, SocketAddr}, time::Duration};
+    /// let stub = SocketAddr::from((Ipv4Addr::new(127, 0, 0, 53), 53));
+    /// let conf = ClientConfig::minimal(stub);
+    /// assert_eq!(conf.protocol_strategy(), ProtocolStrategy::NoTcp);
+    /// assert_eq!(conf.edns(), EDns::Off);
+    /// assert_eq!(conf.query_timeout(), None);
+    /// assert_eq!(conf.query_lifetime(), Duration::from_secs(2));
+    /// ```
+    ///
+    /// [`with_nameserver`]: Self::with_nameserver
+    /// [`set_protocol_strategy`]: Self::set_protocol_strategy
+    /// [`set_recursion`]: Self::set_recursion
+    /// [`set_edns`]: Self::set_edns
+    /// [`set_query_timeout`]: Self::set_query_timeout
+    /// [`set_query_lifetime`]: Self::set_query_lifetime
+    pub fn minimal(nameserver: SocketAddr) -> ClientConfig {
+        Self::with_nameserver(nameserver)
+            .set_protocol_strategy(ProtocolStrategy::NoTcp)
+            .set_recursion(Recursion::On)
+            .set_edns(EDns::Off)
+            .set_query_timeout(None)
+            .set_query_lifetime(Duration::from_secs(2))
+    }
+
     /// Checks if the nameserver is specified.
     ///
     /// The default configuration doesn't specify a nameserver.
@@ -214,6 +339,207 @@ impl ClientConfig {
         Ok(self)
     }
 
+    /// Sets the interface to bind to by its index.
+    ///
+    /// The index is resolved to the interface name (see `if_indextoname(3)`) when this method is
+    /// called, and the name is used as in [`set_bind_device`]. This is handy when only the
+    /// interface index is known, e.g. as returned by `if_nametoindex(3)`.
+    ///
+    /// See [`bind_device`] for more information.
+    ///
+    /// # Returns
+    ///
+    /// - [`Error::BadParam`] - if there is no interface with the specified index
+    ///
+    /// [`bind_device`]: Self::bind_device
+    /// [`set_bind_device`]: Self::set_bind_device
+    #[cfg(all(target_os = "linux", feature = "net-tokio", feature = "socket2"))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(all(target_os = "linux", feature = "net-tokio", feature = "socket2")))
+    )]
+    pub fn set_bind_device_index(self, index: u32) -> Result<Self> {
+        let mut buf = [0 as libc::c_char; libc::IF_NAMESIZE];
+        let res = unsafe { libc::if_indextoname(index, buf.as_mut_ptr()) };
+        if res.is_null() {
+            return Err(Error::BadParam("unknown interface index"));
+        }
+
+        let name = unsafe { std::ffi::CStr::from_ptr(buf.as_ptr()) };
+        let name = name
+            .to_str()
+            .map_err(|_| Error::BadParam("interface name is not valid UTF-8"))?;
+        self.set_bind_device(Some(name))
+    }
+
+    /// Returns the requested size of the UDP socket receive buffer.
+    ///
+    /// If set, the `SO_RCVBUF` socket option is set on the UDP socket of a client
+    /// (see `socket(7)` man page). A larger buffer reduces the chance of dropped responses
+    /// under high query load. The option is applied by all clients.
+    ///
+    /// Note that the OS may adjust the requested size, e.g. Linux doubles it and clamps it
+    /// to the `net.core.rmem_max` limit.
+    ///
+    /// Default: `None` - the OS default is used.
+    #[cfg(all(target_os = "linux", feature = "socket2"))]
+    #[cfg_attr(docsrs, doc(cfg(all(target_os = "linux", feature = "socket2"))))]
+    pub fn socket_recv_buffer(&self) -> Option<usize> {
+        self.socket_recv_buffer_
+    }
+
+    /// Sets the requested size of the UDP socket receive buffer.
+    ///
+    /// See [`socket_recv_buffer`] for more information.
+    ///
+    /// [`socket_recv_buffer`]: Self::socket_recv_buffer
+    #[cfg(all(target_os = "linux", feature = "socket2"))]
+    #[cfg_attr(docsrs, doc(cfg(all(target_os = "linux", feature = "socket2"))))]
+    pub fn set_socket_recv_buffer(mut self, size: Option<usize>) -> Self {
+        self.socket_recv_buffer_ = size;
+        self
+    }
+
+    /// Returns the requested size of the UDP socket send buffer.
+    ///
+    /// If set, the `SO_SNDBUF` socket option is set on the UDP socket of a client
+    /// (see `socket(7)` man page). The option is applied by all clients.
+    ///
+    /// Note that the OS may adjust the requested size, e.g. Linux doubles it and clamps it
+    /// to the `net.core.wmem_max` limit.
+    ///
+    /// Default: `None` - the OS default is used.
+    #[cfg(all(target_os = "linux", feature = "socket2"))]
+    #[cfg_attr(docsrs, doc(cfg(all(target_os = "linux", feature = "socket2"))))]
+    pub fn socket_send_buffer(&self) -> Option<usize> {
+        self.socket_send_buffer_
+    }
+
+    /// Sets the requested size of the UDP socket send buffer.
+    ///
+    /// See [`socket_send_buffer`] for more information.
+    ///
+    /// [`socket_send_buffer`]: Self::socket_send_buffer
+    #[cfg(all(target_os = "linux", feature = "socket2"))]
+    #[cfg_attr(docsrs, doc(cfg(all(target_os = "linux", feature = "socket2"))))]
+    pub fn set_socket_send_buffer(mut self, size: Option<usize>) -> Self {
+        self.socket_send_buffer_ = size;
+        self
+    }
+
+    /// Sets the requested socket buffer sizes on a UDP socket.
+    #[cfg(all(target_os = "linux", feature = "socket2"))]
+    #[allow(dead_code)] // clients module may be disabled
+    pub(crate) fn set_socket_buffers(&self, sock: &std::net::UdpSocket) -> Result<()> {
+        let sock = socket2::SockRef::from(sock);
+        if let Some(size) = self.socket_recv_buffer_ {
+            sock.set_recv_buffer_size(size)?;
+        }
+        if let Some(size) = self.socket_send_buffer_ {
+            sock.set_send_buffer_size(size)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the TCP Fast Open configuration.
+    ///
+    /// If enabled, the `TCP_FASTOPEN_CONNECT` socket option is set on the TCP socket of a client
+    /// (see `tcp(7)` man page). This allows the query to be sent in the `SYN` packet, saving a
+    /// round-trip, once a Fast Open cookie of the nameserver is known to the kernel
+    /// ([RFC 7413]).
+    ///
+    /// If the socket option is not supported by the kernel, a regular TCP connection is used.
+    ///
+    /// Default: `false`
+    ///
+    /// [RFC 7413]: https://www.rfc-editor.org/rfc/rfc7413.html
+    #[cfg(all(target_os = "linux", feature = "net-tokio", feature = "socket2"))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(all(target_os = "linux", feature = "net-tokio", feature = "socket2")))
+    )]
+    pub fn tcp_fast_open(&self) -> bool {
+        self.tcp_fast_open_
+    }
+
+    /// Sets the TCP Fast Open configuration.
+    ///
+    /// See [`tcp_fast_open`] for more information.
+    ///
+    /// [`tcp_fast_open`]: Self::tcp_fast_open
+    #[cfg(all(target_os = "linux", feature = "net-tokio", feature = "socket2"))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(all(target_os = "linux", feature = "net-tokio", feature = "socket2")))
+    )]
+    pub fn set_tcp_fast_open(mut self, enable: bool) -> Self {
+        self.tcp_fast_open_ = enable;
+        self
+    }
+
+    /// Returns the UDP socket connection configuration.
+    ///
+    /// If enabled, the UDP socket of a client is connected to the nameserver (see `connect(2)`
+    /// man page). The kernel then drops datagrams arriving from other sources, and reports
+    /// ICMP errors, e.g. port unreachable, to the client as I/O errors instead of letting the
+    /// query time out.
+    ///
+    /// If disabled, queries are sent with `sendto(2)` from an unconnected socket, and responses
+    /// arriving from an address other than the nameserver are discarded by the client rather
+    /// than by the kernel. ICMP errors are not reported, so a query to an unreachable nameserver
+    /// times out. This mode leaves the socket free for sending to multiple addresses.
+    ///
+    /// Default: `true`
+    pub fn connect_udp(&self) -> bool {
+        self.connect_udp_
+    }
+
+    /// Sets the UDP socket connection configuration.
+    ///
+    /// See [`connect_udp`] for more information.
+    ///
+    /// [`connect_udp`]: Self::connect_udp
+    pub fn set_connect_udp(mut self, connect_udp: bool) -> Self {
+        self.connect_udp_ = connect_udp;
+        self
+    }
+
+    /// Returns the response message ID check configuration.
+    ///
+    /// If enabled, a UDP response is accepted only if its message ID equals the ID of the query.
+    /// Responses with another ID are discarded, and the client keeps waiting for the response
+    /// until the query times out.
+    ///
+    /// If disabled, the message ID of a UDP response is not checked. This is intended for
+    /// transparent proxies that rewrite message IDs, and guarantee that responses are
+    /// correlated with queries by other means. Responses received over TCP are not checked
+    /// in either case, as the connection is used for a single query.
+    ///
+    /// # Security
+    ///
+    /// The random message ID is the main defense of a client against off-path spoofing
+    /// ([RFC 5452]). With the check disabled, an attacker able to guess the source port of
+    /// the query, and to send a datagram with the source address of the nameserver, can inject
+    /// a forged response that answers the same question. Disable the check only when the path to
+    /// the nameserver is trusted, e.g. a proxy on the loopback interface.
+    ///
+    /// Default: `true`
+    ///
+    /// [RFC 5452]: https://www.rfc-editor.org/rfc/rfc5452.html
+    pub fn id_check(&self) -> bool {
+        self.id_check_
+    }
+
+    /// Sets the response message ID check configuration.
+    ///
+    /// See [`id_check`] for more information.
+    ///
+    /// [`id_check`]: Self::id_check
+    pub fn set_id_check(mut self, id_check: bool) -> Self {
+        self.id_check_ = id_check;
+        self
+    }
+
     /// Returns the query lifetime duration.
     ///
     /// Query lifetime duration is the upper bound on the overall query duration, including all
@@ -261,6 +587,40 @@ impl ClientConfig {
         self
     }
 
+    /// Returns the retransmission jitter configuration.
+    ///
+    /// When enabled, the [`query_timeout`] of every UDP query transmission is randomized
+    /// in the range of ±25% of the configured value. This prevents many clients, which
+    /// retransmit on a fixed interval, from synchronizing and overloading a server.
+    ///
+    /// Default: `false` - the configured [`query_timeout`] is used as is
+    ///
+    /// [`query_timeout`]: Self::query_timeout
+    pub fn retransmit_jitter(&self) -> bool {
+        self.retransmit_jitter_
+    }
+
+    /// Sets the retransmission jitter configuration.
+    ///
+    /// See [`retransmit_jitter`] for more information.
+    ///
+    /// [`retransmit_jitter`]: Self::retransmit_jitter
+    pub fn set_retransmit_jitter(mut self, retransmit_jitter: bool) -> Self {
+        self.retransmit_jitter_ = retransmit_jitter;
+        self
+    }
+
+    /// Returns the timeout of a single UDP query transmission, with jitter applied if enabled.
+    #[allow(dead_code)] // clients module may be disabled
+    pub(crate) fn retransmit_timeout(&self) -> Option<Duration> {
+        let timeout = self.query_timeout_?;
+        if self.retransmit_jitter_ {
+            Some(timeout.mul_f64(rand::random::<f64>() * 0.5 + 0.75))
+        } else {
+            Some(timeout)
+        }
+    }
+
     /// Returns the protocol strategy.
     ///
     /// See [`ProtocolStrategy`] for more information.
@@ -299,6 +659,19 @@ impl ClientConfig {
         self
     }
 
+    /// Returns the query lifetime, clamped to not exceed the optional `deadline`.
+    #[allow(dead_code)] // clients module may be disabled
+    #[inline]
+    pub(crate) fn lifetime_until(&self, deadline: Option<Instant>) -> Result<Duration> {
+        match deadline {
+            Some(deadline) => match deadline.checked_duration_since(Instant::now()) {
+                Some(left) if !left.is_zero() => Ok(left.min(self.query_lifetime_)),
+                _ => Err(Error::Timeout),
+            },
+            None => Ok(self.query_lifetime_),
+        }
+    }
+
     #[allow(dead_code)] // clients module may be disabled
     #[inline]
     pub(crate) fn check(&self) -> Result<()> {
@@ -387,6 +760,49 @@ impl ClientConfig {
         self
     }
 
+    /// Returns the maximal size (in bytes) of a response accepted over TCP.
+    ///
+    /// In DNS-over-TCP protocol a message is preceded by its length. A client reads the length
+    /// first, and fails the query with [`Error::ResponseTooLong`] if it exceeds this limit.
+    /// The message itself is not read in this case.
+    ///
+    /// This protects a client from a hostile nameserver announcing a large message and
+    /// sending it slowly. Note that in any case the response must fit in the buffer used for
+    /// the query (see [`buffer_size`]).
+    ///
+    /// Default: `65535`
+    ///
+    /// [`buffer_size`]: Self::buffer_size
+    pub fn max_tcp_response_size(&self) -> usize {
+        self.max_tcp_response_size_
+    }
+
+    /// Sets the maximal size (in bytes) of a response accepted over TCP.
+    ///
+    /// See [`max_tcp_response_size`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use rsdns::clients::ClientConfig;
+    /// let mut conf = ClientConfig::new();
+    /// assert_eq!(conf.max_tcp_response_size(), 65535);
+    ///
+    /// conf = conf.set_max_tcp_response_size(8192);
+    /// assert_eq!(conf.max_tcp_response_size(), 8192);
+    ///
+    /// // minimal size is 512 bytes
+    /// conf = conf.set_max_tcp_response_size(64);
+    /// assert_eq!(conf.max_tcp_response_size(), 512);
+    /// ```
+    ///
+    /// [`max_tcp_response_size`]: Self::max_tcp_response_size
+    pub fn set_max_tcp_response_size(mut self, size: usize) -> Self {
+        self.max_tcp_response_size_ =
+            size.clamp(DNS_MESSAGE_BUFFER_MIN_LENGTH, DNS_MESSAGE_MAX_LENGTH);
+        self
+    }
+
     /// Returns the EDNS configuration.
     ///
     /// Specifies if to send the `OPT` pseudo-record in the query.
@@ -411,6 +827,651 @@ impl ClientConfig {
         self
     }
 
+    /// Returns the maximal size (in bytes) of a UDP response the nameserver may send.
+    ///
+    /// This is the UDP payload size advertised in the `OPT` pseudo-record of the query, or `512`
+    /// if [`edns`] is disabled. A payload size less than `512` is treated as `512`
+    /// ([RFC 6891 section 6.2.5]). A buffer of this size fits any UDP response, which is
+    /// useful for sizing buffers passed to [`Client::query_raw`].
+    ///
+    /// Note that the advertised payload size is also limited by the length of the buffer
+    /// passed to the query. Responses received over TCP may be larger, up to
+    /// [`max_tcp_response_size`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use rsdns::clients::{ClientConfig, EDns};
+    /// let conf = ClientConfig::new();
+    /// assert_eq!(conf.max_udp_response(), 1232);
+    ///
+    /// let conf = conf.set_edns(EDns::Off);
+    /// assert_eq!(conf.max_udp_response(), 512);
+    /// ```
+    ///
+    /// [`edns`]: Self::edns
+    /// [`max_tcp_response_size`]: Self::max_tcp_response_size
+    /// [`Client::query_raw`]: crate::clients::tokio::Client::query_raw
+    /// [RFC 6891 section 6.2.5]: https://www.rfc-editor.org/rfc/rfc6891.html#section-6.2.5
+    pub fn max_udp_response(&self) -> usize {
+        match self.edns_ {
+            EDns::On {
+                udp_payload_size, ..
+            } => (udp_payload_size as usize).max(DNS_MESSAGE_BUFFER_MIN_LENGTH),
+            EDns::Off => DNS_MESSAGE_BUFFER_MIN_LENGTH,
+        }
+    }
+
+    /// Returns the name server identifier request configuration.
+    ///
+    /// When enabled, the `OPT` pseudo-record of the query includes an `NSID` option
+    /// ([RFC 5001]), requesting the server to return its identifier. This is useful to find out
+    /// which node of an anycast service answered the query. The identifier is available with
+    /// [`Opt::nsid`] when parsing the response.
+    ///
+    /// This configuration has effect only if [`edns`] is enabled.
+    ///
+    /// Default: `false`
+    ///
+    /// [RFC 5001]: https://www.rfc-editor.org/rfc/rfc5001.html
+    /// [`Opt::nsid`]: crate::records::Opt::nsid
+    /// [`edns`]: Self::edns
+    pub fn request_nsid(&self) -> bool {
+        self.request_nsid_
+    }
+
+    /// Sets the name server identifier request configuration.
+    ///
+    /// See [`request_nsid`] for more information.
+    ///
+    /// [`request_nsid`]: Self::request_nsid
+    pub fn set_request_nsid(mut self, request_nsid: bool) -> Self {
+        self.request_nsid_ = request_nsid;
+        self
+    }
+
+    /// Returns the zone expire request configuration.
+    ///
+    /// When enabled, the `OPT` pseudo-record of the query includes an `EXPIRE` option
+    /// ([RFC 7314]), requesting an authoritative server to return the number of seconds
+    /// remaining before the zone expires. This is mostly useful for `SOA` queries sent by
+    /// secondary server tooling. The value is available with [`Opt::expire`] when parsing
+    /// the response.
+    ///
+    /// This configuration has effect only if [`edns`] is enabled.
+    ///
+    /// Default: `false`
+    ///
+    /// [RFC 7314]: https://www.rfc-editor.org/rfc/rfc7314.html
+    /// [`Opt::expire`]: crate::records::Opt::expire
+    /// [`edns`]: Self::edns
+    pub fn request_expire(&self) -> bool {
+        self.request_expire_
+    }
+
+    /// Sets the zone expire request configuration.
+    ///
+    /// See [`request_expire`] for more information.
+    ///
+    /// [`request_expire`]: Self::request_expire
+    pub fn set_request_expire(mut self, request_expire: bool) -> Self {
+        self.request_expire_ = request_expire;
+        self
+    }
+
+    /// Returns the chain query request configuration.
+    ///
+    /// When set, the `OPT` pseudo-record of the query includes a `CHAIN` option ([RFC 7901])
+    /// holding the closest trust point known to the client, e.g. the root zone. This requests
+    /// a validating resolver to include all records needed to validate the answer, starting from
+    /// the trust point. The trust point echoed by the resolver is available with [`Opt::chain`]
+    /// when parsing the response.
+    ///
+    /// This configuration has effect only if [`edns`] is enabled.
+    ///
+    /// Default: `None`
+    ///
+    /// [RFC 7901]: https://www.rfc-editor.org/rfc/rfc7901.html
+    /// [`Opt::chain`]: crate::records::Opt::chain
+    /// [`edns`]: Self::edns
+    pub fn request_chain(&self) -> Option<&Name> {
+        self.request_chain_.as_ref()
+    }
+
+    /// Sets the chain query request configuration.
+    ///
+    /// See [`request_chain`] for more information.
+    ///
+    /// [`request_chain`]: Self::request_chain
+    pub fn set_request_chain(mut self, trust_point: Option<Name>) -> Self {
+        self.request_chain_ = trust_point;
+        self
+    }
+
+    /// Returns the DNSSEC algorithm understood configuration.
+    ///
+    /// When set, the `OPT` pseudo-record of the query includes a `DAU` option ([RFC 6975])
+    /// listing the numbers of the `DNSKEY` signing algorithms understood by the client.
+    /// Together with [`dhu`] and [`n3u`], this allows zone operators to measure the algorithm
+    /// support of validating resolvers, e.g. before an algorithm rollover. The option is
+    /// available with [`Opt::dau`] when parsing a message.
+    ///
+    /// At most 256 algorithms may be listed, the number of distinct algorithm numbers.
+    ///
+    /// This configuration has effect only if [`edns`] is enabled.
+    ///
+    /// Default: `None`
+    ///
+    /// [RFC 6975]: https://www.rfc-editor.org/rfc/rfc6975.html
+    /// [`Opt::dau`]: crate::records::Opt::dau
+    /// [`dhu`]: Self::dhu
+    /// [`n3u`]: Self::n3u
+    /// [`edns`]: Self::edns
+    pub fn dau(&self) -> Option<&[u8]> {
+        self.dau_.as_deref()
+    }
+
+    /// Sets the DNSSEC algorithm understood configuration.
+    ///
+    /// See [`dau`] for more information.
+    ///
+    /// # Returns
+    ///
+    /// - [`Error::BadParam`] - if more than 256 algorithms are listed
+    ///
+    /// [`dau`]: Self::dau
+    pub fn set_dau(mut self, algorithms: Option<Vec<u8>>) -> Result<Self> {
+        check_understood_algorithms(&algorithms)?;
+        self.dau_ = algorithms;
+        Ok(self)
+    }
+
+    /// Returns the `DS` hash understood configuration.
+    ///
+    /// When set, the `OPT` pseudo-record of the query includes a `DHU` option ([RFC 6975])
+    /// listing the numbers of the `DS` digest algorithms understood by the client.
+    /// See [`dau`] for more information.
+    ///
+    /// Default: `None`
+    ///
+    /// [RFC 6975]: https://www.rfc-editor.org/rfc/rfc6975.html
+    /// [`dau`]: Self::dau
+    pub fn dhu(&self) -> Option<&[u8]> {
+        self.dhu_.as_deref()
+    }
+
+    /// Sets the `DS` hash understood configuration.
+    ///
+    /// See [`dhu`] for more information.
+    ///
+    /// # Returns
+    ///
+    /// - [`Error::BadParam`] - if more than 256 algorithms are listed
+    ///
+    /// [`dhu`]: Self::dhu
+    pub fn set_dhu(mut self, algorithms: Option<Vec<u8>>) -> Result<Self> {
+        check_understood_algorithms(&algorithms)?;
+        self.dhu_ = algorithms;
+        Ok(self)
+    }
+
+    /// Returns the `NSEC3` hash understood configuration.
+    ///
+    /// When set, the `OPT` pseudo-record of the query includes an `N3U` option ([RFC 6975])
+    /// listing the numbers of the `NSEC3` hash algorithms understood by the client.
+    /// See [`dau`] for more information.
+    ///
+    /// Default: `None`
+    ///
+    /// [RFC 6975]: https://www.rfc-editor.org/rfc/rfc6975.html
+    /// [`dau`]: Self::dau
+    pub fn n3u(&self) -> Option<&[u8]> {
+        self.n3u_.as_deref()
+    }
+
+    /// Sets the `NSEC3` hash understood configuration.
+    ///
+    /// See [`n3u`] for more information.
+    ///
+    /// # Returns
+    ///
+    /// - [`Error::BadParam`] - if more than 256 algorithms are listed
+    ///
+    /// [`n3u`]: Self::n3u
+    pub fn set_n3u(mut self, algorithms: Option<Vec<u8>>) -> Result<Self> {
+        check_understood_algorithms(&algorithms)?;
+        self.n3u_ = algorithms;
+        Ok(self)
+    }
+
+    /// Returns the EDNS fallback configuration.
+    ///
+    /// When enabled, a query is retried once without the `OPT` pseudo-record if the server
+    /// responds with `FORMERR` or `NOTIMP`. Some legacy servers respond so to queries with EDNS
+    /// ([RFC 6891 section 7]). The retry shares the query lifetime with the original query, and
+    /// uses a different message ID, so that a late response to the original query is discarded.
+    /// A message ID fixed with [`QueryOptions::set_msg_id`] is used for both queries.
+    ///
+    /// This configuration has effect only if [`edns`] is enabled.
+    ///
+    /// Default: `false`
+    ///
+    /// [RFC 6891 section 7]: https://www.rfc-editor.org/rfc/rfc6891.html#section-7
+    /// [`edns`]: Self::edns
+    /// [`QueryOptions::set_msg_id`]: crate::clients::QueryOptions::set_msg_id
+    pub fn edns_fallback(&self) -> bool {
+        self.edns_fallback_
+    }
+
+    /// Sets the EDNS fallback configuration.
+    ///
+    /// See [`edns_fallback`] for more information.
+    ///
+    /// [`edns_fallback`]: Self::edns_fallback
+    pub fn set_edns_fallback(mut self, edns_fallback: bool) -> Self {
+        self.edns_fallback_ = edns_fallback;
+        self
+    }
+
+    /// Returns the strict EDNS configuration.
+    ///
+    /// When enabled, a response with an `OPT` pseudo-record using an EDNS version other than `0`,
+    /// or setting reserved flags (any flag except `DO`), is rejected with
+    /// [`Error::UnsupportedEdnsVersion`] or [`Error::ReservedEdnsFlags`] respectively
+    /// ([RFC 6891 section 6.1.3]). This helps to catch server misbehavior in conformance tests.
+    /// When disabled, the EDNS version and flags of a response are not checked.
+    ///
+    /// See also [`MessageReader::set_strict_edns`] for parsing messages in strict mode.
+    ///
+    /// Default: `false`
+    ///
+    /// [RFC 6891 section 6.1.3]: https://www.rfc-editor.org/rfc/rfc6891.html#section-6.1.3
+    /// [`MessageReader::set_strict_edns`]: crate::message::reader::MessageReader::set_strict_edns
+    pub fn strict_edns(&self) -> bool {
+        self.strict_edns_
+    }
+
+    /// Sets the strict EDNS configuration.
+    ///
+    /// See [`strict_edns`] for more information.
+    ///
+    /// [`strict_edns`]: Self::strict_edns
+    pub fn set_strict_edns(mut self, strict_edns: bool) -> Self {
+        self.strict_edns_ = strict_edns;
+        self
+    }
+
+    /// Checks if a response to a query sent with EDNS requires a retry without EDNS.
+    #[allow(dead_code)] // clients module may be disabled
+    pub(crate) fn edns_fallback_required(&self, response: &[u8]) -> bool {
+        if !self.edns_fallback_ || self.edns_ == EDns::Off {
+            return false;
+        }
+        match MessageReader::new(response).and_then(|mut mr| mr.header()) {
+            Ok(header) => matches!(header.flags.response_code(), RCode::FORMERR | RCode::NOTIMP),
+            Err(_) => false,
+        }
+    }
+
+    /// Checks the EDNS version and flags of a response, if [`strict_edns`] is enabled.
+    ///
+    /// A response which cannot be parsed up to the `OPT` pseudo-record is not rejected here.
+    ///
+    /// [`strict_edns`]: Self::strict_edns
+    #[allow(dead_code)] // clients module may be disabled
+    pub(crate) fn check_strict_edns(&self, response: &[u8]) -> Result<()> {
+        if !self.strict_edns_ {
+            return Ok(());
+        }
+        match MessageIterator::new(response).and_then(|mi| mi.opt()) {
+            Ok(Some(opt)) => opt.check_strict(),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the EDNS options attached to queries, in the order they are written.
+    ///
+    /// This is a read-only view over the EDNS configuration, e.g. [`request_nsid`] and
+    /// [`request_expire`].
+    /// It is useful for verifying the configuration before queries are sent.
+    ///
+    /// Returns an empty vector if [`edns`] is disabled.
+    ///
+    /// [`request_nsid`]: Self::request_nsid
+    /// [`request_expire`]: Self::request_expire
+    /// [`edns`]: Self::edns
+    pub fn edns_options(&self) -> Vec<EdnsOption> {
+        self.opt(DNS_MESSAGE_MAX_LENGTH)
+            .map(|opt| opt.options())
+            .unwrap_or_default()
+    }
+
+    /// Returns the `OPT` pseudo-record attached to queries, if EDNS is enabled.
+    ///
+    /// The UDP payload size is limited by `max_udp_payload_size`, e.g. the size of the
+    /// response buffer.
+    #[inline]
+    pub(crate) fn opt(&self, max_udp_payload_size: usize) -> Option<Opt> {
+        match self.edns_ {
+            EDns::On {
+                version,
+                udp_payload_size,
+            } => {
+                let ups = (udp_payload_size as usize).min(max_udp_payload_size);
+                let mut opt = Opt::new(version, ups as u16);
+                opt.set_nsid_request(self.request_nsid_)
+                    .set_expire_request(self.request_expire_)
+                    .set_chain_request(self.request_chain_.clone())
+                    .set_dau(self.dau_.clone())
+                    .set_dhu(self.dhu_.clone())
+                    .set_n3u(self.n3u_.clone());
+                Some(opt)
+            }
+            EDns::Off => None,
+        }
+    }
+
+    /// Returns the query name case configuration.
+    ///
+    /// When enabled, ASCII letters of the query name are converted to lowercase before the query
+    /// is sent. This makes queries for the same name identical regardless of the case used by
+    /// the application, which is friendlier to caches along the way.
+    ///
+    /// Default: `false` - the query name is sent as provided
+    pub fn lowercase_qname(&self) -> bool {
+        self.lowercase_qname_
+    }
+
+    /// Sets the query name case configuration.
+    ///
+    /// See [`lowercase_qname`] for more information.
+    ///
+    /// [`lowercase_qname`]: Self::lowercase_qname
+    pub fn set_lowercase_qname(mut self, lowercase_qname: bool) -> Self {
+        self.lowercase_qname_ = lowercase_qname;
+        self
+    }
+
+    /// Returns the fully qualified query name configuration.
+    ///
+    /// *rsdns* doesn't implement search domains, so a query name is always absolute.
+    /// By default, the root label is appended to a query name without the trailing dot,
+    /// i.e. `"example.com"` and `"example.com."` are the same name.
+    ///
+    /// When enabled, a query name without the trailing dot is rejected with
+    /// [`Error::BadParam`](crate::Error::BadParam). This is useful for applications which
+    /// implement the search domains themselves, and want relative names to never reach
+    /// the nameserver by mistake.
+    ///
+    /// Default: `false`
+    pub fn require_fqdn(&self) -> bool {
+        self.require_fqdn_
+    }
+
+    /// Sets the fully qualified query name configuration.
+    ///
+    /// See [`require_fqdn`] for more information.
+    ///
+    /// [`require_fqdn`]: Self::require_fqdn
+    pub fn set_require_fqdn(mut self, require_fqdn: bool) -> Self {
+        self.require_fqdn_ = require_fqdn;
+        self
+    }
+
+    /// Returns the query name validation configuration.
+    ///
+    /// When enabled, a query name which is not a valid host name is rejected with
+    /// [`Error::BadParam`](crate::Error::BadParam) before a query is sent. This allows
+    /// applications to fail fast on user input, instead of sending queries for names which
+    /// cannot exist. See [`HostnameCheck`] for more information.
+    ///
+    /// The root name `.` is always accepted.
+    ///
+    /// Default: [`HostnameCheck::Off`]
+    pub fn hostname_check(&self) -> HostnameCheck {
+        self.hostname_check_
+    }
+
+    /// Sets the query name validation configuration.
+    ///
+    /// See [`hostname_check`] for more information.
+    ///
+    /// [`hostname_check`]: Self::hostname_check
+    pub fn set_hostname_check(mut self, hostname_check: HostnameCheck) -> Self {
+        self.hostname_check_ = hostname_check;
+        self
+    }
+
+    /// Checks a query name according to [`hostname_check`].
+    ///
+    /// [`hostname_check`]: Self::hostname_check
+    #[allow(dead_code)] // clients module may be disabled
+    pub(crate) fn check_qname(&self, qname: &str) -> Result<()> {
+        let allow_underscore = match self.hostname_check_ {
+            HostnameCheck::Off => return Ok(()),
+            HostnameCheck::Ldh => false,
+            HostnameCheck::LdhUnderscore => true,
+        };
+        if qname == "." || is_valid_hostname(qname, allow_underscore) {
+            Ok(())
+        } else {
+            Err(Error::BadParam("query name is not a valid host name"))
+        }
+    }
+
+    /// Returns the CNAME following configuration.
+    ///
+    /// A recursive nameserver usually resolves the *CNAME chain* of the query name by itself,
+    /// and includes the records of the requested type at the canonical name in the answer.
+    /// However, the answer may end with a `CNAME` record, e.g. when the canonical name is
+    /// in a zone the nameserver is not willing to resolve.
+    ///
+    /// When enabled, [`Client::query_rrset`] follows up with a query for the requested type
+    /// at the last name of the *CNAME chain*, instead of failing with [`Error::NoAnswer`].
+    /// The number of follow-up queries is bounded by [`max_cname_hops`].
+    ///
+    /// Default: `false`
+    ///
+    /// [`Client::query_rrset`]: crate::clients::tokio::Client::query_rrset
+    /// [`Error::NoAnswer`]: crate::Error::NoAnswer
+    /// [`max_cname_hops`]: Self::max_cname_hops
+    pub fn follow_cname(&self) -> bool {
+        self.follow_cname_
+    }
+
+    /// Sets the CNAME following configuration.
+    ///
+    /// See [`follow_cname`] for more information.
+    ///
+    /// [`follow_cname`]: Self::follow_cname
+    pub fn set_follow_cname(mut self, follow_cname: bool) -> Self {
+        self.follow_cname_ = follow_cname;
+        self
+    }
+
+    /// Returns the maximal number of follow-up queries issued when following a *CNAME chain*.
+    ///
+    /// This configuration has effect only if [`follow_cname`] is enabled.
+    ///
+    /// Default: `8`
+    ///
+    /// [`follow_cname`]: Self::follow_cname
+    pub fn max_cname_hops(&self) -> u8 {
+        self.max_cname_hops_
+    }
+
+    /// Sets the maximal number of follow-up queries issued when following a *CNAME chain*.
+    ///
+    /// See [`max_cname_hops`] for more information.
+    ///
+    /// [`max_cname_hops`]: Self::max_cname_hops
+    pub fn set_max_cname_hops(mut self, max_cname_hops: u8) -> Self {
+        self.max_cname_hops_ = max_cname_hops;
+        self
+    }
+
+    /// Returns the strict owner name check configuration.
+    ///
+    /// A record in the answer section of a response is expected to be owned by the question
+    /// name, or by one of the names of the *CNAME chain* starting at it. A record owned by
+    /// another name is not part of the answer, and may be an attempt of cache poisoning.
+    ///
+    /// When enabled, a response with such a record in the answer section is rejected with
+    /// [`Error::UnrelatedOwnerName`]. When disabled, such records are ignored by the methods
+    /// returning records, i.e. [`Client::query_rrset`] and [`Client::query_any`].
+    ///
+    /// Default: `false`
+    ///
+    /// [`Client::query_rrset`]: crate::clients::tokio::Client::query_rrset
+    /// [`Client::query_any`]: crate::clients::tokio::Client::query_any
+    /// [`Error::UnrelatedOwnerName`]: crate::Error::UnrelatedOwnerName
+    pub fn strict_owner_check(&self) -> bool {
+        self.strict_owner_check_
+    }
+
+    /// Sets the strict owner name check configuration.
+    ///
+    /// See [`strict_owner_check`] for more information.
+    ///
+    /// [`strict_owner_check`]: Self::strict_owner_check
+    pub fn set_strict_owner_check(mut self, strict_owner_check: bool) -> Self {
+        self.strict_owner_check_ = strict_owner_check;
+        self
+    }
+
+    /// Checks the owner names of the answer section, if [`strict_owner_check`] is enabled.
+    ///
+    /// [`strict_owner_check`]: Self::strict_owner_check
+    #[allow(dead_code)] // clients module may be disabled
+    pub(crate) fn check_owner_names(&self, response: &[u8]) -> Result<()> {
+        if !self.strict_owner_check_ {
+            return Ok(());
+        }
+        check_answer_owners(response)
+    }
+
+    /// Returns the strict trailing bytes check configuration.
+    ///
+    /// A well-formed UDP response holds a single DNS message. Bytes following the last record
+    /// of the message are not part of it, e.g. a datagram carrying two concatenated messages.
+    ///
+    /// When enabled, a UDP response with trailing bytes is rejected with
+    /// [`Error::TrailingBytes`]. When disabled, trailing bytes are ignored.
+    ///
+    /// Default: `false`
+    ///
+    /// See also [`MessageReader::trailing_bytes`].
+    ///
+    /// [`Error::TrailingBytes`]: crate::Error::TrailingBytes
+    /// [`MessageReader::trailing_bytes`]: crate::message::reader::MessageReader::trailing_bytes
+    pub fn strict_trailing_bytes(&self) -> bool {
+        self.strict_trailing_bytes_
+    }
+
+    /// Sets the strict trailing bytes check configuration.
+    ///
+    /// See [`strict_trailing_bytes`] for more information.
+    ///
+    /// [`strict_trailing_bytes`]: Self::strict_trailing_bytes
+    pub fn set_strict_trailing_bytes(mut self, strict_trailing_bytes: bool) -> Self {
+        self.strict_trailing_bytes_ = strict_trailing_bytes;
+        self
+    }
+
+    /// Checks that a response has no trailing bytes, if [`strict_trailing_bytes`] is enabled.
+    ///
+    /// [`strict_trailing_bytes`]: Self::strict_trailing_bytes
+    #[allow(dead_code)] // clients module may be disabled
+    pub(crate) fn check_trailing_bytes(&self, response: &[u8]) -> Result<()> {
+        if !self.strict_trailing_bytes_ {
+            return Ok(());
+        }
+        let mut mr = MessageReader::new(response)?;
+        mr.header()?;
+        mr.skip_questions()?;
+        while mr.has_records() {
+            let marker = mr.record_marker()?;
+            mr.skip_record_data(&marker)?;
+        }
+        match mr.trailing_bytes() {
+            Some(0) | None => Ok(()),
+            Some(n) => Err(Error::TrailingBytes(n)),
+        }
+    }
+
+    /// Returns the IP address family preference.
+    ///
+    /// Specifies the order of addresses returned by host lookups.
+    /// See [`IpPreference`] for more information.
+    ///
+    /// Default: [`IpPreference::Ipv4`]
+    pub fn ip_preference(&self) -> IpPreference {
+        self.ip_preference_
+    }
+
+    /// Sets the IP address family preference.
+    ///
+    /// See [`ip_preference`] for more information.
+    ///
+    /// [`ip_preference`]: Self::ip_preference
+    pub fn set_ip_preference(mut self, preference: IpPreference) -> Self {
+        self.ip_preference_ = preference;
+        self
+    }
+
+    /// Returns the IP address families of host lookups.
+    ///
+    /// Specifies which address record types are queried by host lookups.
+    /// See [`AddressFamily`] for more information.
+    ///
+    /// Default: [`AddressFamily::Both`]
+    pub fn address_family(&self) -> AddressFamily {
+        self.address_family_
+    }
+
+    /// Sets the IP address families of host lookups.
+    ///
+    /// See [`address_family`] for more information.
+    ///
+    /// [`address_family`]: Self::address_family
+    pub fn set_address_family(mut self, family: AddressFamily) -> Self {
+        self.address_family_ = family;
+        self
+    }
+
+    /// Returns the SOCKS5 proxy used to reach the nameserver.
+    ///
+    /// If set, queries are tunneled to the nameserver through the proxy over TCP,
+    /// regardless of the [`protocol_strategy`]. UDP is not used in this case.
+    ///
+    /// The connection to the proxy is set up as a direct TCP connection to the nameserver
+    /// would be, e.g. it is bound to the configured bind device (`bind_device`).
+    /// [`bind_addr`] applies to the UDP socket only, and is not used with a proxy.
+    ///
+    /// See [`Socks5Proxy`] for more information.
+    ///
+    /// Default: `None`.
+    ///
+    /// [`protocol_strategy`]: Self::protocol_strategy
+    /// [`bind_addr`]: Self::bind_addr
+    #[cfg(feature = "socks5")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "socks5")))]
+    pub fn socks5_proxy(&self) -> Option<&Socks5Proxy> {
+        self.socks5_proxy_.as_ref()
+    }
+
+    /// Sets the SOCKS5 proxy used to reach the nameserver.
+    ///
+    /// See [`socks5_proxy`] for more information.
+    ///
+    /// [`socks5_proxy`]: Self::socks5_proxy
+    #[cfg(feature = "socks5")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "socks5")))]
+    pub fn set_socks5_proxy(mut self, proxy: Option<Socks5Proxy>) -> Self {
+        self.socks5_proxy_ = proxy;
+        self
+    }
+
     fn ipv4_unspecified() -> SocketAddr {
         SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0))
     }
@@ -427,15 +1488,64 @@ impl Default for ClientConfig {
             bind_addr_: Self::ipv4_unspecified(),
             #[cfg(all(target_os = "linux", feature = "net-tokio", feature = "socket2"))]
             interface_: InterfaceName::default(),
+            #[cfg(all(target_os = "linux", feature = "socket2"))]
+            socket_recv_buffer_: None,
+            #[cfg(all(target_os = "linux", feature = "socket2"))]
+            socket_send_buffer_: None,
+            #[cfg(all(target_os = "linux", feature = "net-tokio", feature = "socket2"))]
+            tcp_fast_open_: false,
+            connect_udp_: true,
+            id_check_: true,
             query_lifetime_: Duration::from_secs(10),
             query_timeout_: Some(Duration::from_secs(2)),
+            retransmit_jitter_: false,
             protocol_strategy_: ProtocolStrategy::Udp,
             recursion_: Recursion::On,
             buffer_size_: DNS_MESSAGE_MAX_LENGTH,
+            max_tcp_response_size_: DNS_MESSAGE_MAX_LENGTH,
             edns_: EDns::On {
                 version: 0,
                 udp_payload_size: 1232,
             },
+            request_nsid_: false,
+            request_expire_: false,
+            request_chain_: None,
+            dau_: None,
+            dhu_: None,
+            n3u_: None,
+            edns_fallback_: false,
+            strict_edns_: false,
+            lowercase_qname_: false,
+            require_fqdn_: false,
+            hostname_check_: HostnameCheck::Off,
+            follow_cname_: false,
+            max_cname_hops_: 8,
+            strict_owner_check_: false,
+            strict_trailing_bytes_: false,
+            ip_preference_: IpPreference::Ipv4,
+            address_family_: AddressFamily::Both,
+            #[cfg(feature = "socks5")]
+            socks5_proxy_: None,
         }
     }
 }
+
+#[cfg(all(test, target_os = "linux", feature = "net-tokio", feature = "socket2"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bind_device_index() {
+        let index = unsafe { libc::if_nametoindex(c"lo".as_ptr()) };
+        assert_ne!(index, 0);
+
+        let conf = ClientConfig::new().set_bind_device_index(index).unwrap();
+        assert_eq!(conf.bind_device(), Some("lo"));
+
+        let conf = conf.set_bind_device(None).unwrap();
+        assert_eq!(conf.bind_device(), None);
+
+        let res = ClientConfig::new().set_bind_device_index(0);
+        assert!(matches!(res, Err(Error::BadParam(_))), "{res:?}");
+    }
+}
@@ -8,7 +8,19 @@ pub enum EDns {
     On {
         /// The EDNS version.
         ///
+        /// A non-zero version may be used to test how a nameserver handles unsupported EDNS
+        /// versions. A server that doesn't implement the requested version responds with
+        /// [`BADVERS`] ([RFC 6891 section 6.1.3]). The version supported by the server is
+        /// reported in the `OPT` record of the response (see [`MessageIterator::opt`] and
+        /// [`Opt::version`]), and the full response code is obtained with [`RCode::extended`].
+        ///
         /// Default: `0`
+        ///
+        /// [`BADVERS`]: crate::message::RCode::BADVERS
+        /// [RFC 6891 section 6.1.3]: https://www.rfc-editor.org/rfc/rfc6891.html#section-6.1.3
+        /// [`MessageIterator::opt`]: crate::message::reader::MessageIterator::opt
+        /// [`Opt::version`]: crate::records::Opt::version
+        /// [`RCode::extended`]: crate::message::RCode::extended
         version: u8,
 
         /// Specifies the max size (in bytes) of UDP payload the client is capable of
@@ -0,0 +1,19 @@
+/// Query name validation.
+///
+/// Defines whether query names are checked to be valid host names before a query is sent.
+/// See [`is_valid_hostname`](crate::names::is_valid_hostname).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum HostnameCheck {
+    /// Query names are not checked.
+    ///
+    /// This is the default behavior.
+    Off,
+
+    /// Query names must be made of letters, digits and hyphens only (LDH).
+    Ldh,
+
+    /// Query names must be made of letters, digits, hyphens and underscores.
+    ///
+    /// This allows the service names used by `SRV` and `TXT` records, e.g. `_sip._tcp.example.com`.
+    LdhUnderscore,
+}
@@ -0,0 +1,13 @@
+/// IP address family preference.
+///
+/// Defines the order of addresses returned by host lookups, e.g. `Client::lookup_host`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum IpPreference {
+    /// IPv4 addresses are listed before IPv6 addresses.
+    ///
+    /// This is the default behavior.
+    Ipv4,
+
+    /// IPv6 addresses are listed before IPv4 addresses.
+    Ipv6,
+}
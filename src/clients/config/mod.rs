@@ -1,13 +1,27 @@
 //! Client configuration.
 
+mod address_family;
+pub use address_family::*;
+
 mod edns;
 pub use edns::*;
 
+mod hostname_check;
+pub use hostname_check::*;
+
+mod ip_preference;
+pub use ip_preference::*;
+
 mod protocol_strategy;
 pub use protocol_strategy::*;
 
 mod recursion;
 pub use recursion::*;
 
+#[cfg(feature = "socks5")]
+mod socks5_proxy;
+#[cfg(feature = "socks5")]
+pub use socks5_proxy::*;
+
 mod client_config;
 pub use client_config::*;
@@ -16,6 +16,22 @@ pub enum ProtocolStrategy {
     /// This setting forces usage of UDP only. Truncated responses are returned as is, without
     /// being retried.
     ///
+    /// `Client::query_raw` returns a truncated response with the `TC` flag set, and leaves it to
+    /// the caller to handle it. Methods which parse the response, e.g. `Client::query_rrset`
+    /// and `Client::lookup_host`, fail with [`Error::MessageTruncated`] instead of returning
+    /// a partial answer.
+    ///
+    /// Note that this is a runtime setting, and the TCP code of the clients is compiled in
+    /// regardless of the strategy. It only prevents the fallback to TCP on truncation.
+    /// TCP is still used when:
+    ///
+    /// - the strategy of a query is overridden with [`QueryOptions::set_protocol_strategy`]
+    /// - a SOCKS5 proxy is configured (`ClientConfig::set_socks5_proxy`), as queries are
+    ///   tunneled through the proxy over TCP
+    ///
     /// Queries that by definition are required to use only TCP are exempt from these rules.
+    ///
+    /// [`Error::MessageTruncated`]: crate::Error::MessageTruncated
+    /// [`QueryOptions::set_protocol_strategy`]: crate::clients::QueryOptions::set_protocol_strategy
     NoTcp,
 }
@@ -0,0 +1,81 @@
+use crate::{Error, Result};
+use std::net::SocketAddr;
+
+/// SOCKS5 proxy configuration.
+///
+/// Defines a [SOCKS5] proxy that clients use to reach the nameserver.
+/// Queries are tunneled through the proxy using the `CONNECT` command, i.e. over TCP.
+///
+/// Only the *no authentication* and *username/password* ([RFC 1929]) authentication methods
+/// are supported.
+///
+/// # Examples
+///
+/// ```rust
+/// use rsdns::clients::Socks5Proxy;
+/// use std::{net::SocketAddr, str::FromStr};
+///
+/// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let proxy = Socks5Proxy::new(SocketAddr::from_str("127.0.0.1:1080")?)
+///     .set_auth(Some(("user", "password")))?;
+///
+/// assert_eq!(proxy.auth(), Some(("user", "password")));
+/// # Ok(())
+/// # }
+/// # foo().unwrap();
+/// ```
+///
+/// [SOCKS5]: https://www.rfc-editor.org/rfc/rfc1928.html
+/// [RFC 1929]: https://www.rfc-editor.org/rfc/rfc1929.html
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[must_use]
+pub struct Socks5Proxy {
+    pub(crate) addr_: SocketAddr,
+    pub(crate) auth_: Option<(String, String)>,
+}
+
+impl Socks5Proxy {
+    /// Creates a proxy configuration with no authentication.
+    pub fn new(addr: SocketAddr) -> Self {
+        Self {
+            addr_: addr,
+            auth_: None,
+        }
+    }
+
+    /// Returns the proxy address.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr_
+    }
+
+    /// Returns the username and password used to authenticate with the proxy.
+    ///
+    /// Default: `None` - no authentication.
+    pub fn auth(&self) -> Option<(&str, &str)> {
+        self.auth_.as_ref().map(|(u, p)| (u.as_str(), p.as_str()))
+    }
+
+    /// Sets the username and password used to authenticate with the proxy.
+    ///
+    /// Both the username and the password must be non-empty and at most 255 bytes long.
+    ///
+    /// See [`auth`] for more information.
+    ///
+    /// [`auth`]: Self::auth
+    pub fn set_auth(mut self, auth: Option<(&str, &str)>) -> Result<Self> {
+        match auth {
+            Some((username, password)) => {
+                for v in [username, password] {
+                    if v.is_empty() || v.len() > u8::MAX as usize {
+                        return Err(Error::BadParam(
+                            "invalid SOCKS5 username or password length",
+                        ));
+                    }
+                }
+                self.auth_ = Some((username.to_string(), password.to_string()));
+            }
+            None => self.auth_ = None,
+        }
+        Ok(self)
+    }
+}
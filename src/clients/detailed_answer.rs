@@ -0,0 +1,42 @@
+use crate::{
+    message::Header,
+    records::{data::RData, Opt, RecordSet},
+};
+use std::{net::SocketAddr, time::Duration};
+
+/// A record set together with the details of the response and the query that produced it.
+///
+/// `DetailedAnswer` is returned by `Client::query_rrset_detailed`. It is intended for
+/// diagnostic tools, which report how an answer was obtained in addition to the answer itself.
+///
+/// # Stability
+///
+/// The struct is marked `#[non_exhaustive]`, so it can't be constructed outside of *rsdns*,
+/// and new fields may be added in a minor release. The existing fields keep their meaning.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub struct DetailedAnswer<D: RData> {
+    /// The record set, parsed as by `Client::query_rrset`.
+    pub rrset: RecordSet<D>,
+
+    /// The header of the response.
+    pub header: Header,
+
+    /// The `OPT` pseudo-record of the response, if present.
+    pub opt: Option<Opt>,
+
+    /// Whether the response was received over TCP.
+    ///
+    /// This is `true` when TCP is configured with [`ProtocolStrategy::Tcp`], and when
+    /// a truncated UDP response was followed by a TCP query.
+    ///
+    /// [`ProtocolStrategy::Tcp`]: crate::clients::ProtocolStrategy::Tcp
+    pub via_tcp: bool,
+
+    /// The address of the nameserver which answered the query.
+    pub server: SocketAddr,
+
+    /// The time elapsed from the start of the query until the response was received,
+    /// including retransmissions and the TCP fallback.
+    pub elapsed: Duration,
+}
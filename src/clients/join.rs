@@ -0,0 +1,59 @@
+use std::{
+    future::{poll_fn, Future},
+    pin::Pin,
+    task::Poll,
+};
+
+/// Polls futures concurrently within the current task, until all of them complete.
+///
+/// Returns the outputs of the futures in the order the futures are given.
+pub(crate) async fn join_all<F: Future>(futures: Vec<F>) -> Vec<F::Output> {
+    let mut futures: Vec<Pin<Box<F>>> = futures.into_iter().map(Box::pin).collect();
+    let mut outputs: Vec<Option<F::Output>> = futures.iter().map(|_| None).collect();
+    poll_fn(|cx| {
+        let mut pending = false;
+        for (fut, output) in futures.iter_mut().zip(outputs.iter_mut()) {
+            if output.is_none() {
+                match fut.as_mut().poll(cx) {
+                    Poll::Ready(v) => *output = Some(v),
+                    Poll::Pending => pending = true,
+                }
+            }
+        }
+        if pending {
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    })
+    .await;
+    outputs.into_iter().flatten().collect()
+}
+
+/// Polls two futures concurrently within the current task, until both of them complete.
+///
+/// Unlike [`join_all`], the futures may be of different types.
+pub(crate) async fn join<A: Future, B: Future>(a: A, b: B) -> (A::Output, B::Output) {
+    let (mut a, mut b) = (Box::pin(a), Box::pin(b));
+    let (mut output_a, mut output_b) = (None, None);
+    poll_fn(|cx| {
+        if output_a.is_none() {
+            if let Poll::Ready(v) = a.as_mut().poll(cx) {
+                output_a = Some(v);
+            }
+        }
+        if output_b.is_none() {
+            if let Poll::Ready(v) = b.as_mut().poll(cx) {
+                output_b = Some(v);
+            }
+        }
+        match (output_a.take(), output_b.take()) {
+            (Some(a), Some(b)) => Poll::Ready((a, b)),
+            (a, b) => {
+                (output_a, output_b) = (a, b);
+                Poll::Pending
+            }
+        }
+    })
+    .await
+}
@@ -0,0 +1,571 @@
+use crate::{
+    clients::IpPreference,
+    message::{
+        reader::{MessageIterator, MessageReader, NameRef, RecordHeaderRef},
+        MessageType, RCode, RecordsSection,
+    },
+    names::InlineName,
+    records::{
+        data::{Aaaa, Cname, Mx, Ptr, RData, RecordData, A},
+        NegativeAnswer, RecordSet, Referral, ResourceRecord, Type,
+    },
+    Error, Result,
+};
+use std::net::IpAddr;
+
+/// Converts [`Error::NoAnswer`] to `None`, leaving other results intact.
+#[inline]
+pub(crate) fn optional_rrset<D: RData>(res: Result<RecordSet<D>>) -> Result<Option<RecordSet<D>>> {
+    match res {
+        Ok(rrset) => Ok(Some(rrset)),
+        Err(Error::NoAnswer) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Merges the addresses of `A` and `AAAA` record sets ordered by preferred address family.
+///
+/// Returns [`Error::NoAnswer`] if both record sets are missing.
+pub(crate) fn merge_addrs(
+    v4: Option<RecordSet<A>>,
+    v6: Option<RecordSet<Aaaa>>,
+    preference: IpPreference,
+) -> Result<Vec<IpAddr>> {
+    if v4.is_none() && v6.is_none() {
+        return Err(Error::NoAnswer);
+    }
+
+    let v4 = v4
+        .into_iter()
+        .flat_map(|rrset| rrset.rdata)
+        .map(IpAddr::from);
+    let v6 = v6
+        .into_iter()
+        .flat_map(|rrset| rrset.rdata)
+        .map(IpAddr::from);
+
+    let addrs = match preference {
+        IpPreference::Ipv4 => v4.chain(v6).collect(),
+        IpPreference::Ipv6 => v6.chain(v4).collect(),
+    };
+
+    Ok(addrs)
+}
+
+/// Converts a failed host lookup of a non-existent name to an empty list of addresses,
+/// leaving other results intact.
+#[inline]
+pub(crate) fn optional_addrs(res: Result<Vec<IpAddr>>) -> Result<Vec<IpAddr>> {
+    match res {
+        Err(Error::NoAnswer) | Err(Error::BadResponseCode(RCode::NXDOMAIN)) => Ok(Vec::new()),
+        res => res,
+    }
+}
+
+/// A mail exchange: its preference, domain name and addresses.
+pub(crate) type MailExchange = (u16, String, Vec<IpAddr>);
+
+/// Parses the `MX` record set of a response message, together with the addresses of the mail
+/// exchanges found in the additional section (glue).
+///
+/// Mail exchanges are sorted by preference, and their addresses are ordered by preferred
+/// address family. A mail exchange without glue has an empty list of addresses.
+pub(crate) fn mail_exchanges(msg: &[u8], preference: IpPreference) -> Result<Vec<MailExchange>> {
+    let rrset = RecordSet::<Mx>::from_msg(msg)?;
+    let mut mxs: Vec<(Mx, Vec<IpAddr>, Vec<IpAddr>)> = rrset
+        .rdata
+        .into_iter()
+        .map(|mx| (mx, Vec::new(), Vec::new()))
+        .collect();
+
+    for res in MessageIterator::new(msg)?.records() {
+        let (section, record) = res?;
+        if section != RecordsSection::Additional {
+            continue;
+        }
+        for (mx, v4, v6) in mxs.iter_mut() {
+            if record.name != mx.exchange {
+                continue;
+            }
+            match record.rdata {
+                RecordData::A(ref a) => v4.push(IpAddr::from(*a)),
+                RecordData::Aaaa(ref aaaa) => v6.push(IpAddr::from(*aaaa)),
+                _ => {}
+            }
+        }
+    }
+
+    mxs.sort_by_key(|(mx, _, _)| mx.preference);
+
+    Ok(mxs
+        .into_iter()
+        .map(|(mx, v4, v6)| {
+            let addrs = match preference {
+                IpPreference::Ipv4 => v4.into_iter().chain(v6).collect(),
+                IpPreference::Ipv6 => v6.into_iter().chain(v4).collect(),
+            };
+            (mx.preference, mx.exchange.into(), addrs)
+        })
+        .collect())
+}
+
+/// Converts a `PTR` record set to a list of host names.
+///
+/// A missing record set, or a non-existent domain name, result in an empty list.
+pub(crate) fn ptr_names(res: Result<RecordSet<Ptr>>) -> Result<Vec<String>> {
+    match res {
+        Ok(rrset) => Ok(rrset
+            .rdata
+            .into_iter()
+            .map(|ptr| ptr.ptrdname.into())
+            .collect()),
+        Err(Error::NoAnswer) | Err(Error::BadResponseCode(RCode::NXDOMAIN)) => Ok(Vec::new()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Checks that every record of the answer section is owned by the question name, or by one of
+/// the names of the *CNAME chain* starting at it.
+///
+/// Returns [`Error::UnrelatedOwnerName`] otherwise.
+pub(crate) fn check_answer_owners(msg: &[u8]) -> Result<()> {
+    let (names, headers) = answer_owners(msg)?;
+    for h in headers.iter() {
+        if !contains(&names, h.name())? {
+            return Err(Error::UnrelatedOwnerName);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the question name followed by the names of the *CNAME chain* starting at it,
+/// and the headers of the answer section.
+fn answer_owners(msg: &[u8]) -> Result<(Vec<NameRef<'_>>, Vec<RecordHeaderRef<'_>>)> {
+    let mut mr = MessageReader::new(msg)?;
+    mr.header()?;
+    let mut names: Vec<NameRef<'_>> = vec![mr.the_question_ref()?.qname];
+
+    let mut headers = Vec::with_capacity(mr.records_count_in(RecordsSection::Answer));
+    while mr.has_records_in(RecordsSection::Answer) {
+        let header = mr.record_header_ref()?;
+        mr.skip_record_data(header.marker())?;
+        headers.push(header);
+    }
+
+    // a CNAME chain may be in any order; every name is looked up once
+    let mut i = 0;
+    while i < names.len() {
+        for h in headers.iter() {
+            if h.rtype() == Type::CNAME && h.name().eq(&names[i])? {
+                let target = mr.name_ref_at(h.marker());
+                if !contains(&names, &target)? {
+                    names.push(target);
+                }
+            }
+        }
+        i += 1;
+    }
+
+    Ok((names, headers))
+}
+
+fn contains(names: &[NameRef<'_>], name: &NameRef<'_>) -> Result<bool> {
+    for n in names {
+        if n.eq(name)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Converts a `CNAME` record set to the canonical name.
+///
+/// A missing record set, or a non-existent domain name, result in `None`.
+pub(crate) fn cname_target(res: Result<RecordSet<Cname>>) -> Result<Option<String>> {
+    match res {
+        Ok(rrset) => Ok(rrset.rdata.into_iter().next().map(|c| c.cname.into())),
+        Err(Error::NoAnswer) | Err(Error::BadResponseCode(RCode::NXDOMAIN)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Parses a [`RecordSet`] from a response message, or a [`NegativeAnswer`] if the response
+/// is negative.
+pub(crate) fn rrset_or_negative<D: RData>(
+    msg: &[u8],
+) -> Result<std::result::Result<RecordSet<D>, NegativeAnswer>> {
+    match RecordSet::<D>::from_msg(msg) {
+        Ok(rrset) => Ok(Ok(rrset)),
+        Err(e @ (Error::NoAnswer | Error::BadResponseCode(RCode::NXDOMAIN))) => {
+            match NegativeAnswer::from_msg(msg)? {
+                Some(na) => Ok(Err(na)),
+                None => Err(e),
+            }
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Parses a [`Referral`] from a response message.
+///
+/// Returns [`Error::BadResponseCode`] if the response code is not `NOERROR`, and
+/// [`Error::NoAnswer`] if the response holds no `NS` records.
+pub(crate) fn referral(msg: &[u8]) -> Result<Referral> {
+    Referral::from_msg(msg)?.ok_or(Error::NoAnswer)
+}
+
+/// Parses the answer section of a response message.
+///
+/// Records owned by names other than the question name and the names of the *CNAME chain*
+/// starting at it are ignored. Records of types not supported by [`RecordData`] are returned
+/// as [`RecordData::Unknown`]. Returns [`Error::NoAnswer`] if no records are found.
+pub(crate) fn answer_records(msg: &[u8]) -> Result<Vec<ResourceRecord>> {
+    let mut mr = MessageReader::new(msg)?;
+
+    let flags = mr.header()?.flags;
+    if flags.message_type() != MessageType::Response {
+        return Err(Error::BadMessageType(flags.message_type()));
+    }
+    if flags.truncated() {
+        return Err(Error::MessageTruncated);
+    }
+    if flags.response_code() != RCode::NOERROR {
+        return Err(Error::BadResponseCode(flags.response_code()));
+    }
+    mr.skip_questions()?;
+
+    let (names, _) = answer_owners(msg)?;
+    let mut records = Vec::with_capacity(mr.records_count_in(RecordsSection::Answer));
+    while mr.has_records_in(RecordsSection::Answer) {
+        let header = mr.record_header_ref()?;
+        let marker = header.marker().clone();
+        if !contains(&names, header.name())? {
+            mr.skip_record_data(&marker)?;
+            continue;
+        }
+        records.push(ResourceRecord {
+            name: InlineName::try_from(header.name())?,
+            rclass: marker.rclass,
+            rtype: marker.rtype,
+            ttl: marker.ttl,
+            rdata: mr.record_data_any(&marker)?,
+        });
+    }
+
+    if records.is_empty() {
+        return Err(Error::NoAnswer);
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        names::Name,
+        records::{data::Unknown, Class, Type},
+    };
+    use std::{
+        net::{Ipv4Addr, Ipv6Addr},
+        str::FromStr,
+    };
+
+    fn rrset<D: RData>(rdata: Vec<D>) -> RecordSet<D> {
+        RecordSet {
+            name: Name::root(),
+            rclass: Class::IN,
+            ttl: 10,
+            rdata,
+        }
+    }
+
+    #[test]
+    fn test_optional_rrset() {
+        assert!(matches!(
+            optional_rrset::<A>(Ok(rrset(vec![]))),
+            Ok(Some(_))
+        ));
+        assert!(matches!(
+            optional_rrset::<A>(Err(Error::NoAnswer)),
+            Ok(None)
+        ));
+        assert!(matches!(
+            optional_rrset::<A>(Err(Error::Timeout)),
+            Err(Error::Timeout)
+        ));
+    }
+
+    #[rustfmt::skip]
+    const ANY_RESPONSE: [u8; 87] = [
+        0x00, 0x01, 0x81, 0x80, 0x00, 0x01, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, // header
+        0x07, b'e', b'x', b'a', b'm', b'p', b'l', b'e', 0x03, b'c', b'o', b'm', 0x00,
+        0x00, 0xFF, 0x00, 0x01, // question
+        0xC0, 0x0C, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x01, 0x2C, 0x00, 0x04,
+        0xC0, 0x00, 0x02, 0x01, // A 192.0.2.1
+        0xC0, 0x0C, 0xFF, 0x00, 0x00, 0x01, 0x00, 0x00, 0x01, 0x2C, 0x00, 0x02,
+        0x01, b'x', // TYPE65280 - not supported by RecordData
+        0xC0, 0x0C, 0x00, 0x1C, 0x00, 0x01, 0x00, 0x00, 0x01, 0x2C, 0x00, 0x10,
+        0x20, 0x01, 0x0D, 0xB8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x01, // AAAA 2001:db8::1
+    ];
+
+    #[test]
+    fn test_check_answer_owners() {
+        use crate::message::{QueryWriter, ResponseBuilder};
+
+        fn response(records: &[(&str, Option<&str>)]) -> Vec<u8> {
+            let mut qbuf = [0u8; 512];
+            let len = QueryWriter::new(&mut qbuf)
+                .write("example.com", Type::A, Class::IN, true, None)
+                .unwrap();
+            let mut buf = [0u8; 512];
+            let mut rb = ResponseBuilder::new(&mut buf, &qbuf[2..len]).unwrap();
+            for (name, cname) in records {
+                match cname {
+                    Some(cname) => rb.add_answer(
+                        name,
+                        Class::IN,
+                        300,
+                        &Cname {
+                            cname: Name::from_str(cname).unwrap(),
+                        },
+                    ),
+                    None => rb.add_answer(name, Class::IN, 300, &A::from(Ipv4Addr::LOCALHOST)),
+                }
+                .unwrap();
+            }
+            let len = rb.finish().unwrap();
+            buf[2..len].to_vec()
+        }
+
+        check_answer_owners(&ANY_RESPONSE).unwrap();
+        check_answer_owners(&response(&[])).unwrap();
+        check_answer_owners(&response(&[
+            ("example.com", Some("a.example.net")),
+            ("a.example.net", None),
+        ]))
+        .unwrap();
+        // the chain is out of order
+        check_answer_owners(&response(&[
+            ("b.example.net", None),
+            ("a.example.net", Some("b.example.net")),
+            ("EXAMPLE.com", Some("a.example.net")),
+        ]))
+        .unwrap();
+
+        for records in [
+            &[("example.com", None), ("example.org", None)][..],
+            &[
+                ("a.example.net", Some("b.example.net")),
+                ("b.example.net", None),
+            ][..],
+        ] {
+            assert!(matches!(
+                check_answer_owners(&response(records)),
+                Err(Error::UnrelatedOwnerName)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_answer_records() {
+        let records = answer_records(&ANY_RESPONSE).unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].rtype, Type::A);
+        assert_eq!(records[1].rtype, Type::from(65280));
+        assert_eq!(
+            records[1].rdata,
+            RecordData::Unknown(Unknown {
+                rtype: Type::from(65280),
+                data: vec![1, b'x'],
+            })
+        );
+        assert_eq!(records[2].rtype, Type::AAAA);
+        assert_eq!(records[2].name.as_str(), "example.com.");
+
+        let mut msg = ANY_RESPONSE;
+        msg[3] = 0x83; // NXDOMAIN
+        assert!(matches!(
+            answer_records(&msg),
+            Err(Error::BadResponseCode(RCode::NXDOMAIN))
+        ));
+
+        let mut msg = ANY_RESPONSE;
+        msg[2] = 0x01; // query
+        assert!(matches!(
+            answer_records(&msg),
+            Err(Error::BadMessageType(MessageType::Query))
+        ));
+
+        let mut msg = ANY_RESPONSE;
+        msg[7] = 0; // no answers
+        assert!(matches!(answer_records(&msg), Err(Error::NoAnswer)));
+    }
+
+    #[test]
+    fn test_rrset_or_negative() {
+        let rrset = rrset_or_negative::<A>(&ANY_RESPONSE).unwrap().unwrap();
+        assert_eq!(rrset.rdata, vec![A::from(Ipv4Addr::new(192, 0, 2, 1))]);
+
+        let mut msg = ANY_RESPONSE;
+        msg[3] = 0x83; // NXDOMAIN
+        msg[7] = 0; // no answers
+        let na = rrset_or_negative::<A>(&msg[..29]).unwrap().unwrap_err();
+        assert_eq!(na.rcode, RCode::NXDOMAIN);
+        assert_eq!(na.soa, None);
+
+        // a negative response code with records in the answer section is not a negative answer
+        let mut msg = ANY_RESPONSE;
+        msg[3] = 0x83; // NXDOMAIN
+        assert!(matches!(
+            rrset_or_negative::<A>(&msg),
+            Err(Error::BadResponseCode(RCode::NXDOMAIN))
+        ));
+
+        let mut msg = ANY_RESPONSE;
+        msg[3] = 0x82; // SERVFAIL
+        assert!(matches!(
+            rrset_or_negative::<A>(&msg),
+            Err(Error::BadResponseCode(RCode::SERVFAIL))
+        ));
+    }
+
+    #[rustfmt::skip]
+    const MX_RESPONSE: [u8; 113] = [
+        0x00, 0x01, 0x81, 0x80, 0x00, 0x01, 0x00, 0x02, 0x00, 0x00, 0x00, 0x02, // header
+        0x07, b'e', b'x', b'a', b'm', b'p', b'l', b'e', 0x03, b'c', b'o', b'm', 0x00,
+        0x00, 0x0F, 0x00, 0x01, // question
+        0xC0, 0x0C, 0x00, 0x0F, 0x00, 0x01, 0x00, 0x00, 0x01, 0x2C, 0x00, 0x08,
+        0x00, 0x14, 0x03, b'm', b'x', b'2', 0xC0, 0x0C, // MX 20 mx2.example.com
+        0xC0, 0x0C, 0x00, 0x0F, 0x00, 0x01, 0x00, 0x00, 0x01, 0x2C, 0x00, 0x08,
+        0x00, 0x0A, 0x03, b'm', b'x', b'1', 0xC0, 0x0C, // MX 10 mx1.example.com
+        0xC0, 0x3F, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x01, 0x2C, 0x00, 0x04,
+        0xC0, 0x00, 0x02, 0x01, // mx1.example.com A 192.0.2.1
+        0xC0, 0x3F, 0x00, 0x1C, 0x00, 0x01, 0x00, 0x00, 0x01, 0x2C, 0x00, 0x10,
+        0x20, 0x01, 0x0D, 0xB8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x01, // mx1.example.com AAAA 2001:db8::1
+    ];
+
+    #[test]
+    fn test_mail_exchanges() {
+        let v4 = IpAddr::from(Ipv4Addr::new(192, 0, 2, 1));
+        let v6 = IpAddr::from(Ipv6Addr::from_str("2001:db8::1").unwrap());
+
+        let mxs = mail_exchanges(&MX_RESPONSE, IpPreference::Ipv4).unwrap();
+        assert_eq!(
+            mxs,
+            vec![
+                (10, "mx1.example.com.".to_string(), vec![v4, v6]),
+                (20, "mx2.example.com.".to_string(), vec![]),
+            ]
+        );
+
+        let mxs = mail_exchanges(&MX_RESPONSE, IpPreference::Ipv6).unwrap();
+        assert_eq!(mxs[0].2, vec![v6, v4]);
+
+        let mut msg = MX_RESPONSE;
+        msg[7] = 0; // no answers
+        msg[11] = 0; // no additional records
+        assert!(matches!(
+            mail_exchanges(&msg[..29], IpPreference::Ipv4),
+            Err(Error::NoAnswer)
+        ));
+    }
+
+    #[test]
+    fn test_referral() {
+        // an answer with no NS records
+        assert!(matches!(referral(&MX_RESPONSE), Err(Error::NoAnswer)));
+
+        let mut msg = MX_RESPONSE;
+        msg[3] = 0x83; // NXDOMAIN
+        assert!(matches!(
+            referral(&msg),
+            Err(Error::BadResponseCode(RCode::NXDOMAIN))
+        ));
+    }
+
+    #[test]
+    fn test_optional_addrs() {
+        let addrs = vec![IpAddr::from(Ipv4Addr::LOCALHOST)];
+        assert_eq!(optional_addrs(Ok(addrs.clone())).unwrap(), addrs);
+        assert!(optional_addrs(Err(Error::NoAnswer)).unwrap().is_empty());
+        assert!(optional_addrs(Err(Error::BadResponseCode(RCode::NXDOMAIN)))
+            .unwrap()
+            .is_empty());
+        assert!(matches!(
+            optional_addrs(Err(Error::Timeout)),
+            Err(Error::Timeout)
+        ));
+    }
+
+    #[test]
+    fn test_ptr_names() {
+        let ptr = Ptr {
+            ptrdname: Name::from_str("host.example.com").unwrap(),
+        };
+        assert_eq!(
+            ptr_names(Ok(rrset(vec![ptr]))).unwrap(),
+            vec!["host.example.com.".to_string()]
+        );
+        assert!(ptr_names(Err(Error::NoAnswer)).unwrap().is_empty());
+        assert!(ptr_names(Err(Error::BadResponseCode(RCode::NXDOMAIN)))
+            .unwrap()
+            .is_empty());
+        assert!(matches!(
+            ptr_names(Err(Error::BadResponseCode(RCode::SERVFAIL))),
+            Err(Error::BadResponseCode(RCode::SERVFAIL))
+        ));
+    }
+
+    #[test]
+    fn test_cname_target() {
+        let cname = Cname {
+            cname: Name::from_str("www.example.net").unwrap(),
+        };
+        assert_eq!(
+            cname_target(Ok(rrset(vec![cname]))).unwrap(),
+            Some("www.example.net.".to_string())
+        );
+        assert_eq!(cname_target(Err(Error::NoAnswer)).unwrap(), None);
+        assert_eq!(
+            cname_target(Err(Error::BadResponseCode(RCode::NXDOMAIN))).unwrap(),
+            None
+        );
+        assert!(matches!(
+            cname_target(Err(Error::BadResponseCode(RCode::SERVFAIL))),
+            Err(Error::BadResponseCode(RCode::SERVFAIL))
+        ));
+    }
+
+    #[test]
+    fn test_merge_addrs() {
+        let a = A::from(Ipv4Addr::new(192, 0, 2, 1));
+        let aaaa = Aaaa::from(Ipv6Addr::LOCALHOST);
+
+        let addrs = merge_addrs(
+            Some(rrset(vec![a])),
+            Some(rrset(vec![aaaa])),
+            IpPreference::Ipv4,
+        )
+        .unwrap();
+        assert_eq!(addrs, vec![IpAddr::from(a), IpAddr::from(aaaa)]);
+
+        let addrs = merge_addrs(
+            Some(rrset(vec![a])),
+            Some(rrset(vec![aaaa])),
+            IpPreference::Ipv6,
+        )
+        .unwrap();
+        assert_eq!(addrs, vec![IpAddr::from(aaaa), IpAddr::from(a)]);
+
+        let addrs = merge_addrs(None, Some(rrset(vec![aaaa])), IpPreference::Ipv4).unwrap();
+        assert_eq!(addrs, vec![IpAddr::from(aaaa)]);
+
+        let addrs = merge_addrs(Some(rrset(vec![a])), None, IpPreference::Ipv6).unwrap();
+        assert_eq!(addrs, vec![IpAddr::from(a)]);
+
+        assert!(matches!(
+            merge_addrs(None, None, IpPreference::Ipv4),
+            Err(Error::NoAnswer)
+        ));
+    }
+}
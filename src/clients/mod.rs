@@ -14,6 +14,53 @@
 //! clients for the corresponding runtime. `net-std` enables the synchronous client.
 //! The `clients` module is enabled only if one of the client implementations is enabled.
 //!
+//! The client implementations are independent of each other, and any combination of them may
+//! be enabled simultaneously. This allows a library to offer several runtime backends,
+//! leaving the choice to the final application.
+//!
+//! ## Concurrency
+//!
+//! A client issues one query at a time. Its methods take `&mut self`, as every client owns
+//! a socket and a buffer which are reused for every query. Clients are `Send`, but cannot be
+//! shared between threads (or tasks) by reference.
+//!
+//! The recommended pattern is a client per thread (or task). A [`ClientConfig`] is cheap to
+//! clone, so a single configuration may be used to create all clients. When queries are
+//! infrequent, a single client may be shared behind a `Mutex` instead, at the cost of
+//! serializing the queries.
+//!
+//! ```rust
+//! # #[cfg(feature = "net-std")]
+//! use rsdns::{
+//!     clients::{std::Client, ClientConfig},
+//!     records::{data::A, Class},
+//! };
+//! # use std::{net::SocketAddr, str::FromStr, thread};
+//!
+//! # #[cfg(feature = "net-std")]
+//! fn resolve_all(qnames: Vec<String>) -> rsdns::Result<()> {
+//!     let nameserver = SocketAddr::from_str("8.8.8.8:53").unwrap();
+//!     let config = ClientConfig::with_nameserver(nameserver);
+//!
+//!     let handles: Vec<_> = qnames
+//!         .into_iter()
+//!         .map(|qname| {
+//!             let config = config.clone();
+//!             thread::spawn(move || -> rsdns::Result<Vec<A>> {
+//!                 // a client per thread
+//!                 let mut client = Client::new(config)?;
+//!                 Ok(client.query_rrset::<A>(&qname, Class::IN)?.rdata)
+//!             })
+//!         })
+//!         .collect();
+//!
+//!     for h in handles {
+//!         println!("{:?}", h.join().unwrap()?);
+//!     }
+//!     Ok(())
+//! }
+//! ```
+//!
 //! [`tokio`]: crate::clients::tokio
 //! [`async_std`]: crate::clients::async_std
 //! [`smol`]: crate::clients::smol
@@ -43,3 +90,20 @@ pub mod std;
 
 mod config;
 pub use config::*;
+
+mod addrs;
+pub use addrs::*;
+
+mod detailed_answer;
+pub use detailed_answer::*;
+
+mod query_options;
+pub use query_options::*;
+
+mod lookup;
+
+#[cfg(any(feature = "net-tokio", feature = "net-async-std", feature = "net-smol"))]
+mod join;
+
+#[cfg(feature = "socks5")]
+mod socks5;
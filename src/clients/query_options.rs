@@ -0,0 +1,109 @@
+use crate::clients::ProtocolStrategy;
+use std::time::Instant;
+
+/// Per-query options.
+///
+/// `QueryOptions` overrides the client-wide [`ClientConfig`] for a single call of
+/// `Client::query_raw_with` or `Client::query_rrset_with`. Every option is unset by default,
+/// which leaves the client's behavior unchanged.
+///
+/// # Examples
+///
+/// ```rust
+/// # use rsdns::clients::{ProtocolStrategy, QueryOptions};
+/// #
+/// let opts = QueryOptions::new().set_protocol_strategy(ProtocolStrategy::Tcp);
+///
+/// assert_eq!(opts.protocol_strategy(), Some(ProtocolStrategy::Tcp));
+/// assert_eq!(opts.msg_id(), None);
+/// assert_eq!(opts.deadline(), None);
+/// ```
+///
+/// [`ClientConfig`]: crate::clients::ClientConfig
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct QueryOptions {
+    pub(crate) msg_id_: Option<u16>,
+    pub(crate) deadline_: Option<Instant>,
+    pub(crate) protocol_strategy_: Option<ProtocolStrategy>,
+}
+
+impl QueryOptions {
+    /// Creates options with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the message ID of the query.
+    ///
+    /// When set, the query is sent with this message ID instead of a random value, and only
+    /// a response with the same message ID is accepted. The ID is used for every query
+    /// issued by the call, including the follow-up queries of [`ClientConfig::follow_cname`].
+    ///
+    /// Default: `None`
+    ///
+    /// [`ClientConfig::follow_cname`]: crate::clients::ClientConfig::follow_cname
+    pub fn msg_id(&self) -> Option<u16> {
+        self.msg_id_
+    }
+
+    /// Sets the message ID of the query.
+    ///
+    /// See [`msg_id`] for more information.
+    ///
+    /// [`msg_id`]: Self::msg_id
+    pub fn set_msg_id(mut self, msg_id: u16) -> Self {
+        self.msg_id_ = Some(msg_id);
+        self
+    }
+
+    /// Returns the absolute deadline of the call.
+    ///
+    /// When set, the whole resolution, including retransmissions, TCP fallback and
+    /// the follow-up queries of [`ClientConfig::follow_cname`], must complete before
+    /// the deadline. Every query is bounded by the minimum between
+    /// [`ClientConfig::query_lifetime`] and the time left until the deadline.
+    ///
+    /// [`Error::Timeout`] is returned if the deadline is reached.
+    ///
+    /// Default: `None`
+    ///
+    /// [`ClientConfig::follow_cname`]: crate::clients::ClientConfig::follow_cname
+    /// [`ClientConfig::query_lifetime`]: crate::clients::ClientConfig::query_lifetime
+    /// [`Error::Timeout`]: crate::Error::Timeout
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline_
+    }
+
+    /// Sets the absolute deadline of the call.
+    ///
+    /// See [`deadline`] for more information.
+    ///
+    /// [`deadline`]: Self::deadline
+    pub fn set_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline_ = Some(deadline);
+        self
+    }
+
+    /// Returns the protocol strategy of the call.
+    ///
+    /// When set, overrides [`ClientConfig::protocol_strategy`] for all queries issued by
+    /// the call. For example, [`ProtocolStrategy::Tcp`] may be used to fetch a record set
+    /// known to be too large for UDP, without configuring a separate client.
+    ///
+    /// Default: `None`
+    ///
+    /// [`ClientConfig::protocol_strategy`]: crate::clients::ClientConfig::protocol_strategy
+    pub fn protocol_strategy(&self) -> Option<ProtocolStrategy> {
+        self.protocol_strategy_
+    }
+
+    /// Sets the protocol strategy of the call.
+    ///
+    /// See [`protocol_strategy`] for more information.
+    ///
+    /// [`protocol_strategy`]: Self::protocol_strategy
+    pub fn set_protocol_strategy(mut self, strategy: ProtocolStrategy) -> Self {
+        self.protocol_strategy_ = Some(strategy);
+        self
+    }
+}
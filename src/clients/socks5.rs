@@ -0,0 +1,157 @@
+//! SOCKS5 protocol messages ([RFC 1928], [RFC 1929]).
+//!
+//! [RFC 1928]: https://www.rfc-editor.org/rfc/rfc1928.html
+//! [RFC 1929]: https://www.rfc-editor.org/rfc/rfc1929.html
+
+use crate::{clients::Socks5Proxy, Error, Result};
+use std::net::SocketAddr;
+
+const VERSION: u8 = 5;
+const AUTH_VERSION: u8 = 1;
+const METHOD_NO_AUTH: u8 = 0x00;
+const METHOD_USERNAME_PASSWORD: u8 = 0x02;
+const CMD_CONNECT: u8 = 0x01;
+const ATYP_IPV4: u8 = 0x01;
+const ATYP_DOMAIN_NAME: u8 = 0x03;
+const ATYP_IPV6: u8 = 0x04;
+
+/// The length of the fixed part of the `CONNECT` reply, preceding the bound address.
+pub(crate) const CONNECT_REPLY_HEAD_LENGTH: usize = 4;
+
+/// Returns the method selection message, offering the single method suitable for `proxy`.
+pub(crate) fn greeting(proxy: &Socks5Proxy) -> [u8; 3] {
+    [VERSION, 1, method(proxy)]
+}
+
+/// Checks the method selection reply of the proxy.
+pub(crate) fn check_greeting_reply(proxy: &Socks5Proxy, reply: [u8; 2]) -> Result<()> {
+    if reply[0] != VERSION {
+        return Err(Error::Socks5Error("unsupported protocol version"));
+    }
+    if reply[1] != method(proxy) {
+        return Err(Error::Socks5Error("no acceptable authentication method"));
+    }
+    Ok(())
+}
+
+/// Returns the username/password authentication request, if `proxy` requires authentication.
+pub(crate) fn auth_request(proxy: &Socks5Proxy) -> Option<Vec<u8>> {
+    let (username, password) = proxy.auth()?;
+    let mut req = Vec::with_capacity(3 + username.len() + password.len());
+    req.push(AUTH_VERSION);
+    req.push(username.len() as u8);
+    req.extend_from_slice(username.as_bytes());
+    req.push(password.len() as u8);
+    req.extend_from_slice(password.as_bytes());
+    Some(req)
+}
+
+/// Checks the username/password authentication reply of the proxy.
+pub(crate) fn check_auth_reply(reply: [u8; 2]) -> Result<()> {
+    if reply[0] != AUTH_VERSION || reply[1] != 0 {
+        return Err(Error::Socks5Error("authentication failed"));
+    }
+    Ok(())
+}
+
+/// Returns the `CONNECT` request to `addr`.
+pub(crate) fn connect_request(addr: SocketAddr) -> arrayvec::ArrayVec<u8, 22> {
+    let mut req = arrayvec::ArrayVec::new();
+    req.extend([VERSION, CMD_CONNECT, 0]);
+    match addr {
+        SocketAddr::V4(a) => {
+            req.push(ATYP_IPV4);
+            req.extend(a.ip().octets());
+        }
+        SocketAddr::V6(a) => {
+            req.push(ATYP_IPV6);
+            req.extend(a.ip().octets());
+        }
+    }
+    req.extend(addr.port().to_be_bytes());
+    req
+}
+
+/// Checks the fixed part of the `CONNECT` reply.
+///
+/// Returns the length of the bound address that follows, including the port.
+/// For a domain name address `None` is returned, and the length is defined by the next byte.
+pub(crate) fn check_connect_reply(head: [u8; CONNECT_REPLY_HEAD_LENGTH]) -> Result<Option<usize>> {
+    if head[0] != VERSION {
+        return Err(Error::Socks5Error("unsupported protocol version"));
+    }
+    match head[1] {
+        0x00 => {}
+        0x01 => return Err(Error::Socks5Error("general SOCKS server failure")),
+        0x02 => return Err(Error::Socks5Error("connection not allowed by ruleset")),
+        0x03 => return Err(Error::Socks5Error("network unreachable")),
+        0x04 => return Err(Error::Socks5Error("host unreachable")),
+        0x05 => return Err(Error::Socks5Error("connection refused")),
+        0x06 => return Err(Error::Socks5Error("TTL expired")),
+        0x07 => return Err(Error::Socks5Error("command not supported")),
+        0x08 => return Err(Error::Socks5Error("address type not supported")),
+        _ => return Err(Error::Socks5Error("unknown reply code")),
+    }
+    match head[3] {
+        ATYP_IPV4 => Ok(Some(4 + 2)),
+        ATYP_IPV6 => Ok(Some(16 + 2)),
+        ATYP_DOMAIN_NAME => Ok(None),
+        _ => Err(Error::Socks5Error("address type not supported")),
+    }
+}
+
+#[inline]
+fn method(proxy: &Socks5Proxy) -> u8 {
+    if proxy.auth().is_some() {
+        METHOD_USERNAME_PASSWORD
+    } else {
+        METHOD_NO_AUTH
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_greeting() {
+        let proxy = Socks5Proxy::new(SocketAddr::from_str("127.0.0.1:1080").unwrap());
+        assert_eq!(greeting(&proxy), [5, 1, 0]);
+        assert!(auth_request(&proxy).is_none());
+        assert!(check_greeting_reply(&proxy, [5, 0]).is_ok());
+        assert!(check_greeting_reply(&proxy, [5, 0xFF]).is_err());
+        assert!(check_greeting_reply(&proxy, [4, 0]).is_err());
+
+        let proxy = proxy.set_auth(Some(("user", "pw"))).unwrap();
+        assert_eq!(greeting(&proxy), [5, 1, 2]);
+        assert_eq!(
+            auth_request(&proxy).unwrap(),
+            [1, 4, b'u', b's', b'e', b'r', 2, b'p', b'w']
+        );
+        assert!(check_greeting_reply(&proxy, [5, 2]).is_ok());
+        assert!(check_greeting_reply(&proxy, [5, 0]).is_err());
+        assert!(check_auth_reply([1, 0]).is_ok());
+        assert!(check_auth_reply([1, 1]).is_err());
+    }
+
+    #[test]
+    fn test_connect() {
+        let req = connect_request(SocketAddr::from_str("192.0.2.1:53").unwrap());
+        assert_eq!(req.as_slice(), [5, 1, 0, 1, 192, 0, 2, 1, 0, 53]);
+
+        let req = connect_request(SocketAddr::from_str("[2001:db8::1]:53").unwrap());
+        assert_eq!(req.len(), 22);
+        assert_eq!(req[3], 4);
+        assert_eq!(req[20..], [0, 53]);
+
+        assert_eq!(check_connect_reply([5, 0, 0, 1]).unwrap(), Some(6));
+        assert_eq!(check_connect_reply([5, 0, 0, 4]).unwrap(), Some(18));
+        assert_eq!(check_connect_reply([5, 0, 0, 3]).unwrap(), None);
+        assert!(matches!(
+            check_connect_reply([5, 5, 0, 1]),
+            Err(Error::Socks5Error("connection refused"))
+        ));
+        assert!(check_connect_reply([5, 0, 0, 9]).is_err());
+    }
+}
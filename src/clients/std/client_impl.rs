@@ -1,9 +1,15 @@
+#[cfg(feature = "socks5")]
+use crate::clients::{socks5, Socks5Proxy};
 use crate::{
-    clients::config::{ClientConfig, EDns, ProtocolStrategy, Recursion},
-    constants::DNS_MESSAGE_BUFFER_MIN_LENGTH,
+    clients::{
+        config::{ClientConfig, ProtocolStrategy, Recursion, QUERY_OPT_MAX_LENGTH},
+        lookup::answer_records,
+        QueryOptions,
+    },
+    constants::{DNS_MESSAGE_BUFFER_MIN_LENGTH, DOMAIN_NAME_MAX_LENGTH, HEADER_LENGTH},
     errors::{Error, Result},
     message::{reader::MessageReader, Flags, QueryWriter},
-    records::{data::RData, Class, Opt, RecordSet, Type},
+    records::{data::RData, Class, RecordSet, ResourceRecord, RrsetOrCname, Type},
 };
 use std::{
     io::{ErrorKind, Read, Write},
@@ -11,7 +17,9 @@ use std::{
     time::{Duration, Instant},
 };
 
-const QUERY_BUFFER_SIZE: usize = 288;
+// the length prefix, the header, the question and the OPT record
+const QUERY_BUFFER_SIZE: usize =
+    2 + HEADER_LENGTH + DOMAIN_NAME_MAX_LENGTH + 4 + QUERY_OPT_MAX_LENGTH;
 type MsgBuf = arrayvec::ArrayVec<u8, QUERY_BUFFER_SIZE>;
 
 struct ClientCtx<'a, 'b, 'c, 'd> {
@@ -20,11 +28,16 @@ struct ClientCtx<'a, 'b, 'c, 'd> {
     qclass: Class,
     sock: &'b UdpSocket,
     config: &'c ClientConfig,
+    protocol_strategy: ProtocolStrategy,
+    via_tcp: bool,
     msg_id: u16,
+    fixed_msg_id: Option<u16>,
     msg: MsgBuf,
     buf: &'d mut [u8],
     start: Instant,
     query_start: Instant,
+    query_timeout: Duration,
+    lifetime: Duration,
 }
 
 pub(crate) struct ClientImpl {
@@ -36,7 +49,11 @@ pub(crate) struct ClientImpl {
 impl ClientImpl {
     pub fn new(config: ClientConfig) -> Result<Self> {
         let socket = UdpSocket::bind(config.bind_addr_)?;
-        socket.connect(config.nameserver_)?;
+        #[cfg(all(target_os = "linux", feature = "socket2"))]
+        config.set_socket_buffers(&socket)?;
+        if config.connect_udp_ {
+            socket.connect(config.nameserver_)?;
+        }
 
         let buf = match config.buffer_size() {
             0 => Vec::new(),
@@ -59,11 +76,28 @@ impl ClientImpl {
         qname: &str,
         qtype: Type,
         qclass: Class,
+        opts: QueryOptions,
         buf: &mut [u8],
     ) -> Result<usize> {
+        self.query_raw_via(qname, qtype, qclass, opts, buf)
+            .map(|(size, _)| size)
+    }
+
+    pub fn query_raw_via(
+        &self,
+        qname: &str,
+        qtype: Type,
+        qclass: Class,
+        opts: QueryOptions,
+        buf: &mut [u8],
+    ) -> Result<(usize, bool)> {
         if buf.len() < DNS_MESSAGE_BUFFER_MIN_LENGTH {
             return Err(Error::BufferTooShort(DNS_MESSAGE_BUFFER_MIN_LENGTH));
         }
+        let lifetime = self.config.lifetime_until(opts.deadline_)?;
+        // the question name of the response is compared with `qname`
+        let qname = if qname.is_empty() { "." } else { qname };
+        self.config.check_qname(qname)?;
         let now = Instant::now();
         let mut ctx = ClientCtx {
             qname,
@@ -71,33 +105,91 @@ impl ClientImpl {
             qclass,
             sock: &self.socket,
             config: &self.config,
+            protocol_strategy: opts
+                .protocol_strategy_
+                .unwrap_or(self.config.protocol_strategy_),
+            via_tcp: false,
             msg_id: 0,
+            fixed_msg_id: opts.msg_id_,
             msg: MsgBuf::default(),
             buf,
             start: now,
             query_start: now,
+            query_timeout: lifetime,
+            lifetime,
         };
-        ctx.prepare_message()?;
-        ctx.query_raw()
+        ctx.prepare_message(opts.msg_id_, true)?;
+        let size = ctx.query_raw()?;
+        Ok((size, ctx.via_tcp))
     }
 
-    pub fn query_rrset<D: RData>(&mut self, qname: &str, qclass: Class) -> Result<RecordSet<D>> {
-        if self.config.buffer_size() == 0 {
-            return Err(Error::BadParam("non-zero buffer_size is required"));
+    pub fn query_rrset<D: RData>(
+        &mut self,
+        qname: &str,
+        qclass: Class,
+        opts: QueryOptions,
+    ) -> Result<RecordSet<D>> {
+        if !qclass.is_data_class() {
+            return Err(Error::UnsupportedClass(qclass));
         }
+        let mut hops = 0;
+        let mut result = self.query_rrset_or_cname::<D>(qname, qclass, opts)?;
+        loop {
+            match result {
+                RrsetOrCname::Rrset(rrset) => return Ok(rrset),
+                RrsetOrCname::Cname(cname) => {
+                    if !self.config.follow_cname_ || hops >= self.config.max_cname_hops_ {
+                        return Err(Error::NoAnswer);
+                    }
+                    hops += 1;
+                    result = self.query_rrset_or_cname::<D>(cname.as_str(), qclass, opts)?;
+                }
+            }
+        }
+    }
+
+    fn query_rrset_or_cname<D: RData>(
+        &mut self,
+        qname: &str,
+        qclass: Class,
+        opts: QueryOptions,
+    ) -> Result<RrsetOrCname<D>> {
+        self.query_parsed(qname, D::RTYPE, qclass, opts, |msg, _| {
+            RecordSet::from_msg_or_cname(msg)
+        })
+    }
+
+    pub fn query_any(&mut self, qname: &str, qclass: Class) -> Result<Vec<ResourceRecord>> {
         if !qclass.is_data_class() {
             return Err(Error::UnsupportedClass(qclass));
         }
+        self.query_parsed(
+            qname,
+            Type::ANY,
+            qclass,
+            QueryOptions::default(),
+            |msg, _| answer_records(msg),
+        )
+    }
+
+    /// Issues a DNS query into the internal buffer, and parses the response with `parse`.
+    ///
+    /// `parse` is called with the response message, and whether it was received via TCP.
+    pub fn query_parsed<T>(
+        &mut self,
+        qname: &str,
+        qtype: Type,
+        qclass: Class,
+        opts: QueryOptions,
+        parse: impl FnOnce(&[u8], bool) -> Result<T>,
+    ) -> Result<T> {
+        if self.config.buffer_size() == 0 {
+            return Err(Error::BadParam("non-zero buffer_size is required"));
+        }
         let mut buf = unsafe { self.take_buf() };
-        let response_len = match self.query_raw(qname, D::RTYPE, qclass, &mut buf) {
-            Ok(v) => v,
-            Err(e) => {
-                std::mem::swap(&mut self.buf, &mut buf);
-                return Err(e);
-            }
-        };
-        unsafe { buf.set_len(response_len) };
-        let result = RecordSet::from_msg(&buf);
+        let result = self
+            .query_raw_via(qname, qtype, qclass, opts, &mut buf)
+            .and_then(|(len, via_tcp)| parse(&buf[..len], via_tcp));
         std::mem::swap(&mut self.buf, &mut buf);
         result
     }
@@ -115,6 +207,16 @@ impl ClientImpl {
 impl ClientCtx<'_, '_, '_, '_> {
     #[inline]
     fn query_raw(&mut self) -> Result<usize> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(
+            "query",
+            qname = self.qname,
+            qtype = %self.qtype,
+            qclass = %self.qclass,
+            nameserver = %self.config.nameserver_,
+        )
+        .entered();
+
         match self.query_raw_impl() {
             Err(Error::IoError(v)) if v.kind() == ErrorKind::TimedOut => Err(Error::Timeout),
             Err(Error::IoError(v)) if v.kind() == ErrorKind::WouldBlock => Err(Error::Timeout),
@@ -124,10 +226,28 @@ impl ClientCtx<'_, '_, '_, '_> {
     }
 
     fn query_raw_impl(&mut self) -> Result<usize> {
+        let mut size = self.exchange()?;
+        if self.config.edns_fallback_required(&self.buf[..size]) {
+            trace_event!(elapsed = ?self.start.elapsed(), "EDNS not supported, retrying without EDNS");
+            // a different ID discards late responses to the first query, unless the ID is fixed
+            let msg_id = self
+                .fixed_msg_id
+                .unwrap_or_else(|| self.msg_id.wrapping_add(rand::random::<u16>().max(1)));
+            self.prepare_message(Some(msg_id), false)?;
+            size = self.exchange()?;
+        }
+        self.config.check_strict_edns(&self.buf[..size])?;
+        self.config.check_owner_names(&self.buf[..size])?;
+        Ok(size)
+    }
+
+    fn exchange(&mut self) -> Result<usize> {
+        self.via_tcp = false;
         if self.udp_first() {
             let (size, flags) = self.udp_exchange()?;
 
             if flags.truncated() && self.tcp_allowed() {
+                trace_event!(elapsed = ?self.start.elapsed(), "truncated response, falling back to TCP");
                 self.tcp_exchange()
             } else {
                 Ok(size)
@@ -138,37 +258,109 @@ impl ClientCtx<'_, '_, '_, '_> {
     }
 
     fn tcp_exchange(&mut self) -> Result<usize> {
-        let mut sock = TcpStream::connect_timeout(&self.config.nameserver_, self.lifetime_left()?)?;
+        self.via_tcp = true;
+        let mut sock = self.tcp_connect()?;
 
         Self::set_timeout_tcp(&sock, self.lifetime_left()?)?;
         sock.write_all(&self.msg)?;
+        trace_event!(elapsed = ?self.start.elapsed(), protocol = "tcp", "query sent");
 
         Self::set_timeout_tcp(&sock, self.lifetime_left()?)?;
         let mut response_size_buf = [0u8; 2];
         sock.read_exact(&mut response_size_buf)?;
 
         let response_size = u16::from_be_bytes(response_size_buf) as usize;
+        if response_size < HEADER_LENGTH {
+            return Err(Error::MessageTooShort(response_size));
+        }
+        if response_size > self.config.max_tcp_response_size_ {
+            return Err(Error::ResponseTooLong {
+                size: response_size,
+                max: self.config.max_tcp_response_size_,
+            });
+        }
         if response_size > self.buf.len() {
             return Err(Error::BufferTooShort(response_size));
         }
 
         Self::set_timeout_tcp(&sock, self.lifetime_left()?)?;
         sock.read_exact(&mut self.buf[..response_size])?;
+        trace_event!(
+            elapsed = ?self.start.elapsed(),
+            protocol = "tcp",
+            size = response_size,
+            "response received"
+        );
 
         Ok(response_size)
     }
 
+    fn tcp_connect(&self) -> Result<TcpStream> {
+        #[cfg(feature = "socks5")]
+        if let Some(proxy) = &self.config.socks5_proxy_ {
+            return self.socks5_connect(proxy);
+        }
+
+        Ok(TcpStream::connect_timeout(
+            &self.config.nameserver_,
+            self.lifetime_left()?,
+        )?)
+    }
+
+    #[cfg(feature = "socks5")]
+    fn socks5_connect(&self, proxy: &Socks5Proxy) -> Result<TcpStream> {
+        let mut sock = TcpStream::connect_timeout(&proxy.addr_, self.lifetime_left()?)?;
+        Self::set_timeout_tcp(&sock, self.lifetime_left()?)?;
+
+        sock.write_all(&socks5::greeting(proxy))?;
+        let mut reply = [0u8; 2];
+        sock.read_exact(&mut reply)?;
+        socks5::check_greeting_reply(proxy, reply)?;
+
+        if let Some(request) = socks5::auth_request(proxy) {
+            sock.write_all(&request)?;
+            sock.read_exact(&mut reply)?;
+            socks5::check_auth_reply(reply)?;
+        }
+
+        sock.write_all(&socks5::connect_request(self.config.nameserver_))?;
+        let mut head = [0u8; socks5::CONNECT_REPLY_HEAD_LENGTH];
+        sock.read_exact(&mut head)?;
+        let bound_addr_len = match socks5::check_connect_reply(head)? {
+            Some(len) => len,
+            None => {
+                let mut len = [0u8; 1];
+                sock.read_exact(&mut len)?;
+                len[0] as usize + 2
+            }
+        };
+        let mut bound_addr = [0u8; u8::MAX as usize + 2];
+        sock.read_exact(&mut bound_addr[..bound_addr_len])?;
+
+        Ok(sock)
+    }
+
     fn udp_exchange(&mut self) -> Result<(usize, Flags)> {
         loop {
             self.query_start = Instant::now();
+            self.query_timeout = self.config.retransmit_timeout().unwrap_or(self.lifetime);
             Self::set_timeout_udp(self.sock, self.query_left()?)?;
 
-            self.sock.send(&self.msg[2..])?;
+            if self.config.connect_udp_ {
+                self.sock.send(&self.msg[2..])?;
+            } else {
+                self.sock.send_to(&self.msg[2..], self.config.nameserver_)?;
+            }
+            trace_event!(elapsed = ?self.start.elapsed(), protocol = "udp", "query sent");
 
             match self.udp_receive_loop() {
                 Ok(v) => break Ok(v),
-                Err(Error::IoError(v)) if v.kind() == ErrorKind::WouldBlock => continue,
-                Err(Error::IoError(v)) if v.kind() == ErrorKind::TimedOut => continue,
+                Err(Error::IoError(v))
+                    if v.kind() == ErrorKind::WouldBlock || v.kind() == ErrorKind::TimedOut =>
+                {
+                    trace_event!(elapsed = ?self.start.elapsed(), "query timed out, retransmitting");
+                    continue;
+                }
                 Err(v) => break Err(v),
             }
         }
@@ -178,22 +370,38 @@ impl ClientCtx<'_, '_, '_, '_> {
         loop {
             Self::set_timeout_udp(self.sock, self.query_left()?)?;
 
-            let size = self.sock.recv(self.buf)?;
+            let size = if self.config.connect_udp_ {
+                self.sock.recv(self.buf)?
+            } else {
+                let (size, source) = self.sock.recv_from(self.buf)?;
+                if source != self.config.nameserver_ {
+                    trace_event!(%source, "response discarded: source address mismatch");
+                    continue;
+                }
+                size
+            };
 
             let response = &self.buf[..size];
             let mut mr = {
                 match MessageReader::new(response) {
                     Ok(mr) => mr,
-                    Err(_) => continue,
+                    Err(_e) => {
+                        trace_event!(error = %_e, "response discarded: parse error");
+                        continue;
+                    }
                 }
             };
 
             let header = match mr.header() {
                 Ok(h) => h,
-                Err(_) => continue,
+                Err(_e) => {
+                    trace_event!(error = %_e, "response discarded: parse error");
+                    continue;
+                }
             };
 
-            if header.id != self.msg_id {
+            if self.config.id_check_ && header.id != self.msg_id {
+                trace_event!(id = header.id, "response discarded: message ID mismatch");
                 continue;
             }
 
@@ -202,22 +410,25 @@ impl ClientCtx<'_, '_, '_, '_> {
                     && question.qclass == self.qclass
                     && question.qname == self.qname
                 {
+                    trace_event!(
+                        elapsed = ?self.start.elapsed(),
+                        protocol = "udp",
+                        size,
+                        "response received"
+                    );
+                    self.config.check_trailing_bytes(response)?;
                     return Ok((size, header.flags));
                 }
             }
+            trace_event!("response discarded: question mismatch");
         }
     }
 
-    fn prepare_message(&mut self) -> Result<()> {
-        let opt = match self.config.edns_ {
-            EDns::On {
-                version,
-                udp_payload_size,
-            } => {
-                let ups = (udp_payload_size as usize).min(self.buf.len());
-                Some(Opt::new(version, ups as u16))
-            }
-            EDns::Off => None,
+    fn prepare_message(&mut self, msg_id: Option<u16>, edns: bool) -> Result<()> {
+        let opt = if edns {
+            self.config.opt(self.buf.len())
+        } else {
+            None
         };
 
         unsafe {
@@ -225,9 +436,14 @@ impl ClientCtx<'_, '_, '_, '_> {
         }
 
         let recursion = self.config.recursion_ == Recursion::On;
-        let mut qw = QueryWriter::new(&mut self.msg);
-
+        let mut qw = match msg_id {
+            Some(id) => QueryWriter::with_id(&mut self.msg, id),
+            None => QueryWriter::new(&mut self.msg),
+        };
         self.msg_id = qw.message_id();
+        qw.set_lowercase_qname(self.config.lowercase_qname_)
+            .set_require_fqdn(self.config.require_fqdn_);
+
         let msg_len = qw.write(self.qname, self.qtype, self.qclass, recursion, opt)?;
 
         unsafe {
@@ -239,7 +455,12 @@ impl ClientCtx<'_, '_, '_, '_> {
 
     #[inline]
     fn udp_first(&self) -> bool {
-        match self.config.protocol_strategy_ {
+        #[cfg(feature = "socks5")]
+        if self.config.socks5_proxy_.is_some() {
+            return false;
+        }
+
+        match self.protocol_strategy {
             ProtocolStrategy::Udp | ProtocolStrategy::NoTcp => true,
             ProtocolStrategy::Tcp => false,
         }
@@ -247,7 +468,7 @@ impl ClientCtx<'_, '_, '_, '_> {
 
     #[inline]
     fn tcp_allowed(&self) -> bool {
-        self.config.protocol_strategy_ != ProtocolStrategy::NoTcp
+        self.protocol_strategy != ProtocolStrategy::NoTcp
     }
 
     fn set_timeout_udp(sock: &UdpSocket, timeout: Duration) -> Result<()> {
@@ -266,19 +487,16 @@ impl ClientCtx<'_, '_, '_, '_> {
 
     fn lifetime_left(&self) -> Result<Duration> {
         let elapsed = self.start.elapsed();
-        if elapsed >= self.config.query_lifetime_ {
+        if elapsed >= self.lifetime {
             return Err(Error::Timeout);
         }
-        Ok(self.config.query_lifetime_ - elapsed)
+        Ok(self.lifetime - elapsed)
     }
 
     fn query_left(&self) -> Result<Duration> {
         let lifetime_left = self.lifetime_left()?;
 
-        let timeout = match self.config.query_timeout_ {
-            Some(duration) => duration,
-            _ => self.config.query_lifetime_,
-        };
+        let timeout = self.query_timeout;
 
         let elapsed = self.query_start.elapsed();
 
@@ -33,19 +33,27 @@ pub enum Error {
     DomainNameLabelInvalidChar(&'static str, u8),
 
     #[error(
-        "domain name label length exceeds allowed limit {}: {0}",
+        "domain name label length exceeds allowed limit {}: {len} (label {index} at offset {offset})",
         DOMAIN_NAME_LABEL_MAX_LENGTH
     )]
-    DomainNameLabelTooLong(usize),
+    DomainNameLabelTooLong {
+        index: u32,
+        offset: usize,
+        len: usize,
+    },
 
     #[error("domain name label is empty")]
     DomainNameLabelIsEmpty,
 
     #[error(
-        "domain name length exceeds allowed limit {}: {0}",
+        "domain name length exceeds allowed limit {}: {len} (label {index} at offset {offset})",
         DOMAIN_NAME_MAX_LENGTH
     )]
-    DomainNameTooLong(usize),
+    DomainNameTooLong {
+        index: u32,
+        offset: usize,
+        len: usize,
+    },
 
     #[error(
         "domain name pointer count exceeds allowed limit {}",
@@ -59,11 +67,11 @@ pub enum Error {
     #[error("domain name label pointer {pointer} exceeds buffer max offset {max_offset}")]
     DomainNameBadPointer { pointer: usize, max_offset: usize },
 
-    #[error("buffer end reached unexpectedly")]
-    EndOfBuffer,
+    #[error("buffer end reached unexpectedly at offset {offset}")]
+    EndOfBuffer { offset: usize },
 
-    #[error("buffer window end reached unexpectedly")]
-    EndOfWindow,
+    #[error("buffer window end reached unexpectedly at offset {offset}")]
+    EndOfWindow { offset: usize },
 
     #[error("cursor is already in window mode")]
     CursorAlreadyInWindow,
@@ -92,6 +100,12 @@ pub enum Error {
     #[error("message size exceeds 65535 bytes: {0}")]
     MessageTooLong(usize),
 
+    #[error("message size is less than the header length: {0}")]
+    MessageTooShort(usize),
+
+    #[error("response size {size} exceeds the allowed limit {max}")]
+    ResponseTooLong { size: usize, max: usize },
+
     #[error("section '{0}' offset is unknown")]
     RecordsSectionOffsetUnknown(RecordsSection),
 
@@ -114,6 +128,10 @@ pub enum Error {
     #[error("bad parameter: {0}")]
     BadParam(&'static str),
 
+    /// SOCKS5 proxy negotiation failed
+    #[error("SOCKS5 proxy error: {0}")]
+    Socks5Error(&'static str),
+
     /// *rsdns* tries to avoid panics. InternalError is used instead.
     #[error("internal error: {0}")]
     InternalError(&'static str),
@@ -121,6 +139,42 @@ pub enum Error {
     /// Records reader is exhausted or in error state
     #[error("records reader is exhausted or a previous call resulted in error")]
     ReaderDone,
+
+    /// Record data type doesn't match the record type
+    #[error("record type mismatch: expected {expected}, found {actual}")]
+    RecordTypeMismatch { expected: Type, actual: Type },
+
+    /// Message section contains fewer records than declared in the message header
+    #[error("{section} section count mismatch: {claimed} records declared, {actual} found")]
+    CountMismatch {
+        section: RecordsSection,
+        claimed: u16,
+        actual: u16,
+    },
+
+    /// More than one SPF record is published for a domain name
+    #[error("multiple SPF records found: {0}")]
+    MultipleSpfRecords(usize),
+
+    /// The `OPT` pseudo-record uses an EDNS version other than `0`
+    #[error("unsupported EDNS version: {0}")]
+    UnsupportedEdnsVersion(u8),
+
+    /// The `OPT` pseudo-record sets reserved EDNS flags
+    #[error("reserved EDNS flags are set: {0:#06X}")]
+    ReservedEdnsFlags(u16),
+
+    /// The message contains more than one `OPT` pseudo-record
+    #[error("message contains multiple OPT records")]
+    MultipleOptRecords,
+
+    /// The answer section contains a record not owned by the question name or its aliases
+    #[error("answer section contains a record with an unrelated owner name")]
+    UnrelatedOwnerName,
+
+    /// The message is followed by trailing bytes
+    #[error("message is followed by {0} trailing bytes")]
+    TrailingBytes(usize),
 }
 
 /// Class couldn't be created from a string.
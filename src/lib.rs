@@ -46,11 +46,19 @@
 //! 2. `net-async-std` - enables the [`clients::async_std`] module
 //! 3. `net-smol` - enables the [`clients::smol`] module
 //! 4. `net-std` - enables the [`clients::std`] module
-//! 5. `socket2` - together with `net-tokio` enables `bind-to-device` support
-//!    (currently on Linux only)
+//! 5. `socket2` - enables configuration of socket buffer sizes, and together with
+//!    `net-tokio` enables `bind-to-device` support and TCP Fast Open (currently on Linux only)
+//! 6. `serde` - enables serialization of [`Type`](records::Type) and [`Class`](records::Class)
+//!    with [`serde`](https://docs.rs/serde)
+//! 7. `socks5` - enables sending queries through a SOCKS5 proxy
+//!    (see [`ClientConfig::set_socks5_proxy`](clients::ClientConfig::set_socks5_proxy))
+//! 8. `tracing` - enables [`tracing`](https://docs.rs/tracing) spans and events in clients
+//! 9. `tokio-util` - enables [`DnsTcpCodec`](message::DnsTcpCodec), a
+//!    [`tokio-util`](https://docs.rs/tokio-util) codec for DNS messages transmitted over TCP
 //!
 //! Note that none of the features is enabled by default. The [`clients`] module exists only
-//! if one of the `net-*` features is enabled.
+//! if one of the `net-*` features is enabled. Any combination of the `net-*` features
+//! may be enabled at the same time.
 
 //! # Examples
 //!
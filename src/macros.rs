@@ -14,3 +14,12 @@ macro_rules! cfg_any_client {
         )*
     }
 }
+
+/// Emits a `tracing` debug event if the `tracing` feature is enabled; expands to nothing otherwise.
+#[allow(unused_macros)]
+macro_rules! trace_event {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($arg)*);
+    }
+}
@@ -1,4 +1,25 @@
-use crate::{bytes::Cursor, Result};
+use crate::{
+    bytes::{Cursor, WCursor},
+    Error, Result,
+};
+use std::fmt::{self, Formatter, Write};
+
+/// Formats a character-string in presentation format
+/// ([RFC 1035 section 5.1](https://www.rfc-editor.org/rfc/rfc1035.html#section-5.1)).
+///
+/// The string is enclosed in quotes. Quotes and backslashes are escaped with a backslash,
+/// and non-printable bytes are written as `\DDD` with a decimal byte value.
+pub(crate) fn fmt_character_string(f: &mut Formatter<'_>, s: &[u8]) -> fmt::Result {
+    f.write_char('"')?;
+    for &b in s {
+        match b {
+            b'"' | b'\\' => write!(f, "\\{}", b as char)?,
+            0x20..=0x7E => f.write_char(b as char)?,
+            _ => write!(f, "\\{b:03}")?,
+        }
+    }
+    f.write_char('"')
+}
 
 impl Cursor<'_> {
     pub fn read_character_string(&mut self) -> Result<Vec<u8>> {
@@ -6,3 +27,13 @@ impl Cursor<'_> {
         Ok(Vec::from(self.slice(len as usize)?))
     }
 }
+
+impl WCursor<'_> {
+    pub fn write_character_string(&mut self, s: &[u8]) -> Result<()> {
+        if s.len() > u8::MAX as usize {
+            return Err(Error::BadParam("character-string exceeds 255 bytes"));
+        }
+        self.u8(s.len() as u8)?;
+        self.bytes(s)
+    }
+}
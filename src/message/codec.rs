@@ -0,0 +1,125 @@
+use crate::{constants::DNS_MESSAGE_MAX_LENGTH, Error, Result};
+use bytes::{Buf, BufMut, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+/// A [`tokio_util::codec`] for DNS messages transmitted over TCP.
+///
+/// DNS messages sent over TCP are prefixed with a two byte length field ([RFC 1035 section 4.2.2]).
+/// `DnsTcpCodec` handles this framing: the [`Decoder`] yields whole messages without the length
+/// prefix, and the [`Encoder`] accepts whole messages and prepends the length prefix.
+///
+/// The codec doesn't parse messages. Use [`MessageReader`] to parse decoded messages.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytes::BytesMut;
+/// use rsdns::message::DnsTcpCodec;
+/// use tokio_util::codec::{Decoder, Encoder};
+///
+/// let mut codec = DnsTcpCodec::new();
+/// let mut buf = BytesMut::new();
+///
+/// codec.encode(&b"message"[..], &mut buf).unwrap();
+/// assert_eq!(&buf[..], b"\x00\x07message");
+///
+/// let msg = codec.decode(&mut buf).unwrap().unwrap();
+/// assert_eq!(&msg[..], b"message");
+/// assert!(buf.is_empty());
+/// ```
+///
+/// [RFC 1035 section 4.2.2]: https://www.rfc-editor.org/rfc/rfc1035.html#section-4.2.2
+/// [`MessageReader`]: crate::message::reader::MessageReader
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio-util")))]
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct DnsTcpCodec {
+    _priv: (),
+}
+
+impl DnsTcpCodec {
+    /// Creates a new codec.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Decoder for DnsTcpCodec {
+    type Item = BytesMut;
+    type Error = Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<BytesMut>> {
+        if src.len() < 2 {
+            return Ok(None);
+        }
+
+        let msg_len = u16::from_be_bytes([src[0], src[1]]) as usize;
+        let frame_len = 2 + msg_len;
+        if src.len() < frame_len {
+            src.reserve(frame_len - src.len());
+            return Ok(None);
+        }
+
+        src.advance(2);
+        Ok(Some(src.split_to(msg_len)))
+    }
+}
+
+impl<T: AsRef<[u8]>> Encoder<T> for DnsTcpCodec {
+    type Error = Error;
+
+    fn encode(&mut self, item: T, dst: &mut BytesMut) -> Result<()> {
+        let msg = item.as_ref();
+        if msg.len() > DNS_MESSAGE_MAX_LENGTH {
+            return Err(Error::MessageTooLong(msg.len()));
+        }
+
+        dst.reserve(2 + msg.len());
+        dst.put_u16(msg.len() as u16);
+        dst.extend_from_slice(msg);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_partial() {
+        let mut codec = DnsTcpCodec::new();
+        let mut buf = BytesMut::new();
+
+        assert!(codec.decode(&mut buf).unwrap().is_none());
+
+        buf.extend_from_slice(&[0]);
+        assert!(codec.decode(&mut buf).unwrap().is_none());
+
+        buf.extend_from_slice(&[3, 1, 2]);
+        assert!(codec.decode(&mut buf).unwrap().is_none());
+        assert_eq!(buf.len(), 4);
+
+        buf.extend_from_slice(&[3, 0, 1, 4]);
+        assert_eq!(&codec.decode(&mut buf).unwrap().unwrap()[..], [1, 2, 3]);
+        assert_eq!(&buf[..], [0, 1, 4]);
+        assert_eq!(&codec.decode(&mut buf).unwrap().unwrap()[..], [4]);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_encode() {
+        let mut codec = DnsTcpCodec::new();
+        let mut buf = BytesMut::new();
+
+        codec.encode(vec![1, 2, 3], &mut buf).unwrap();
+        codec.encode([], &mut buf).unwrap();
+        assert_eq!(&buf[..], [0, 3, 1, 2, 3, 0, 0]);
+
+        let too_long = vec![0u8; u16::MAX as usize + 1];
+        assert!(matches!(
+            codec.encode(&too_long, &mut buf),
+            Err(Error::MessageTooLong(l)) if l == too_long.len()
+        ));
+        assert_eq!(buf.len(), 7);
+    }
+}
@@ -6,17 +6,15 @@ macro_rules! get_bit {
     };
 }
 
-cfg_any_client! {
-    macro_rules! set_bit {
-        ($e:expr, $l:literal, $v:ident) => {
-            let mask = 1 << $l;
-            if $v {
-                $e |= mask;
-            } else {
-                $e &= !mask;
-            }
-        };
-    }
+macro_rules! set_bit {
+    ($e:expr, $l:literal, $v:ident) => {
+        let mask = 1 << $l;
+        if $v {
+            $e |= mask;
+        } else {
+            $e &= !mask;
+        }
+    };
 }
 
 /// Message flags.
@@ -35,13 +33,20 @@ impl Flags {
         Flags { bits: 0 }
     }
 
+    /// Returns the raw 16-bit flags word, as stored in the message header.
+    ///
+    /// This includes the reserved `Z` bits, so it allows a bit-for-bit reproduction of the flags.
+    #[inline]
+    pub const fn bits(self) -> u16 {
+        self.bits
+    }
+
     /// Returns the message type.
     pub fn message_type(self) -> MessageType {
         (get_bit!(self.bits, 15)).into()
     }
 
     /// Sets the message type.
-    #[cfg(test)]
     pub(crate) fn set_message_type(&mut self, message_type: MessageType) -> &mut Self {
         let value: bool = message_type.into();
         set_bit!(self.bits, 15, value);
@@ -56,7 +61,6 @@ impl Flags {
     }
 
     /// Sets the message opcode.
-    #[cfg(test)]
     pub(crate) fn set_opcode(&mut self, opcode: OpCode) -> &mut Self {
         let mask = 0b0111_1000_0000_0000;
         self.bits = (self.bits & !mask) | ((opcode.value() as u16) << 11);
@@ -72,7 +76,6 @@ impl Flags {
     }
 
     /// Sets the authoritative answer flag.
-    #[cfg(test)]
     pub(crate) fn set_authoritative_answer(&mut self, value: bool) -> &mut Self {
         set_bit!(self.bits, 10, value);
         self
@@ -87,7 +90,6 @@ impl Flags {
     }
 
     /// Sets the truncated flag.
-    #[cfg(test)]
     pub(crate) fn set_truncated(&mut self, value: bool) -> &mut Self {
         set_bit!(self.bits, 9, value);
         self
@@ -101,12 +103,10 @@ impl Flags {
         get_bit!(self.bits, 8)
     }
 
-    cfg_any_client! {
-        /// Sets the recursion desired flag.
-        pub(crate) fn set_recursion_desired(&mut self, value: bool) -> &mut Self {
-            set_bit!(self.bits, 8, value);
-            self
-        }
+    /// Sets the recursion desired flag.
+    pub(crate) fn set_recursion_desired(&mut self, value: bool) -> &mut Self {
+        set_bit!(self.bits, 8, value);
+        self
     }
 
     /// Returns the recursion available flag.
@@ -118,7 +118,6 @@ impl Flags {
     }
 
     /// Sets the recursion available flag.
-    #[cfg(test)]
     pub(crate) fn set_recursion_available(&mut self, value: bool) -> &mut Self {
         set_bit!(self.bits, 7, value);
         self
@@ -146,9 +145,9 @@ impl Flags {
     }
 
     /// Sets the response code.
-    #[cfg(test)]
     pub(crate) fn set_response_code(&mut self, rcode: RCode) -> &mut Self {
-        self.bits |= rcode.value();
+        let mask = 0b0000_0000_0000_1111;
+        self.bits = (self.bits & !mask) | (rcode.value() & mask);
         self
     }
 }
@@ -188,6 +187,7 @@ mod tests {
         set(&mut f, true);
         assert!(get(f));
         assert_eq!(u16::from(f), mask);
+        assert_eq!(f.bits(), mask);
 
         set(&mut f, false);
         assert!(!get(f));
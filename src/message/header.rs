@@ -1,7 +1,7 @@
 use crate::{
     bytes::{Cursor, Reader},
     constants::HEADER_LENGTH,
-    message::Flags,
+    message::{Flags, MessageType, SectionCounts},
     Error, Result,
 };
 
@@ -26,22 +26,74 @@ pub struct Header {
     pub ar_count: u16,
 }
 
-cfg_any_client! {
-    impl crate::bytes::Writer<Header> for crate::bytes::WCursor<'_> {
-        fn write(&mut self, h: &Header) -> Result<usize> {
-            if self.len() >= HEADER_LENGTH {
-                unsafe {
-                    self.u16_be_unchecked(h.id);
-                    self.u16_be_unchecked(h.flags.into());
-                    self.u16_be_unchecked(h.qd_count);
-                    self.u16_be_unchecked(h.an_count);
-                    self.u16_be_unchecked(h.ns_count);
-                    self.u16_be_unchecked(h.ar_count);
-                }
-                Ok(HEADER_LENGTH)
-            } else {
-                Err(Error::EndOfBuffer)
+impl Header {
+    /// Returns the message type.
+    ///
+    /// This is a shortcut for [`Flags::message_type`].
+    #[inline]
+    pub fn message_type(&self) -> MessageType {
+        self.flags.message_type()
+    }
+
+    /// Checks if the message is a query.
+    #[inline]
+    pub fn is_query(&self) -> bool {
+        self.message_type().is_query()
+    }
+
+    /// Checks if the message is a response.
+    #[inline]
+    pub fn is_response(&self) -> bool {
+        self.message_type().is_response()
+    }
+
+    /// Returns the raw 16-bit flags word.
+    ///
+    /// This is a shortcut for [`Flags::bits`].
+    #[inline]
+    pub fn raw_flags(&self) -> u16 {
+        self.flags.bits()
+    }
+
+    /// Returns the number of entries in each section of the message.
+    ///
+    /// This is the same as the four `*_count` fields, named after the sections they count.
+    #[inline]
+    pub fn counts(&self) -> SectionCounts {
+        SectionCounts {
+            questions: self.qd_count,
+            answers: self.an_count,
+            authorities: self.ns_count,
+            additionals: self.ar_count,
+        }
+    }
+
+    /// Returns the recursion available flag.
+    ///
+    /// This is a shortcut for [`Flags::recursion_available`].
+    ///
+    /// Note that authoritative-only nameservers do not set this flag. *rsdns* clients do not
+    /// treat a cleared flag as an error.
+    #[inline]
+    pub fn recursion_available(&self) -> bool {
+        self.flags.recursion_available()
+    }
+}
+
+impl crate::bytes::Writer<Header> for crate::bytes::WCursor<'_> {
+    fn write(&mut self, h: &Header) -> Result<usize> {
+        if self.len() >= HEADER_LENGTH {
+            unsafe {
+                self.u16_be_unchecked(h.id);
+                self.u16_be_unchecked(h.flags.into());
+                self.u16_be_unchecked(h.qd_count);
+                self.u16_be_unchecked(h.an_count);
+                self.u16_be_unchecked(h.ns_count);
+                self.u16_be_unchecked(h.ar_count);
             }
+            Ok(HEADER_LENGTH)
+        } else {
+            Err(Error::EndOfBuffer { offset: self.pos() })
         }
     }
 }
@@ -60,7 +112,7 @@ impl Reader<Header> for Cursor<'_> {
                 })
             }
         } else {
-            Err(Error::EndOfBuffer)
+            Err(Error::EndOfBuffer { offset: self.pos() })
         }
     }
 }
@@ -105,9 +157,53 @@ mod tests {
 
         let mut cursor = Cursor::new(&buf[..]);
 
-        let another = cursor.read().unwrap();
+        let another: Header = cursor.read().unwrap();
 
         assert_eq!(header, another);
+        assert_eq!(another.raw_flags(), u16::from_be_bytes([buf[2], buf[3]]));
+    }
+
+    #[test]
+    fn test_message_type() {
+        let mut header = Header::default();
+        assert_eq!(header.message_type(), MessageType::Query);
+        assert!(header.is_query());
+        assert!(!header.is_response());
+
+        header.flags.set_message_type(MessageType::Response);
+        assert_eq!(header.message_type(), MessageType::Response);
+        assert!(!header.is_query());
+        assert!(header.is_response());
+    }
+
+    #[test]
+    fn test_counts() {
+        let header = Header {
+            qd_count: 1,
+            an_count: 2,
+            ns_count: 3,
+            ar_count: 4,
+            ..Default::default()
+        };
+        assert_eq!(
+            header.counts(),
+            SectionCounts {
+                questions: 1,
+                answers: 2,
+                authorities: 3,
+                additionals: 4,
+            }
+        );
+        assert_eq!(Header::default().counts(), SectionCounts::default());
+    }
+
+    #[test]
+    fn test_recursion_available() {
+        let mut header = Header::default();
+        assert!(!header.recursion_available());
+
+        header.flags.set_recursion_available(true);
+        assert!(header.recursion_available());
     }
 
     #[test]
@@ -116,21 +212,21 @@ mod tests {
         let mut small_arr = [0u8; HEADER_LENGTH - 1];
 
         let res: Result<Header> = Cursor::new(&empty_arr[..]).read();
-        assert!(matches!(res, Err(Error::EndOfBuffer)));
+        assert!(matches!(res, Err(Error::EndOfBuffer { offset: 0 })));
 
         let res: Result<Header> = Cursor::new(&small_arr[..]).read();
-        assert!(matches!(res, Err(Error::EndOfBuffer)));
+        assert!(matches!(res, Err(Error::EndOfBuffer { offset: 0 })));
 
         let header = Header::default();
 
         assert!(matches!(
             WCursor::new(&mut empty_arr[..]).write(&header),
-            Err(Error::EndOfBuffer)
+            Err(Error::EndOfBuffer { offset: 0 })
         ));
 
         assert!(matches!(
             WCursor::new(&mut small_arr[..]).write(&header),
-            Err(Error::EndOfBuffer)
+            Err(Error::EndOfBuffer { offset: 0 })
         ));
     }
 }
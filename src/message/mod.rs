@@ -1,6 +1,12 @@
 //! Message handling.
 
 mod character_string;
+pub(crate) use character_string::fmt_character_string;
+
+#[cfg(feature = "tokio-util")]
+mod codec;
+#[cfg(feature = "tokio-util")]
+pub use codec::*;
 
 mod flags;
 pub use flags::*;
@@ -17,17 +23,19 @@ pub use records_section::*;
 mod opcode;
 pub use opcode::*;
 
-cfg_any_client! {
-    mod query_writer;
-
-    #[cfg_attr(test, allow(unused_imports))]
-    pub(crate) use query_writer::*;
-}
+mod query_writer;
+pub use query_writer::*;
 
 mod question;
 pub use question::*;
 
 pub mod reader;
 
+mod response_builder;
+pub use response_builder::*;
+
+mod section_counts;
+pub use section_counts::*;
+
 mod rcode;
 pub use rcode::*;
@@ -1,28 +1,127 @@
 use crate::{
     bytes::{WCursor, Writer},
+    constants::DNS_MESSAGE_MAX_LENGTH,
     message::{Flags, Header},
     records::{Class, Opt, Type},
-    Result,
+    Error, Result,
 };
 
+/// A DNS query writer.
+///
+/// `QueryWriter` serializes a query message into a caller-owned buffer. It allows sending
+/// queries over a custom transport, while the response is parsed with
+/// [`MessageReader`](crate::message::reader::MessageReader).
+///
+/// The query is written with the two-byte length prefix used by DNS over TCP
+/// ([RFC 1035 section 4.2.2]). For TCP-like transports the whole written buffer is sent.
+/// For UDP-like transports the first two bytes must be skipped.
+///
+/// # Examples
+///
+/// ```rust
+/// use rsdns::{
+///     message::QueryWriter,
+///     records::{Class, Type},
+/// };
+///
+/// # fn foo() -> rsdns::Result<()> {
+/// let mut buf = [0u8; 512];
+/// let mut qw = QueryWriter::new(&mut buf);
+/// let msg_id = qw.message_id();
+/// let len = qw.write("example.com", Type::A, Class::IN, true, None)?;
+///
+/// let tcp_message = &buf[..len];
+/// let udp_message = &buf[2..len];
+/// # assert_eq!(u16::from_be_bytes([buf[2], buf[3]]), msg_id);
+/// # Ok(())
+/// # }
+/// # foo().unwrap();
+/// ```
+///
+/// [RFC 1035 section 4.2.2]: https://www.rfc-editor.org/rfc/rfc1035.html#section-4.2.2
 pub struct QueryWriter<'a> {
     wcursor: WCursor<'a>,
     id: u16,
+    lowercase_qname: bool,
+    require_fqdn: bool,
+    raw_flags: Option<u16>,
 }
 
 impl<'a> QueryWriter<'a> {
+    /// Creates a query writer with a random message ID.
     pub fn new(buf: &'a mut [u8]) -> Self {
+        Self::with_id(buf, rand::random::<u16>())
+    }
+
+    /// Creates a query writer with a specific message ID.
+    pub fn with_id(buf: &'a mut [u8], id: u16) -> Self {
         QueryWriter {
             wcursor: WCursor::new(buf),
-            id: rand::random::<u16>(),
+            id,
+            lowercase_qname: false,
+            require_fqdn: false,
+            raw_flags: None,
         }
     }
 
+    /// Returns the message ID of the query.
     #[inline]
     pub fn message_id(&self) -> u16 {
         self.id
     }
 
+    /// Sets whether the query name is converted to ASCII lowercase when written.
+    ///
+    /// By default the query name is written as provided.
+    pub fn set_lowercase_qname(&mut self, value: bool) -> &mut Self {
+        self.lowercase_qname = value;
+        self
+    }
+
+    /// Sets whether the query name must be fully qualified, i.e. end with a dot.
+    ///
+    /// By default the query name is considered absolute regardless of the trailing dot,
+    /// i.e. `"example.com"` and `"example.com."` produce the same query.
+    /// When set, [`write`] fails with [`Error::BadParam`] for a query name without the
+    /// trailing dot.
+    ///
+    /// [`write`]: Self::write
+    pub fn set_require_fqdn(&mut self, value: bool) -> &mut Self {
+        self.require_fqdn = value;
+        self
+    }
+
+    /// Sets the raw 16-bit flags word of the query.
+    ///
+    /// This is a low-level escape hatch intended for conformance testing of nameservers only.
+    /// When set, the flags word is written as is, overriding all flags derived from the
+    /// arguments of [`write`], e.g. the recursion desired flag. This allows setting the
+    /// reserved `Z` bits, or sending otherwise malformed queries.
+    ///
+    /// Default: `None` - flags are derived from the arguments of [`write`]
+    ///
+    /// [`write`]: Self::write
+    pub fn set_raw_flags(&mut self, bits: Option<u16>) -> &mut Self {
+        self.raw_flags = bits;
+        self
+    }
+
+    /// Writes a query message with a single question.
+    ///
+    /// The query name is always absolute. The root label is appended to it if missing,
+    /// i.e. `"example.com"` and `"example.com."` denote the same name.
+    /// The root zone may be queried with either `"."` or `""`.
+    ///
+    /// If `opt` is specified, an `OPT` pseudo-record is written to the additional section.
+    ///
+    /// Returns the number of bytes written, including the two-byte length prefix.
+    ///
+    /// # Returns
+    ///
+    /// - [`Error::EndOfBuffer`] - if the buffer is too short
+    /// - [`Error::MessageTooLong`] - if the message exceeds 65535 bytes
+    /// - [`Error::BadParam`] - if `qname` doesn't end with a dot and
+    ///   [`set_require_fqdn`](Self::set_require_fqdn) is enabled
     pub fn write(
         &mut self,
         qname: &str,
@@ -31,9 +130,19 @@ impl<'a> QueryWriter<'a> {
         recursion_desired: bool,
         opt: Option<Opt>,
     ) -> Result<usize> {
+        let qname = if qname.is_empty() { "." } else { qname };
+        if self.require_fqdn && !qname.ends_with('.') {
+            return Err(Error::BadParam("fully qualified domain name is required"));
+        }
+
+        let flags = match self.raw_flags {
+            Some(bits) => Flags::from(bits),
+            None => *Flags::new().set_recursion_desired(recursion_desired),
+        };
+
         let header = Header {
             id: self.id,
-            flags: *Flags::new().set_recursion_desired(recursion_desired),
+            flags,
             qd_count: 1,
             ar_count: u16::from(opt.is_some()),
             ..Default::default()
@@ -41,7 +150,12 @@ impl<'a> QueryWriter<'a> {
 
         self.wcursor.u16_be(0)?;
         self.wcursor.write(&header)?;
+        let qname_pos = self.wcursor.pos();
         self.wcursor.write_domain_name(qname)?;
+        if self.lowercase_qname {
+            // label length bytes are at most 63, so they are not affected
+            self.wcursor.written_from(qname_pos).make_ascii_lowercase();
+        }
         self.wcursor.u16_be(qtype.value())?;
         self.wcursor.u16_be(qclass.value())?;
 
@@ -50,6 +164,9 @@ impl<'a> QueryWriter<'a> {
         }
 
         let pos = self.wcursor.reset_pos();
+        if pos - 2 > DNS_MESSAGE_MAX_LENGTH {
+            return Err(Error::MessageTooLong(pos - 2));
+        }
         self.wcursor.u16_be((pos - 2) as u16)?;
         Ok(pos)
     }
@@ -96,6 +213,99 @@ mod tests {
         assert_eq!(qc, Class::IN);
     }
 
+    #[test]
+    fn test_with_id() {
+        let mut query = [0u8; 512];
+        let mut qw = QueryWriter::with_id(&mut query[..], 0x1234);
+        assert_eq!(qw.message_id(), 0x1234);
+
+        let size = qw
+            .write("host.example.com", Type::A, Class::IN, true, None)
+            .unwrap();
+
+        let mut c = Cursor::new(&query[2..size]);
+        let header: Header = c.read().unwrap();
+        assert_eq!(header.id, 0x1234);
+    }
+
+    #[test]
+    fn test_lowercase_qname() {
+        for (lowercase, expected) in [
+            (false, b"\x04HoSt\x07ExAmPlE\x03CoM\x00"),
+            (true, b"\x04host\x07example\x03com\x00"),
+        ] {
+            let mut query = [0u8; 512];
+            let mut qw = QueryWriter::new(&mut query[..]);
+            qw.set_lowercase_qname(lowercase);
+
+            let size = qw
+                .write("HoSt.ExAmPlE.CoM", Type::A, Class::IN, true, None)
+                .unwrap();
+            assert_eq!(size, 34 + 2);
+            assert_eq!(&query[14..32], expected);
+        }
+    }
+
+    #[test]
+    fn test_trailing_dot() {
+        let mut relative = [0u8; 512];
+        let size = QueryWriter::with_id(&mut relative[..], 1)
+            .write("host.example.com", Type::A, Class::IN, true, None)
+            .unwrap();
+
+        let mut absolute = [0u8; 512];
+        let mut qw = QueryWriter::with_id(&mut absolute[..], 1);
+        qw.set_require_fqdn(true);
+        let size_abs = qw
+            .write("host.example.com.", Type::A, Class::IN, true, None)
+            .unwrap();
+
+        assert_eq!(size, size_abs);
+        assert_eq!(relative[..size], absolute[..size]);
+
+        let mut query = [0u8; 512];
+        let mut qw = QueryWriter::new(&mut query[..]);
+        qw.set_require_fqdn(true);
+        assert!(matches!(
+            qw.write("host.example.com", Type::A, Class::IN, true, None),
+            Err(Error::BadParam(_))
+        ));
+        assert!(qw.write(".", Type::NS, Class::IN, true, None).is_ok());
+    }
+
+    #[test]
+    fn test_root_qname() {
+        let mut root = [0u8; 512];
+        let size = QueryWriter::with_id(&mut root[..], 1)
+            .write(".", Type::NS, Class::IN, true, None)
+            .unwrap();
+        assert_eq!(size, 2 + 12 + 1 + 4);
+        assert_eq!(root[14..size], [0, 0, 2, 0, 1]);
+
+        let mut empty = [0u8; 512];
+        let mut qw = QueryWriter::with_id(&mut empty[..], 1);
+        qw.set_require_fqdn(true);
+        let size_empty = qw.write("", Type::NS, Class::IN, true, None).unwrap();
+        assert_eq!(root[..size], empty[..size_empty]);
+    }
+
+    #[test]
+    fn test_raw_flags() {
+        let mut query = [0u8; 512];
+        let mut qw = QueryWriter::new(&mut query[..]);
+        qw.set_raw_flags(Some(0b0000_0000_0111_0000));
+
+        let size = qw
+            .write("host.example.com", Type::A, Class::IN, true, None)
+            .unwrap();
+
+        let mut c = Cursor::new(&query[2..size]);
+        let header: Header = c.read().unwrap();
+        assert_eq!(header.flags.z(), 0b111);
+        assert!(!header.flags.recursion_desired());
+        assert_eq!(header.raw_flags(), 0b0000_0000_0111_0000);
+    }
+
     #[test]
     fn test_opt() {
         let mut query = [0u8; 512];
@@ -1,6 +1,6 @@
 use crate::{
-    bytes::{Cursor, Reader},
-    names::InlineName,
+    bytes::{Cursor, Reader, WCursor},
+    names::{wire_length, InlineName},
     records::{Class, Type},
     Result,
 };
@@ -20,6 +20,76 @@ pub struct Question {
     pub qclass: Class,
 }
 
+impl Question {
+    /// Encodes the question in wire format.
+    ///
+    /// The question is encoded as in the questions section of a message: the uncompressed
+    /// query name, followed by the type and the class. The case of the name is preserved.
+    /// The encoded question is independent of a message, e.g. it may be used as a cache key.
+    ///
+    /// # Returns
+    ///
+    /// - [`Error::DomainNameTooLong`] - if the encoded name exceeds 255 bytes
+    /// - any other error encountered while validating the labels of the name
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rsdns::{
+    ///     message::Question,
+    ///     names::InlineName,
+    ///     records::{Class, Type},
+    /// };
+    /// use std::str::FromStr;
+    ///
+    /// # fn foo() -> rsdns::Result<()> {
+    /// let question = Question {
+    ///     qname: InlineName::from_str("example.com")?,
+    ///     qtype: Type::A,
+    ///     qclass: Class::IN,
+    /// };
+    /// assert_eq!(question.to_wire()?, b"\x07example\x03com\x00\x00\x01\x00\x01");
+    /// # Ok(())
+    /// # }
+    /// # foo().unwrap();
+    /// ```
+    ///
+    /// [`Error::DomainNameTooLong`]: crate::Error::DomainNameTooLong
+    pub fn to_wire(&self) -> Result<Vec<u8>> {
+        let mut buf = vec![0u8; wire_length(self.qname.as_str()) + 4];
+        let mut wcursor = WCursor::new(&mut buf);
+        wcursor.write_domain_name(self.qname.as_str())?;
+        wcursor.u16_be(self.qtype.value())?;
+        wcursor.u16_be(self.qclass.value())?;
+        Ok(buf)
+    }
+}
+
+impl std::fmt::Display for Question {
+    /// Formats the question as the name, the class and the type.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rsdns::{
+    ///     message::Question,
+    ///     names::InlineName,
+    ///     records::{Class, Type},
+    /// };
+    /// use std::str::FromStr;
+    ///
+    /// let question = Question {
+    ///     qname: InlineName::from_str("example.com").unwrap(),
+    ///     qtype: Type::A,
+    ///     qclass: Class::IN,
+    /// };
+    /// assert_eq!(question.to_string(), "example.com. IN A");
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.qname, self.qclass, self.qtype)
+    }
+}
+
 impl Reader<Question> for Cursor<'_> {
     fn read(&mut self) -> Result<Question> {
         Ok(Question {
@@ -29,3 +99,31 @@ impl Reader<Question> for Cursor<'_> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_to_wire() {
+        for (qname, qtype, qclass) in [
+            (".", Type::NS, Class::IN),
+            ("ExAmPlE.com.", Type::AAAA, Class::CH),
+        ] {
+            let question = Question {
+                qname: InlineName::from_str(qname).unwrap(),
+                qtype,
+                qclass,
+            };
+            let wire = question.to_wire().unwrap();
+            assert_eq!(wire.len(), wire_length(qname) + 4);
+
+            let mut cursor = Cursor::new(&wire);
+            let decoded: Question = cursor.read().unwrap();
+            assert_eq!(decoded, question);
+            assert_eq!(decoded.qname.as_str(), question.qname.as_str());
+            assert!(cursor.is_empty());
+        }
+    }
+}
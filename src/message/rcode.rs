@@ -9,27 +9,36 @@ const UNKNOWN_RCODE: &str = "__UNKNOWN_RCODE__";
 const RFC3597_PFX: &str = "RCODE";
 
 #[rustfmt::skip]
-static NAMES: [&str; 17] = [
+static NAMES: [&str; 24] = [
     "NOERROR",          // 0
     "FORMERR",          // 1
     "SERVFAIL",         // 2
     "NXDOMAIN",         // 3
     "NOTIMP",           // 4
     "REFUSED",          // 5
-    UNKNOWN_RCODE,      // 6
-    UNKNOWN_RCODE,      // 7
-    UNKNOWN_RCODE,      // 8
-    UNKNOWN_RCODE,      // 9
-    UNKNOWN_RCODE,      // 10
-    UNKNOWN_RCODE,      // 11
+    "YXDOMAIN",         // 6
+    "YXRRSET",          // 7
+    "NXRRSET",          // 8
+    "NOTAUTH",          // 9
+    "NOTZONE",          // 10
+    "DSOTYPENI",        // 11
     UNKNOWN_RCODE,      // 12
     UNKNOWN_RCODE,      // 13
     UNKNOWN_RCODE,      // 14
     UNKNOWN_RCODE,      // 15
     "BADVERS",          // 16
+    "BADKEY",           // 17
+    "BADTIME",          // 18
+    "BADMODE",          // 19
+    "BADNAME",          // 20
+    "BADALG",           // 21
+    "BADTRUNC",         // 22
+    "BADCOOKIE",        // 23
 ];
 
-static KNOWN: [u8; 17] = [1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1];
+static KNOWN: [u8; 24] = [
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1,
+];
 
 /// DNS response code.
 ///
@@ -101,20 +110,72 @@ impl RCode {
     pub const NOTIMP: RCode = RCode::new(4);
     /// Refused - the name server refuses to perform the specified operation for policy reasons.
     pub const REFUSED: RCode = RCode::new(5);
+    /// Some name that ought not to exist, does exist
+    /// [RFC 2136 section 2.2](https://www.rfc-editor.org/rfc/rfc2136.html#section-2.2)
+    pub const YXDOMAIN: RCode = RCode::new(6);
+    /// Some RRset that ought not to exist, does exist
+    /// [RFC 2136 section 2.2](https://www.rfc-editor.org/rfc/rfc2136.html#section-2.2)
+    pub const YXRRSET: RCode = RCode::new(7);
+    /// Some RRset that ought to exist, does not exist
+    /// [RFC 2136 section 2.2](https://www.rfc-editor.org/rfc/rfc2136.html#section-2.2)
+    pub const NXRRSET: RCode = RCode::new(8);
+    /// The server is not authoritative for the zone named in the Zone Section
+    /// [RFC 2136 section 2.2](https://www.rfc-editor.org/rfc/rfc2136.html#section-2.2)
+    pub const NOTAUTH: RCode = RCode::new(9);
+    /// A name used in the Prerequisite or Update Section is not within the zone
+    /// [RFC 2136 section 2.2](https://www.rfc-editor.org/rfc/rfc2136.html#section-2.2)
+    pub const NOTZONE: RCode = RCode::new(10);
+    /// DSO-TYPE not implemented
+    /// [RFC 8490 section 10.2](https://www.rfc-editor.org/rfc/rfc8490.html#section-10.2)
+    pub const DSOTYPENI: RCode = RCode::new(11);
     /// Bad version
     /// [RFC 2671 section 4.6](https://www.rfc-editor.org/rfc/rfc2671.html#section-4.6)
     pub const BADVERS: RCode = RCode::new(16);
+    /// Key not recognized
+    /// [RFC 8945 section 5.3.2](https://www.rfc-editor.org/rfc/rfc8945.html#section-5.3.2)
+    pub const BADKEY: RCode = RCode::new(17);
+    /// Signature out of time window
+    /// [RFC 8945 section 5.2.3](https://www.rfc-editor.org/rfc/rfc8945.html#section-5.2.3)
+    pub const BADTIME: RCode = RCode::new(18);
+    /// Bad TKEY mode
+    /// [RFC 2930 section 2.6](https://www.rfc-editor.org/rfc/rfc2930.html#section-2.6)
+    pub const BADMODE: RCode = RCode::new(19);
+    /// Duplicate key name
+    /// [RFC 2930 section 2.6](https://www.rfc-editor.org/rfc/rfc2930.html#section-2.6)
+    pub const BADNAME: RCode = RCode::new(20);
+    /// Algorithm not supported
+    /// [RFC 2930 section 2.6](https://www.rfc-editor.org/rfc/rfc2930.html#section-2.6)
+    pub const BADALG: RCode = RCode::new(21);
+    /// Bad truncation
+    /// [RFC 8945 section 5.2.2.1](https://www.rfc-editor.org/rfc/rfc8945.html#section-5.2.2.1)
+    pub const BADTRUNC: RCode = RCode::new(22);
+    /// Bad/missing server cookie
+    /// [RFC 7873 section 8](https://www.rfc-editor.org/rfc/rfc7873.html#section-8)
+    pub const BADCOOKIE: RCode = RCode::new(23);
 
     #[cfg(test)]
     #[allow(missing_docs)]
-    pub const VALUES: [RCode; 7] = [
+    pub const VALUES: [RCode; 20] = [
         Self::NOERROR,
         Self::FORMERR,
         Self::SERVFAIL,
         Self::NXDOMAIN,
         Self::NOTIMP,
         Self::REFUSED,
+        Self::YXDOMAIN,
+        Self::YXRRSET,
+        Self::NXRRSET,
+        Self::NOTAUTH,
+        Self::NOTZONE,
+        Self::DSOTYPENI,
         Self::BADVERS,
+        Self::BADKEY,
+        Self::BADTIME,
+        Self::BADMODE,
+        Self::BADNAME,
+        Self::BADALG,
+        Self::BADTRUNC,
+        Self::BADCOOKIE,
     ];
 
     #[inline]
@@ -206,18 +267,34 @@ impl RCode {
         match name.len() {
             6 => match name {
                 "NOTIMP" => Ok(RCode::NOTIMP),
+                "BADKEY" => Ok(RCode::BADKEY),
+                "BADALG" => Ok(RCode::BADALG),
                 _ => Err(UnknownRCodeName),
             },
             7 => match name {
                 "NOERROR" => Ok(RCode::NOERROR),
                 "FORMERR" => Ok(RCode::FORMERR),
                 "REFUSED" => Ok(RCode::REFUSED),
+                "YXRRSET" => Ok(RCode::YXRRSET),
+                "NXRRSET" => Ok(RCode::NXRRSET),
+                "NOTAUTH" => Ok(RCode::NOTAUTH),
+                "NOTZONE" => Ok(RCode::NOTZONE),
                 "BADVERS" => Ok(RCode::BADVERS),
+                "BADTIME" => Ok(RCode::BADTIME),
+                "BADMODE" => Ok(RCode::BADMODE),
+                "BADNAME" => Ok(RCode::BADNAME),
                 _ => Err(UnknownRCodeName),
             },
             8 => match name {
                 "SERVFAIL" => Ok(RCode::SERVFAIL),
                 "NXDOMAIN" => Ok(RCode::NXDOMAIN),
+                "YXDOMAIN" => Ok(RCode::YXDOMAIN),
+                "BADTRUNC" => Ok(RCode::BADTRUNC),
+                _ => Err(UnknownRCodeName),
+            },
+            9 => match name {
+                "DSOTYPENI" => Ok(RCode::DSOTYPENI),
+                "BADCOOKIE" => Ok(RCode::BADCOOKIE),
                 _ => Err(UnknownRCodeName),
             },
             _ => Err(UnknownRCodeName),
@@ -358,7 +435,20 @@ mod test {
         assert_eq!(RCode::NXDOMAIN.name(), "NXDOMAIN");
         assert_eq!(RCode::NOTIMP.name(), "NOTIMP");
         assert_eq!(RCode::REFUSED.name(), "REFUSED");
+        assert_eq!(RCode::YXDOMAIN.name(), "YXDOMAIN");
+        assert_eq!(RCode::YXRRSET.name(), "YXRRSET");
+        assert_eq!(RCode::NXRRSET.name(), "NXRRSET");
+        assert_eq!(RCode::NOTAUTH.name(), "NOTAUTH");
+        assert_eq!(RCode::NOTZONE.name(), "NOTZONE");
+        assert_eq!(RCode::DSOTYPENI.name(), "DSOTYPENI");
         assert_eq!(RCode::BADVERS.name(), "BADVERS");
+        assert_eq!(RCode::BADKEY.name(), "BADKEY");
+        assert_eq!(RCode::BADTIME.name(), "BADTIME");
+        assert_eq!(RCode::BADMODE.name(), "BADMODE");
+        assert_eq!(RCode::BADNAME.name(), "BADNAME");
+        assert_eq!(RCode::BADALG.name(), "BADALG");
+        assert_eq!(RCode::BADTRUNC.name(), "BADTRUNC");
+        assert_eq!(RCode::BADCOOKIE.name(), "BADCOOKIE");
 
         for (i, v) in NAMES.iter().enumerate() {
             assert_eq!(RCode::from(i as u16).name(), *v);
@@ -373,7 +463,20 @@ mod test {
         assert_eq!(RCode::from_name("NXDOMAIN").unwrap(), RCode::NXDOMAIN);
         assert_eq!(RCode::from_name("NOTIMP").unwrap(), RCode::NOTIMP);
         assert_eq!(RCode::from_name("REFUSED").unwrap(), RCode::REFUSED);
+        assert_eq!(RCode::from_name("YXDOMAIN").unwrap(), RCode::YXDOMAIN);
+        assert_eq!(RCode::from_name("YXRRSET").unwrap(), RCode::YXRRSET);
+        assert_eq!(RCode::from_name("NXRRSET").unwrap(), RCode::NXRRSET);
+        assert_eq!(RCode::from_name("NOTAUTH").unwrap(), RCode::NOTAUTH);
+        assert_eq!(RCode::from_name("NOTZONE").unwrap(), RCode::NOTZONE);
+        assert_eq!(RCode::from_name("DSOTYPENI").unwrap(), RCode::DSOTYPENI);
         assert_eq!(RCode::from_name("BADVERS").unwrap(), RCode::BADVERS);
+        assert_eq!(RCode::from_name("BADKEY").unwrap(), RCode::BADKEY);
+        assert_eq!(RCode::from_name("BADTIME").unwrap(), RCode::BADTIME);
+        assert_eq!(RCode::from_name("BADMODE").unwrap(), RCode::BADMODE);
+        assert_eq!(RCode::from_name("BADNAME").unwrap(), RCode::BADNAME);
+        assert_eq!(RCode::from_name("BADALG").unwrap(), RCode::BADALG);
+        assert_eq!(RCode::from_name("BADTRUNC").unwrap(), RCode::BADTRUNC);
+        assert_eq!(RCode::from_name("BADCOOKIE").unwrap(), RCode::BADCOOKIE);
 
         for (i, name) in NAMES.iter().enumerate() {
             if *name != UNKNOWN_RCODE {
@@ -393,7 +496,20 @@ mod test {
         assert_eq!(RCode::from_str("NXDOMAIN").unwrap(), RCode::NXDOMAIN);
         assert_eq!(RCode::from_str("NOTIMP").unwrap(), RCode::NOTIMP);
         assert_eq!(RCode::from_str("REFUSED").unwrap(), RCode::REFUSED);
+        assert_eq!(RCode::from_str("YXDOMAIN").unwrap(), RCode::YXDOMAIN);
+        assert_eq!(RCode::from_str("YXRRSET").unwrap(), RCode::YXRRSET);
+        assert_eq!(RCode::from_str("NXRRSET").unwrap(), RCode::NXRRSET);
+        assert_eq!(RCode::from_str("NOTAUTH").unwrap(), RCode::NOTAUTH);
+        assert_eq!(RCode::from_str("NOTZONE").unwrap(), RCode::NOTZONE);
+        assert_eq!(RCode::from_str("DSOTYPENI").unwrap(), RCode::DSOTYPENI);
         assert_eq!(RCode::from_str("BADVERS").unwrap(), RCode::BADVERS);
+        assert_eq!(RCode::from_str("BADKEY").unwrap(), RCode::BADKEY);
+        assert_eq!(RCode::from_str("BADTIME").unwrap(), RCode::BADTIME);
+        assert_eq!(RCode::from_str("BADMODE").unwrap(), RCode::BADMODE);
+        assert_eq!(RCode::from_str("BADNAME").unwrap(), RCode::BADNAME);
+        assert_eq!(RCode::from_str("BADALG").unwrap(), RCode::BADALG);
+        assert_eq!(RCode::from_str("BADTRUNC").unwrap(), RCode::BADTRUNC);
+        assert_eq!(RCode::from_str("BADCOOKIE").unwrap(), RCode::BADCOOKIE);
 
         for (i, name) in NAMES.iter().enumerate() {
             if *name != UNKNOWN_RCODE {
@@ -419,7 +535,20 @@ mod test {
         assert!(RCode::NXDOMAIN.is_defined());
         assert!(RCode::NOTIMP.is_defined());
         assert!(RCode::REFUSED.is_defined());
+        assert!(RCode::YXDOMAIN.is_defined());
+        assert!(RCode::YXRRSET.is_defined());
+        assert!(RCode::NXRRSET.is_defined());
+        assert!(RCode::NOTAUTH.is_defined());
+        assert!(RCode::NOTZONE.is_defined());
+        assert!(RCode::DSOTYPENI.is_defined());
         assert!(RCode::BADVERS.is_defined());
+        assert!(RCode::BADKEY.is_defined());
+        assert!(RCode::BADTIME.is_defined());
+        assert!(RCode::BADMODE.is_defined());
+        assert!(RCode::BADNAME.is_defined());
+        assert!(RCode::BADALG.is_defined());
+        assert!(RCode::BADTRUNC.is_defined());
+        assert!(RCode::BADCOOKIE.is_defined());
 
         for v in RCode::VALUES {
             assert!(v.is_defined());
@@ -431,10 +560,7 @@ mod test {
 
         for i in 0..=u8::MAX {
             let rcode = RCode::from(i as u16);
-            assert_eq!(
-                rcode.is_defined(),
-                RCode::VALUES.iter().any(|e| *e == rcode)
-            );
+            assert_eq!(rcode.is_defined(), RCode::VALUES.contains(&rcode));
         }
     }
 }
@@ -164,6 +164,29 @@ pub(crate) fn skip_domain_name(c: &mut Cursor<'_>) -> Result<usize> {
     Ok(c.pos() - start)
 }
 
+pub(crate) fn count_domain_name_pointers(c: &mut Cursor<'_>) -> Result<usize> {
+    let mut cursor = c.clone();
+    let mut max_pos = 0;
+    let mut n_pointers = 0;
+    #[allow(unused_assignments)]
+    let mut done = false;
+
+    labels_loop!(
+        cursor,
+        max_pos,
+        n_pointers,
+        done,
+        (),
+        break_loop,
+        check_label
+    );
+
+    let _ = done; // make clippy happy
+
+    c.set_pos(max_pos);
+    Ok(n_pointers)
+}
+
 #[inline]
 const fn is_pointer(b: u8) -> bool {
     (b & POINTER_MASK) == POINTER_MASK
@@ -2,9 +2,10 @@ use crate::{
     bytes::{Cursor, Reader},
     constants::HEADER_LENGTH,
     message::{
-        reader::{Questions, Records},
+        reader::{count_domain_name_pointers, MessageReader, NamePointers, Questions, Records},
         Header, Question, RecordsSection,
     },
+    records::{Opt, Type},
     Error, Result,
 };
 
@@ -87,7 +88,7 @@ pub struct MessageIterator<'a> {
 impl MessageIterator<'_> {
     /// Creates a reader for a message contained in `buf`.
     #[inline]
-    pub fn new(buf: &[u8]) -> Result<MessageIterator> {
+    pub fn new(buf: &[u8]) -> Result<MessageIterator<'_>> {
         let mut cursor = Cursor::new(buf);
         let header: Header = cursor.read()?;
         let mut mi = MessageIterator {
@@ -120,7 +121,7 @@ impl MessageIterator<'_> {
 
     /// Returns an iterator over the questions section of the message.
     #[inline]
-    pub fn questions(&self) -> Questions {
+    pub fn questions(&self) -> Questions<'_> {
         Questions::new(
             Cursor::with_pos(self.buf, HEADER_LENGTH),
             self.header.qd_count,
@@ -129,13 +130,145 @@ impl MessageIterator<'_> {
 
     /// Returns an iterator over the resource record sections of the message.
     #[inline]
-    pub fn records(&self) -> Records {
+    pub fn records(&self) -> Records<'_> {
         Records::new(
             Cursor::with_pos(self.buf, self.offsets[RecordsSection::Answer as usize]),
             &self.header,
         )
     }
 
+    /// Returns the `OPT` pseudo-record of the message, if present.
+    ///
+    /// The `OPT` record of a response carries the EDNS parameters of the server, e.g. the UDP
+    /// payload size advertised by the server ([`Opt::udp_payload_size`]). Comparing it with the
+    /// payload size sent in the query helps to tune the EDNS configuration for a given server.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rsdns::message::reader::MessageIterator;
+    ///
+    /// fn server_payload_size(buf: &[u8]) -> rsdns::Result<Option<u16>> {
+    ///     let mi = MessageIterator::new(buf)?;
+    ///     Ok(mi.opt()?.map(|opt| opt.udp_payload_size()))
+    /// }
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// - [`Error::MultipleOptRecords`] - if the message contains more than one `OPT`
+    ///   pseudo-record ([RFC 6891 section 6.1.1])
+    ///
+    /// [RFC 6891 section 6.1.1]: https://www.rfc-editor.org/rfc/rfc6891.html#section-6.1.1
+    pub fn opt(&self) -> Result<Option<Opt>> {
+        let mut mr = MessageReader::new(self.buf)?;
+        mr.header()?;
+        mr.seek(RecordsSection::Additional)?;
+        let mut opt = None;
+        while mr.has_records() {
+            let marker = mr.record_marker()?;
+            if marker.rtype() == Type::OPT {
+                if opt.is_some() {
+                    return Err(Error::MultipleOptRecords);
+                }
+                opt = Some(mr.opt_record(&marker)?);
+            } else {
+                mr.skip_record_data(&marker)?;
+            }
+        }
+        Ok(opt)
+    }
+
+    /// Returns statistics of the compression pointers in the domain names of the message.
+    ///
+    /// The names of the questions and the owner names of the resource records are parsed,
+    /// as well as the names embedded in the record data of the well-known record types of
+    /// [RFC 1035], the only types allowed to use compression in the record data
+    /// ([RFC 3597 section 4]). The record data of other types is skipped.
+    ///
+    /// The method doesn't apply any policy. It is up to the application to decide which
+    /// numbers indicate an abusive message.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rsdns::message::reader::MessageIterator;
+    ///
+    /// fn is_suspicious(buf: &[u8]) -> rsdns::Result<bool> {
+    ///     let pointers = MessageIterator::new(buf)?.name_pointers()?;
+    ///     Ok(pointers.max_per_name > 4 || pointers.total > 2 * pointers.names)
+    /// }
+    /// ```
+    ///
+    /// [RFC 1035]: https://www.rfc-editor.org/rfc/rfc1035.html
+    /// [RFC 3597 section 4]: https://www.rfc-editor.org/rfc/rfc3597.html#section-4
+    pub fn name_pointers(&self) -> Result<NamePointers> {
+        let mut pointers = NamePointers::default();
+        let mut c = Cursor::with_pos(self.buf, HEADER_LENGTH);
+
+        for _ in 0..self.header.qd_count {
+            pointers.add(count_domain_name_pointers(&mut c)?);
+            c.skip(4)?; // qtype(2) + qclass(2)
+        }
+
+        let n_records = self.header.an_count as usize
+            + self.header.ns_count as usize
+            + self.header.ar_count as usize;
+        for _ in 0..n_records {
+            pointers.add(count_domain_name_pointers(&mut c)?);
+            let rtype: Type = c.read()?;
+            c.skip(6)?; // class(2) + TTL(4)
+            let rd_len = c.u16_be()? as usize;
+
+            let mut rc = c.clone();
+            let n_names = match rtype {
+                Type::NS
+                | Type::MD
+                | Type::MF
+                | Type::CNAME
+                | Type::MB
+                | Type::MG
+                | Type::MR
+                | Type::PTR => 1,
+                Type::SOA | Type::MINFO => 2,
+                Type::MX => {
+                    rc.skip(2)?; // preference
+                    1
+                }
+                _ => 0,
+            };
+            for _ in 0..n_names {
+                pointers.add(count_domain_name_pointers(&mut rc)?);
+            }
+
+            c.skip(rd_len)?;
+        }
+
+        Ok(pointers)
+    }
+
+    /// Checks if the message contains an `OPT` pseudo-record in the additional section.
+    ///
+    /// This is independent of whether EDNS was requested in the query. A response without an
+    /// `OPT` record to an EDNS query indicates that the server doesn't support EDNS, or that
+    /// a middlebox stripped the record on the way. In this case no EDNS features, e.g. a larger
+    /// UDP payload size, are in effect.
+    ///
+    /// Unlike [`opt`](Self::opt), the `OPT` record is not parsed.
+    pub fn has_opt(&self) -> Result<bool> {
+        let mut mr = MessageReader::new(self.buf)?;
+        mr.header()?;
+        mr.seek(RecordsSection::Additional)?;
+        while mr.has_records() {
+            let marker = mr.record_marker()?;
+            if marker.rtype() == Type::OPT {
+                return Ok(true);
+            }
+            mr.skip_record_data(&marker)?;
+        }
+        Ok(false)
+    }
+
     fn section_offset(&mut self, section: RecordsSection) -> Result<usize> {
         use RecordsSection::*;
 
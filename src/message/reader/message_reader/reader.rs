@@ -1,17 +1,21 @@
 use crate::{
     bytes::{Cursor, Reader},
-    constants::HEADER_LENGTH,
+    constants::{DNS_MESSAGE_BUFFER_MIN_LENGTH, DNS_MESSAGE_MAX_LENGTH, HEADER_LENGTH},
     message::{
         reader::{
             NameRef, QuestionRef, RecordHeader, RecordHeaderRef, RecordMarker, RecordOffset,
-            SectionTracker,
+            Records, SectionTracker,
         },
         Header, Question, RecordsSection,
     },
-    names::DName,
-    records::{data::RData, Class, Opt, Type},
+    names::{DName, InlineName},
+    records::{
+        data::{Aaaa, RData, RecordData, Unknown, A},
+        Class, Opt, ResourceRecord, Type,
+    },
     Error, Result,
 };
+use std::{io::Read, net::IpAddr};
 
 #[derive(Debug)]
 /// A fast and flexible message reader.
@@ -116,6 +120,30 @@ use crate::{
 /// [`the_question_ref`]: MessageReader::the_question_ref
 /// [`skip_questions`]: MessageReader::skip_questions
 ///
+/// ## Messages without questions
+///
+/// Some messages carry no questions at all. For example, Multicast DNS
+/// ([RFC 6762 section 6](https://www.rfc-editor.org/rfc/rfc6762.html#section-6)) responses,
+/// and unsolicited (gratuitous) announcements in particular, usually have `QDCOUNT` set to `0`.
+/// Such messages are read as any other message, except that [`the_question`] must not be used.
+/// Either [`skip_questions`] or [`seek`] may be called right after the header, or the records
+/// may be read directly:
+///
+/// ```rust
+/// # use rsdns::{message::{reader::MessageReader, RecordsSection}, Result};
+/// # fn read_announcement(msg: &[u8]) -> Result<()> {
+/// let mut mr = MessageReader::new(msg)?;
+/// let header = mr.header()?;
+/// if header.qd_count == 0 {
+///     mr.seek(RecordsSection::Answer)?;
+/// }
+/// // the reader is positioned to decode the answer records...
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`seek`]: MessageReader::seek
+///
 ///
 /// # Resource Records
 ///
@@ -193,6 +221,15 @@ use crate::{
 /// Note that these methods are immutable, they do not change the internal buffer pointer of
 /// the reader.
 ///
+///
+/// # Trailing Bytes
+///
+/// `MessageReader` reads exactly one message. Data following the last record of the message is
+/// ignored. It can be detected with the [`trailing_bytes`] method once the message is fully
+/// traversed.
+///
+/// [`trailing_bytes`]: MessageReader::trailing_bytes
+///
 /// [`record_data_at`]: MessageReader::record_data_at
 /// [`record_data_bytes_at`]: MessageReader::record_data_bytes_at
 /// [`name_ref_at`]: MessageReader::name_ref_at
@@ -280,6 +317,8 @@ pub struct MessageReader<'a> {
     cursor: Cursor<'a>,
     section_tracker: SectionTracker,
     done: bool,
+    marker_cache: Option<(RecordsSection, usize, usize)>,
+    strict_edns: bool,
 }
 
 impl<'s, 'a: 's> MessageReader<'a> {
@@ -294,16 +333,89 @@ impl<'s, 'a: 's> MessageReader<'a> {
     /// - [`Error::MessageTooLong`] - if message size exceeds 65535 bytes.
     #[inline]
     pub fn new(msg: &'a [u8]) -> Result<MessageReader<'a>> {
-        if msg.len() > u16::MAX as usize {
+        if msg.len() > DNS_MESSAGE_MAX_LENGTH {
             return Err(Error::MessageTooLong(msg.len()));
         }
         Ok(MessageReader {
             cursor: Cursor::new(msg),
             section_tracker: Default::default(),
             done: false,
+            marker_cache: None,
+            strict_edns: false,
         })
     }
 
+    /// Reads a length-prefixed message from `rd` into `buf`, and creates a `MessageReader` for it.
+    ///
+    /// The message is expected in the format used by DNS over TCP ([RFC 1035 section 4.2.2]):
+    /// a two-byte length prefix followed by the message itself. Exactly the declared number of
+    /// bytes is read, so consecutive messages may be read from the same source, e.g. a file
+    /// with captured messages.
+    ///
+    /// This is a blocking operation.
+    ///
+    /// # Returns
+    ///
+    /// - [`Error::MessageTooShort`] - if the declared message length is less than the length of
+    ///   the message header
+    /// - [`Error::BufferTooShort`] - if the declared message length exceeds the size of `buf`
+    /// - [`Error::IoError`] - on a read error, including an end of input before the whole
+    ///   message is read ([`std::io::ErrorKind::UnexpectedEof`])
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rsdns::message::reader::MessageReader;
+    /// use std::{fs::File, io::BufReader};
+    ///
+    /// fn read_dump(path: &str) -> rsdns::Result<()> {
+    ///     let mut rd = BufReader::new(File::open(path)?);
+    ///     let mut buf = [0u8; u16::MAX as usize];
+    ///     let mut mr = MessageReader::read_from(&mut rd, &mut buf)?;
+    ///     println!("{:?}", mr.header()?);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [RFC 1035 section 4.2.2]: https://www.rfc-editor.org/rfc/rfc1035.html#section-4.2.2
+    pub fn read_from<R: Read>(rd: &mut R, buf: &'a mut [u8]) -> Result<MessageReader<'a>> {
+        let mut len = [0u8; 2];
+        rd.read_exact(&mut len)?;
+        let len = u16::from_be_bytes(len) as usize;
+        if len < HEADER_LENGTH {
+            return Err(Error::MessageTooShort(len));
+        }
+        if len > buf.len() {
+            return Err(Error::BufferTooShort(len));
+        }
+        rd.read_exact(&mut buf[..len])?;
+        Self::new(&buf[..len])
+    }
+
+    /// Re-points the `MessageReader` to a new message.
+    ///
+    /// This method resets the reader to the state it has right after creation with
+    /// [`MessageReader::new`]: the section tracker and the error state are cleared. It allows
+    /// reusing a single reader to parse many messages in a loop.
+    ///
+    /// As with a newly created reader, the message header must be read immediately after reset.
+    ///
+    /// # Returns
+    ///
+    /// - [`Error::MessageTooLong`] - if message size exceeds 65535 bytes.
+    ///   In this case the reader is left unchanged.
+    #[inline]
+    pub fn reset(&mut self, msg: &'a [u8]) -> Result<()> {
+        if msg.len() > DNS_MESSAGE_MAX_LENGTH {
+            return Err(Error::MessageTooLong(msg.len()));
+        }
+        self.cursor = Cursor::new(msg);
+        self.section_tracker = Default::default();
+        self.done = false;
+        self.marker_cache = None;
+        Ok(())
+    }
+
     /// Reads the message header.
     ///
     /// This is the first method that must be called after creation of a `MessageReader`.
@@ -440,6 +552,75 @@ impl<'s, 'a: 's> MessageReader<'a> {
         question!(self, question_check_single_question)
     }
 
+    /// Returns the first and only question without advancing the reader.
+    ///
+    /// This method is similar to [`the_question`], except that the question is read from
+    /// a copy of the reader's cursor. Thus the reader may still be used to read the questions
+    /// section or to [`seek`] to a records section. Errors do not put the reader in error state.
+    ///
+    /// Note that this method may be called only immediately after the header is read.
+    ///
+    /// # Returns
+    ///
+    /// - [`Error::ReaderDone`] - if the reader is in error state
+    /// - [`Error::BadQuestionsCount`] - if the number of unread questions is not `1`
+    ///
+    /// [`the_question`]: MessageReader::the_question
+    /// [`seek`]: MessageReader::seek
+    pub fn peek_question(&self) -> Result<Question> {
+        if self.done {
+            return Err(Error::ReaderDone);
+        }
+        let questions_left = self.section_tracker.questions_left();
+        if questions_left != 1 {
+            return Err(Error::BadQuestionsCount(questions_left));
+        }
+        self.cursor.clone().read()
+    }
+
+    /// Returns the encoded bytes of the unread questions without advancing the reader.
+    ///
+    /// When called immediately after the header is read, the returned slice is the whole
+    /// questions section, as sent in the message. A server or a forwarder may copy it into
+    /// a response byte-for-byte, preserving the exact case of the question name, e.g. of a
+    /// [0x20 encoded] query. The questions section starts at the same offset in every message,
+    /// so compression pointers in the copied questions remain valid.
+    ///
+    /// The questions are validated structurally, as in [`skip_questions`]. Errors do not put
+    /// the reader in error state.
+    ///
+    /// # Returns
+    ///
+    /// - [`Error::ReaderDone`] - if the reader is in error state
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rsdns::message::reader::MessageReader;
+    ///
+    /// // appends the questions section of a query to a response being built
+    /// fn echo_questions(query: &[u8], response: &mut Vec<u8>) -> rsdns::Result<()> {
+    ///     let mut mr = MessageReader::new(query)?;
+    ///     mr.header()?;
+    ///     response.extend_from_slice(mr.question_bytes()?);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [`skip_questions`]: MessageReader::skip_questions
+    /// [0x20 encoded]: https://datatracker.ietf.org/doc/html/draft-vixie-dnsext-dns0x20-00
+    pub fn question_bytes(&self) -> Result<&'a [u8]> {
+        if self.done {
+            return Err(Error::ReaderDone);
+        }
+        let mut cursor = self.cursor.clone();
+        for _ in 0..self.section_tracker.questions_left() {
+            cursor.skip_question()?;
+        }
+        let len = cursor.pos() - self.cursor.pos();
+        Ok(&self.cursor.remaining()[..len])
+    }
+
     /// Skips the questions section.
     ///
     /// This is a convenience method to advance the reader to the end of the questions section.
@@ -456,6 +637,135 @@ impl<'s, 'a: 's> MessageReader<'a> {
         res
     }
 
+    /// Returns an iterator over the answer section of the message.
+    ///
+    /// This is a fast path for the common case when the question is known in advance.
+    /// It reads the header, skips the questions section and positions the reader at the first
+    /// record of the answer section, as if [`header`], [`skip_questions`] and [`seek`] were
+    /// called. The header and the questions are still validated structurally.
+    ///
+    /// The returned iterator yields the answer records only. It is independent of the reader,
+    /// which may be used to read the message further, starting at the answer section.
+    ///
+    /// Note that this method must be called immediately after creation of the reader,
+    /// instead of [`header`]. Use [`header`] when the header fields, e.g. the response code,
+    /// should be inspected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rsdns::message::reader::MessageReader;
+    ///
+    /// fn answers_count(msg: &[u8]) -> rsdns::Result<usize> {
+    ///     let mut mr = MessageReader::new(msg)?;
+    ///     let mut count = 0;
+    ///     for res in mr.answers()? {
+    ///         let (_section, _record) = res?;
+    ///         count += 1;
+    ///     }
+    ///     Ok(count)
+    /// }
+    /// ```
+    ///
+    /// [`header`]: Self::header
+    /// [`skip_questions`]: Self::skip_questions
+    /// [`seek`]: Self::seek
+    pub fn answers(&mut self) -> Result<Records<'a>> {
+        let header = self.header()?;
+        self.seek(RecordsSection::Answer)?;
+        let answers_only = Header {
+            ns_count: 0,
+            ar_count: 0,
+            ..header
+        };
+        Ok(Records::new(self.cursor.clone(), &answers_only))
+    }
+
+    /// Collects the addresses from the answer section.
+    ///
+    /// This is a fast path for address resolution. The answer section is traversed once,
+    /// and the data of [`A`] and [`Aaaa`] records of class [`IN`] is collected in order of
+    /// appearance. Other records, e.g. `CNAME`, are skipped without being parsed.
+    /// No memory is allocated besides the returned vector.
+    ///
+    /// Similarly to [`answers`], this method reads the header and skips the questions section.
+    /// It must be called immediately after creation of the reader.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rsdns::message::reader::MessageReader;
+    /// use std::net::IpAddr;
+    ///
+    /// fn addresses(msg: &[u8]) -> rsdns::Result<Vec<IpAddr>> {
+    ///     MessageReader::new(msg)?.collect_addresses()
+    /// }
+    /// ```
+    ///
+    /// [`A`]: crate::records::data::A
+    /// [`Aaaa`]: crate::records::data::Aaaa
+    /// [`IN`]: Class::IN
+    /// [`answers`]: Self::answers
+    pub fn collect_addresses(&mut self) -> Result<Vec<IpAddr>> {
+        self.header()?;
+        self.seek(RecordsSection::Answer)?;
+        let mut addrs = Vec::with_capacity(self.records_count_in(RecordsSection::Answer));
+        while self.has_records_in(RecordsSection::Answer) {
+            let marker = self.record_marker()?;
+            match (marker.rtype, marker.rclass) {
+                (Type::A, Class::IN) => addrs.push(self.record_data::<A>(&marker)?.address.into()),
+                (Type::AAAA, Class::IN) => {
+                    addrs.push(self.record_data::<Aaaa>(&marker)?.address.into())
+                }
+                _ => self.skip_record_data(&marker)?,
+            }
+        }
+        Ok(addrs)
+    }
+
+    /// Collects the markers of all remaining records, skipping their data.
+    ///
+    /// This method allows indexing a message in one pass, e.g. a large zone transfer message,
+    /// while the data of the records is read later (possibly selectively) with the random-access
+    /// methods, e.g. [`record_data_at`]. Each marker holds the section of its record.
+    ///
+    /// The header must be read before this method is called. The remaining questions are skipped.
+    /// When called immediately after the header, the markers of all records of the message are
+    /// returned. After this method returns, the whole message is traversed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rsdns::{
+    ///     message::reader::MessageReader,
+    ///     records::{data::Soa, Type},
+    /// };
+    ///
+    /// // returns the serial numbers of the SOA records
+    /// fn soa_serials(msg: &[u8]) -> rsdns::Result<Vec<u32>> {
+    ///     let mut mr = MessageReader::new(msg)?;
+    ///     mr.header()?;
+    ///     let markers = mr.collect_markers()?;
+    ///     markers
+    ///         .iter()
+    ///         .filter(|m| m.rtype() == Type::SOA)
+    ///         .map(|m| Ok(mr.record_data_at::<Soa>(m)?.serial))
+    ///         .collect()
+    /// }
+    /// ```
+    ///
+    /// [`record_data_at`]: Self::record_data_at
+    pub fn collect_markers(&mut self) -> Result<Vec<RecordMarker>> {
+        self.skip_questions()?;
+        let mut markers = Vec::with_capacity(self.records_count());
+        while self.has_records() {
+            let marker = self.record_marker()?;
+            self.skip_record_data(&marker)?;
+            markers.push(marker);
+        }
+        Ok(markers)
+    }
+
     #[inline(always)]
     fn skip_questions_impl(&mut self) -> Result<()> {
         while self.section_tracker.questions_left() > 0 {
@@ -532,15 +842,24 @@ impl<'s, 'a: 's> MessageReader<'a> {
 
     #[inline(always)]
     fn raw_marker_impl(&mut self, pos: usize, section: RecordsSection) -> Result<RecordMarker> {
+        Self::read_raw_marker(&mut self.cursor, pos, section)
+    }
+
+    #[inline(always)]
+    fn read_raw_marker(
+        cursor: &mut Cursor<'a>,
+        pos: usize,
+        section: RecordsSection,
+    ) -> Result<RecordMarker> {
         let offset = RecordOffset {
             offset: pos,
-            type_offset: self.cursor.pos(),
+            type_offset: cursor.pos(),
         };
 
-        let rtype = Type::from(self.cursor.u16_be()?);
-        let rclass = Class::from(self.cursor.u16_be()?);
-        let ttl = self.cursor.u32_be()?;
-        let rdlen = self.cursor.u16_be()?;
+        let rtype = Type::from(cursor.u16_be()?);
+        let rclass = Class::from(cursor.u16_be()?);
+        let ttl = cursor.u32_be()?;
+        let rdlen = cursor.u16_be()?;
 
         Ok(RecordMarker {
             offset,
@@ -653,6 +972,34 @@ impl<'s, 'a: 's> MessageReader<'a> {
         res
     }
 
+    /// Reads the current record data as [`RecordData`] and advances the reader to the next
+    /// record.
+    ///
+    /// Data of a type not supported by [`RecordData`] is read as [`RecordData::Unknown`].
+    #[allow(dead_code)] // clients module may be disabled
+    pub(crate) fn record_data_any(&mut self, marker: &RecordMarker) -> Result<RecordData> {
+        if !RecordData::is_supported_type(marker.rtype) {
+            let data = self.record_data_bytes(marker)?.to_vec();
+            return Ok(RecordData::Unknown(Unknown {
+                rtype: marker.rtype,
+                data,
+            }));
+        }
+
+        debug_assert!(self.cursor.pos() == marker.rdata_pos());
+        if self.done {
+            return Err(Error::ReaderDone);
+        }
+        let res = RecordData::from_cursor(marker.rtype, &mut self.cursor, marker.rdlen as usize);
+        if res.is_ok() {
+            self.section_tracker
+                .section_read(marker.section, self.cursor.pos());
+        } else {
+            self.done = true;
+        }
+        res
+    }
+
     /// Deserializes the current record data and advances the reader to the next record.
     ///
     /// This method is generic over the record data type, and allows deserialization of all
@@ -679,8 +1026,136 @@ impl<'s, 'a: 's> MessageReader<'a> {
         res
     }
 
+    /// Deserializes the current record data, verifying that the record type matches the data type.
+    ///
+    /// This method is similar to [`MessageReader::record_data`], but it first verifies
+    /// that the record type of `marker` is [`D::RTYPE`](RData::RTYPE).
+    /// On mismatch [`Error::RecordTypeMismatch`] is returned and the reader is left intact,
+    /// i.e. the record data may still be read with another data type, or skipped.
+    ///
+    /// # Panics
+    ///
+    /// This method uses debug assertions to verify that `marker` matches the reader's buffer
+    /// pointer.
+    #[inline]
+    pub fn record_data_checked<D: RData>(&mut self, marker: &RecordMarker) -> Result<D> {
+        if marker.rtype != D::RTYPE {
+            return Err(Error::RecordTypeMismatch {
+                expected: D::RTYPE,
+                actual: marker.rtype,
+            });
+        }
+        self.record_data(marker)
+    }
+
+    /// Deserializes the current record data, and returns it together with the record TTL.
+    ///
+    /// This method is equivalent to [`MessageReader::record_data`], except that the TTL of
+    /// the record is taken from `marker` and returned alongside the data. This is convenient
+    /// for caching, where the data is useless without its TTL.
+    ///
+    /// # Panics
+    ///
+    /// This method uses debug assertions to verify that `marker` matches the reader's buffer
+    /// pointer.
+    #[inline]
+    pub fn record_data_with_ttl<D: RData>(&mut self, marker: &RecordMarker) -> Result<(D, u32)> {
+        self.record_data(marker).map(|rdata| (rdata, marker.ttl))
+    }
+
+    /// Reads the next resource record, advancing across the record sections transparently.
+    ///
+    /// This is a convenience method for simple traversal of all records in a message.
+    /// Records are read from the Answer, Authority and Additional sections in this order,
+    /// without the need to [`seek`](Self::seek) or check [`has_records_in`](Self::has_records_in)
+    /// per section. Unread questions are skipped automatically.
+    ///
+    /// The record data is parsed into [`RecordData`]. Records of types not supported by
+    /// [`RecordData`], including the `OPT` pseudo-record, are skipped.
+    ///
+    /// The message header must be read before calling this method.
+    ///
+    /// Returns `Ok(None)` when there are no more records to read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rsdns::{message::reader::MessageReader, Result};
+    ///
+    /// fn print_records(msg: &[u8]) -> Result<()> {
+    ///     let mut mr = MessageReader::new(msg)?;
+    ///     mr.header()?;
+    ///
+    ///     while let Some((section, record)) = mr.next_record()? {
+    ///         println!("{section}: {record:?}");
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn next_record(&mut self) -> Result<Option<(RecordsSection, ResourceRecord)>> {
+        if self.section_tracker.questions_left() > 0 {
+            self.skip_questions()?;
+        }
+
+        while self.has_records() {
+            let RecordHeader { name, marker } = self.record_header::<InlineName>()?;
+
+            if !RecordData::is_supported_type(marker.rtype) {
+                self.skip_record_data(&marker)?;
+                continue;
+            }
+
+            let res =
+                RecordData::from_cursor(marker.rtype, &mut self.cursor, marker.rdlen as usize);
+            let rdata = match res {
+                Ok(rdata) => rdata,
+                Err(e) => {
+                    self.done = true;
+                    return Err(e);
+                }
+            };
+            self.section_tracker
+                .section_read(marker.section, self.cursor.pos());
+
+            let record = ResourceRecord {
+                name,
+                rclass: marker.rclass,
+                rtype: marker.rtype,
+                ttl: marker.ttl,
+                rdata,
+            };
+            return Ok(Some((marker.section, record)));
+        }
+
+        Ok(None)
+    }
+
+    /// Sets the strict EDNS parsing mode.
+    ///
+    /// By default, [`opt_record`] accepts any EDNS version and flags, leaving their
+    /// interpretation to the caller. In strict mode an `OPT` pseudo-record with an EDNS
+    /// version other than `0`, or with reserved flags set (any flag except `DO`), is rejected.
+    /// This helps to catch server misbehavior in conformance tests.
+    ///
+    /// The mode is kept when the reader is [`reset`].
+    ///
+    /// [`opt_record`]: Self::opt_record
+    /// [`reset`]: Self::reset
+    #[inline]
+    pub fn set_strict_edns(&mut self, strict: bool) {
+        self.strict_edns = strict;
+    }
+
     /// Reads the `OPT` pseudo-record and advances the reader to the next record.
     ///
+    /// # Returns
+    ///
+    /// - [`Error::UnsupportedEdnsVersion`] - in strict EDNS mode, if the EDNS version is not `0`
+    /// - [`Error::ReservedEdnsFlags`] - in strict EDNS mode, if reserved flags are set
+    ///
+    /// See [`set_strict_edns`](Self::set_strict_edns).
+    ///
     /// # Panics
     ///
     /// This method uses debug assertions to verify that:
@@ -706,8 +1181,13 @@ impl<'s, 'a: 's> MessageReader<'a> {
 
     #[inline(always)]
     fn opt_record_impl(&mut self, marker: &RecordMarker) -> Result<Opt> {
-        self.cursor.skip(marker.rdlen as usize)?;
-        Ok(Opt::from_msg(marker.rclass.value(), marker.ttl))
+        let opt = self
+            .cursor
+            .read_opt(marker.rclass.value(), marker.ttl, marker.rdlen as usize)?;
+        if self.strict_edns {
+            opt.check_strict()?;
+        }
+        Ok(opt)
     }
 
     /// Reads the data of a record at specified marker and returns it as a byte slice.
@@ -742,6 +1222,70 @@ impl<'s, 'a: 's> MessageReader<'a> {
         D::from_cursor(&mut cursor, marker.rdlen as usize)
     }
 
+    /// Returns the marker of a record at specified index within a section.
+    ///
+    /// This method allows obtaining markers for random access to the records of a DNS message,
+    /// e.g. with [`MessageReader::record_data_at`]. The records preceding the requested one are
+    /// traversed without reading their data.
+    ///
+    /// The offset of the section must be known, i.e. the section must have been reached by the
+    /// reader (see [`MessageReader::seek`]). The position of the last returned marker is cached,
+    /// so that consecutive calls with increasing indices in the same section don't traverse the
+    /// section from its beginning.
+    ///
+    /// Note that this method doesn't change the reader's buffer pointer.
+    /// Nor it is affected by an error state of the reader.
+    ///
+    /// # Returns
+    ///
+    /// - [`Error::BadParam`] - if `index` exceeds the number of records in the section
+    /// - [`Error::RecordsSectionOffsetUnknown`] - if the offset of the section is not known yet
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rsdns::{
+    ///     message::{reader::MessageReader, RecordsSection},
+    ///     records::data::A,
+    /// };
+    ///
+    /// // returns the address in the last answer
+    /// fn last_address(msg: &[u8]) -> rsdns::Result<A> {
+    ///     let mut mr = MessageReader::new(msg)?;
+    ///     mr.header()?;
+    ///     mr.seek(RecordsSection::Answer)?;
+    ///     let count = mr.records_count_in(RecordsSection::Answer);
+    ///     let marker = mr.marker_at(RecordsSection::Answer, count - 1)?;
+    ///     mr.record_data_at(&marker)
+    /// }
+    /// ```
+    pub fn marker_at(&mut self, section: RecordsSection, index: usize) -> Result<RecordMarker> {
+        if index >= self.section_tracker.records_in(section) {
+            return Err(Error::BadParam("record index out of range"));
+        }
+
+        let (mut i, mut pos) = match self.marker_cache {
+            Some((s, i, pos)) if s == section && i <= index => (i, pos),
+            _ => match self.section_tracker.section_offset(section) {
+                Some(pos) => (0, pos),
+                None => return Err(Error::RecordsSectionOffsetUnknown(section)),
+            },
+        };
+
+        let mut cursor = self.cursor.clone_with_pos(pos);
+        loop {
+            cursor.skip_domain_name()?;
+            let marker = Self::read_raw_marker(&mut cursor, pos, section)?;
+            if i == index {
+                self.marker_cache = Some((section, i, pos));
+                return Ok(marker);
+            }
+            cursor.skip(marker.rdlen as usize)?;
+            pos = cursor.pos();
+            i += 1;
+        }
+    }
+
     /// Returns the data of a record at specified marker as [`NameRef`].
     ///
     /// This method is handy with records that have a single domain name in the
@@ -751,6 +1295,85 @@ impl<'s, 'a: 's> MessageReader<'a> {
         NameRef::new(self.cursor.clone_with_pos(marker.rdata_pos()))
     }
 
+    /// Returns the number of bytes following the last record of the message.
+    ///
+    /// A well-formed message has no trailing bytes. A non-zero value may indicate a malformed
+    /// or malicious message, e.g. a UDP datagram carrying more than one message.
+    ///
+    /// Trailing bytes can be detected only after the whole message was traversed, i.e. when
+    /// there are no more questions and records to read. Returns `None` if the message was not
+    /// traversed yet, or the reader is in error state.
+    #[inline]
+    pub fn trailing_bytes(&self) -> Option<usize> {
+        if self.done
+            || self.cursor.pos() < HEADER_LENGTH
+            || self.section_tracker.questions_left() > 0
+            || self.section_tracker.records_left() > 0
+        {
+            return None;
+        }
+        Some(self.cursor.len())
+    }
+
+    /// Returns the unparsed part of the message.
+    ///
+    /// The returned slice spans from the current position of the reader to the end of
+    /// the message. It allows a proxy to parse only the header and the question, and forward
+    /// the rest of the message unchanged.
+    ///
+    /// Note that domain names in the returned bytes may contain compression pointers to
+    /// the already parsed part of the message. Thus the bytes are meaningful only together with
+    /// the preceding part of the message.
+    ///
+    /// This method is not affected by an error state of the reader.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rsdns::message::reader::MessageReader;
+    ///
+    /// // returns the encoded records of a message
+    /// fn records_bytes(msg: &[u8]) -> rsdns::Result<&[u8]> {
+    ///     let mut mr = MessageReader::new(msg)?;
+    ///     mr.header()?;
+    ///     mr.skip_questions()?;
+    ///     Ok(mr.remaining_bytes())
+    /// }
+    /// ```
+    #[inline]
+    pub fn remaining_bytes(&self) -> &'a [u8] {
+        self.cursor.remaining()
+    }
+
+    /// Returns the length of the message in bytes.
+    ///
+    /// This is the length of the whole message the reader was created with, regardless of
+    /// the reader position. This method is not affected by an error state of the reader.
+    #[inline]
+    pub fn message_len(&self) -> usize {
+        self.cursor.capacity()
+    }
+
+    /// Returns the whole message the reader was created with, regardless of the reader position.
+    #[inline]
+    pub(crate) fn message(&self) -> &'a [u8] {
+        self.cursor.clone_with_pos(0).remaining()
+    }
+
+    /// Checks if the message fits in a UDP payload of a given size.
+    ///
+    /// `payload_size` is usually the UDP payload size advertised by the requestor in the `OPT`
+    /// record of a query ([`Opt::udp_payload_size`]). Values below `512` are treated as `512`,
+    /// the maximal message size of DNS over UDP without EDNS ([RFC 6891 section 6.2.3]).
+    /// A message which doesn't fit should be truncated, e.g. with
+    /// [`ResponseBuilder::set_max_size`](crate::message::ResponseBuilder::set_max_size).
+    ///
+    /// [RFC 6891 section 6.2.3]: https://www.rfc-editor.org/rfc/rfc6891.html#section-6.2.3
+    #[inline]
+    pub fn fits_in_udp(&self, payload_size: u16) -> bool {
+        self.message_len() <= (payload_size as usize).max(DNS_MESSAGE_BUFFER_MIN_LENGTH)
+    }
+
     #[inline(always)]
     fn calc_section(&mut self) -> Result<RecordsSection> {
         self.section_tracker
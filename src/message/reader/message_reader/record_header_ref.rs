@@ -1,9 +1,11 @@
 use crate::{
     message::{
-        reader::{NameRef, RecordMarker, RecordOffset},
+        reader::{NameRef, RecordHeader, RecordMarker, RecordOffset},
         RecordsSection,
     },
+    names::DName,
     records::{Class, Type},
+    Result,
 };
 
 /// A resource record header with [`NameRef`].
@@ -71,4 +73,15 @@ impl<'a> RecordHeaderRef<'a> {
     pub fn section(&self) -> RecordsSection {
         self.marker.section
     }
+
+    /// Converts the header into a [`RecordHeader`] with an owned domain name.
+    ///
+    /// The domain name is decoded from the message buffer, while the marker is copied as is.
+    /// This allows keeping a header after inspecting it, without reading the record again.
+    pub fn to_owned<N: DName>(&self) -> Result<RecordHeader<N>> {
+        Ok(RecordHeader {
+            name: self.name_ref.to_name()?,
+            marker: self.marker.clone(),
+        })
+    }
 }
@@ -1,7 +1,8 @@
 use crate::{
     message::{reader::*, RecordsSection},
     names::{InlineName, Name},
-    records::{data::*, Class, Type},
+    records::{data::*, Class, Opt, Type},
+    Error,
 };
 use std::{
     net::{Ipv4Addr, Ipv6Addr},
@@ -164,6 +165,615 @@ fn test_whole_message() {
     }
 }
 
+#[test]
+fn test_record_header_ref_to_owned() {
+    let mut mr = MessageReader::new(&M0[..]).expect("failed to create MessageReader");
+    mr.header().expect("failed to read the header");
+    mr.the_question_ref().expect("the_question_ref failed");
+
+    let mut headers = Vec::new();
+    while mr.has_records() {
+        let header = mr.record_header_ref().expect("record_header_ref failed");
+        mr.skip_record_data(header.marker())
+            .expect("skip_record_data failed");
+        if header.rtype() == Type::NS {
+            headers.push(header.to_owned::<Name>().expect("to_owned failed"));
+            let inline: RecordHeader<InlineName> = header.to_owned().unwrap();
+            assert_eq!(inline.name().as_str(), "bbc.com.");
+            assert_eq!(inline.marker(), header.marker());
+        }
+    }
+
+    assert_eq!(headers.len(), 8);
+    for h in headers {
+        assert_eq!(h.name().as_str(), "bbc.com.");
+        assert_eq!(h.section(), RecordsSection::Authority);
+        assert_eq!(h.ttl(), 106241);
+        mr.record_data_at::<Ns>(h.marker())
+            .expect("record_data_at failed");
+    }
+}
+
+#[test]
+fn test_name_ref_eq_name() {
+    let mut mr = MessageReader::new(&M0[..]).expect("failed to create MessageReader");
+    mr.header().expect("failed to read the header");
+    let question = mr.the_question_ref().expect("the_question_ref failed");
+
+    let bbc = Name::from_str("bbc.com.").unwrap();
+    let bbc_upper = InlineName::from_str("BBC.Com").unwrap();
+    let other = Name::from_str("bbc.co.uk").unwrap();
+    let sub = Name::from_str("www.bbc.com").unwrap();
+    let com = Name::from_str("com").unwrap();
+    let root = Name::from_str(".").unwrap();
+
+    assert!(question.qname.eq_name(&bbc).unwrap());
+    assert!(question.qname.eq_name(&bbc_upper).unwrap());
+    assert!(!question.qname.eq_name(&other).unwrap());
+    assert!(!question.qname.eq_name(&sub).unwrap());
+    assert!(!question.qname.eq_name(&com).unwrap());
+    assert!(!question.qname.eq_name(&root).unwrap());
+
+    while mr.has_records() {
+        let header = mr.record_header_ref().expect("record_header_ref failed");
+        mr.skip_record_data(header.marker())
+            .expect("skip_record_data failed");
+        let expected = question.qname.eq(header.name()).unwrap();
+        assert_eq!(header.name().eq_name(&bbc).unwrap(), expected);
+    }
+}
+
+#[test]
+fn test_collect_markers() {
+    let mut mr = MessageReader::new(&M0[..]).expect("failed to create MessageReader");
+    mr.header().expect("failed to read the header");
+    let markers = mr.collect_markers().expect("collect_markers failed");
+    assert_eq!(markers.len(), 24);
+    assert!(!mr.has_records());
+    assert_eq!(mr.trailing_bytes(), Some(0));
+
+    let sections: Vec<_> = markers.iter().map(|m| m.section()).collect();
+    assert!(sections[..4].iter().all(|s| *s == RecordsSection::Answer));
+    assert!(sections[4..12]
+        .iter()
+        .all(|s| *s == RecordsSection::Authority));
+    assert!(sections[12..]
+        .iter()
+        .all(|s| *s == RecordsSection::Additional));
+
+    let ns = mr.record_data_at::<Ns>(&markers[5]).unwrap();
+    assert_eq!(ns.nsdname, "dns0.bbc.com.");
+
+    // the already read records are not collected
+    let mut mr = MessageReader::new(&M0[..]).expect("failed to create MessageReader");
+    mr.header().expect("failed to read the header");
+    mr.seek(RecordsSection::Additional).expect("seek failed");
+    assert_eq!(mr.collect_markers().unwrap()[..], markers[12..]);
+}
+
+// an mDNS announcement without questions
+//
+// printer.local.  120  IN  A  192.168.1.10
+// printer.local.  120  IN  A  192.168.1.11
+const M_NO_QUESTIONS: [u8; 57] = [
+    0x00, 0x00, 0x84, 0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x07, 0x70, 0x72, 0x69,
+    0x6e, 0x74, 0x65, 0x72, 0x05, 0x6c, 0x6f, 0x63, 0x61, 0x6c, 0x00, 0x00, 0x01, 0x00, 0x01, 0x00,
+    0x00, 0x00, 0x78, 0x00, 0x04, 0xc0, 0xa8, 0x01, 0x0a, 0xc0, 0x0c, 0x00, 0x01, 0x00, 0x01, 0x00,
+    0x00, 0x00, 0x78, 0x00, 0x04, 0xc0, 0xa8, 0x01, 0x0b,
+];
+
+#[test]
+fn test_no_questions() {
+    let mut mr = MessageReader::new(&M_NO_QUESTIONS[..]).expect("failed to create MessageReader");
+    let header = mr.header().expect("failed to read the header");
+    assert_eq!(header.qd_count, 0);
+    assert!(!mr.has_questions());
+    mr.seek(RecordsSection::Answer).expect("seek failed");
+
+    for last in [10, 11] {
+        let header: RecordHeader<Name> = mr.record_header().expect("record_header failed");
+        assert_eq!(*header.name(), "printer.local.");
+        assert_eq!(header.rclass(), Class::IN);
+        let a: A = mr.record_data(header.marker()).expect("record_data failed");
+        assert_eq!(a.address, Ipv4Addr::new(192, 168, 1, last));
+    }
+    assert!(!mr.has_records());
+    assert_eq!(mr.trailing_bytes(), Some(0));
+
+    let mut mr = MessageReader::new(&M_NO_QUESTIONS[..]).expect("failed to create MessageReader");
+    mr.header().expect("failed to read the header");
+    assert_eq!(mr.collect_markers().unwrap().len(), 2);
+
+    let mi = MessageIterator::new(&M_NO_QUESTIONS[..]).expect("failed to create MessageIterator");
+    assert!(matches!(mi.question(), Err(Error::BadQuestionsCount(0))));
+    assert_eq!(mi.questions().count(), 0);
+    assert_eq!(mi.records().filter(|r| r.is_ok()).count(), 2);
+}
+
+#[test]
+fn test_marker_at() {
+    let mut markers = Vec::new();
+    let mut mr = MessageReader::new(&M0[..]).expect("failed to create MessageReader");
+    mr.header().expect("failed to read the header");
+    assert!(matches!(
+        mr.marker_at(RecordsSection::Answer, 0),
+        Err(Error::RecordsSectionOffsetUnknown(RecordsSection::Answer))
+    ));
+    mr.skip_questions().expect("skip_questions failed");
+    while mr.has_records() {
+        let marker = mr.record_marker().expect("record_marker failed");
+        mr.skip_record_data(&marker)
+            .expect("skip_record_data failed");
+        markers.push(marker);
+    }
+
+    for (section, indices) in [
+        (RecordsSection::Answer, vec![0, 1, 2, 3]),
+        (RecordsSection::Authority, vec![7, 2, 5, 0]),
+        (RecordsSection::Additional, vec![11, 3, 4, 4, 8]),
+    ] {
+        let section_markers: Vec<_> = markers.iter().filter(|m| m.section == section).collect();
+        for index in indices {
+            let marker = mr.marker_at(section, index).expect("marker_at failed");
+            assert_eq!(&marker, section_markers[index]);
+        }
+    }
+
+    let marker = mr.marker_at(RecordsSection::Answer, 2).unwrap();
+    assert_eq!(
+        mr.record_data_at::<A>(&marker).unwrap().address,
+        Ipv4Addr::new(151, 101, 64, 81)
+    );
+
+    assert!(matches!(
+        mr.marker_at(RecordsSection::Answer, 4),
+        Err(Error::BadParam(_))
+    ));
+}
+
+#[test]
+fn test_reset() {
+    let mut mr = MessageReader::new(&M0[..]).expect("failed to create MessageReader");
+    mr.header().expect("failed to read the header");
+    mr.seek(RecordsSection::Additional).expect("seek failed");
+    while mr.has_records() {
+        let marker = mr.record_marker().expect("record_marker failed");
+        mr.skip_record_data(&marker)
+            .expect("skip_record_data failed");
+    }
+    assert!(matches!(mr.record_marker(), Err(Error::ReaderDone)));
+
+    mr.reset(&M1[..]).expect("reset failed");
+    let header = mr.header().expect("failed to read the header");
+    assert_eq!(header.id, 30724);
+    let question = mr.the_question().expect("the_question failed");
+    assert_eq!(question.qname, "cnn.com.");
+    assert_eq!(mr.records_count(), 4);
+    while mr.has_records() {
+        let marker = mr.record_marker().expect("record_marker failed");
+        mr.record_data::<A>(&marker).expect("record_data failed");
+    }
+
+    mr.reset(&M0[..]).expect("reset failed");
+    mr.header().expect("failed to read the header");
+    mr.seek(RecordsSection::Authority).expect("seek failed");
+    assert_eq!(mr.records_count(), 20);
+
+    let long = vec![0u8; u16::MAX as usize + 1];
+    assert!(matches!(mr.reset(&long), Err(Error::MessageTooLong(_))));
+    assert_eq!(mr.records_count(), 20);
+}
+
+#[test]
+fn test_trailing_bytes() {
+    let mut mr = MessageReader::new(&M1[..]).expect("failed to create MessageReader");
+    assert_eq!(mr.trailing_bytes(), None);
+    mr.header().expect("failed to read the header");
+    assert_eq!(mr.trailing_bytes(), None);
+    mr.skip_questions().expect("skip_questions failed");
+    while mr.has_records() {
+        assert_eq!(mr.trailing_bytes(), None);
+        let marker = mr.record_marker().expect("record_marker failed");
+        mr.skip_record_data(&marker)
+            .expect("skip_record_data failed");
+    }
+    assert_eq!(mr.trailing_bytes(), Some(0));
+
+    let mut msg = M1.to_vec();
+    msg.extend_from_slice(&M1[..]);
+    let mut mr = MessageReader::new(&msg).expect("failed to create MessageReader");
+    mr.header().expect("failed to read the header");
+    mr.seek(RecordsSection::Additional).expect("seek failed");
+    assert_eq!(mr.trailing_bytes(), Some(M1.len()));
+
+    let mut mr = MessageReader::new(&M1[..M1.len() - 1]).expect("failed to create MessageReader");
+    mr.header().expect("failed to read the header");
+    assert!(mr.seek(RecordsSection::Additional).is_err());
+    assert_eq!(mr.trailing_bytes(), None);
+}
+
+#[test]
+fn test_peek_question() {
+    let mut mr = MessageReader::new(&M0[..]).expect("failed to create MessageReader");
+    mr.header().expect("failed to read the header");
+
+    let question = mr.peek_question().expect("peek_question failed");
+    assert_eq!(question.qname, "bbc.com.");
+    assert_eq!(question.qtype, Type::A);
+    assert_eq!(question.qclass, Class::IN);
+    assert_eq!(mr.questions_count(), 1);
+
+    assert_eq!(mr.the_question().expect("the_question failed"), question);
+    assert!(matches!(
+        mr.peek_question(),
+        Err(Error::BadQuestionsCount(0))
+    ));
+
+    let mut mr = MessageReader::new(&M0[..]).expect("failed to create MessageReader");
+    mr.header().expect("failed to read the header");
+    mr.peek_question().expect("peek_question failed");
+    mr.seek(RecordsSection::Additional).expect("seek failed");
+    assert_eq!(mr.records_count(), 12);
+}
+
+#[test]
+fn test_question_bytes() {
+    let mut mr = MessageReader::new(&M0[..]).expect("failed to create MessageReader");
+    mr.header().expect("failed to read the header");
+
+    // bbc.com A IN
+    let expected = b"\x03bbc\x03com\x00\x00\x01\x00\x01";
+    assert_eq!(
+        mr.question_bytes().expect("question_bytes failed"),
+        expected
+    );
+    assert_eq!(mr.remaining_bytes()[..expected.len()], expected[..]);
+    assert_eq!(mr.questions_count(), 1);
+
+    mr.skip_questions().expect("skip_questions failed");
+    assert!(mr
+        .question_bytes()
+        .expect("question_bytes failed")
+        .is_empty());
+
+    // a truncated question
+    let mut mr = MessageReader::new(&M0[..20]).expect("failed to create MessageReader");
+    mr.header().expect("failed to read the header");
+    assert!(mr.question_bytes().is_err());
+    assert_eq!(mr.questions_count(), 1);
+}
+
+#[test]
+fn test_record_data_checked() {
+    let mut mr = MessageReader::new(&M0[..]).expect("failed to create MessageReader");
+    mr.header().expect("failed to read the header");
+    mr.skip_questions().expect("skip_questions failed");
+
+    let marker = mr.record_marker().expect("record_marker failed");
+    assert!(matches!(
+        mr.record_data_checked::<Aaaa>(&marker),
+        Err(Error::RecordTypeMismatch {
+            expected: Type::AAAA,
+            actual: Type::A
+        })
+    ));
+
+    let a: A = mr
+        .record_data_checked(&marker)
+        .expect("record_data_checked failed");
+    assert_eq!(a.address, Ipv4Addr::new(151, 101, 128, 81));
+}
+
+#[test]
+fn test_record_data_with_ttl() {
+    let mut mr = MessageReader::new(&M0[..]).expect("failed to create MessageReader");
+    mr.header().expect("failed to read the header");
+    mr.skip_questions().expect("skip_questions failed");
+
+    let marker = mr.record_marker().expect("record_marker failed");
+    let (a, ttl) = mr
+        .record_data_with_ttl::<A>(&marker)
+        .expect("record_data_with_ttl failed");
+    assert_eq!(a.address, Ipv4Addr::new(151, 101, 128, 81));
+    assert_eq!(ttl, 300);
+    assert_eq!(ttl, marker.ttl());
+}
+
+#[test]
+fn test_next_record() {
+    for msg in [&M0[..], &M1[..]] {
+        let expected: Vec<_> = MessageIterator::new(msg)
+            .expect("failed to create MessageIterator")
+            .records()
+            .collect::<crate::Result<_>>()
+            .expect("failed to read records");
+
+        let mut mr = MessageReader::new(msg).expect("failed to create MessageReader");
+        mr.header().expect("failed to read the header");
+
+        let mut actual = Vec::new();
+        while let Some(r) = mr.next_record().expect("next_record failed") {
+            actual.push(r);
+        }
+
+        assert_eq!(actual, expected);
+        assert!(mr.next_record().expect("next_record failed").is_none());
+        assert_eq!(mr.trailing_bytes(), Some(0));
+    }
+
+    let mut mr = MessageReader::new(&M0[..400]).expect("failed to create MessageReader");
+    mr.header().expect("failed to read the header");
+    let res = loop {
+        match mr.next_record() {
+            Ok(Some(_)) => continue,
+            res => break res,
+        }
+    };
+    assert!(matches!(res, Err(Error::EndOfBuffer { .. })));
+    assert!(matches!(mr.next_record(), Ok(None)));
+}
+
+#[test]
+fn test_answers() {
+    let mut mr = MessageReader::new(&M0[..]).expect("failed to create MessageReader");
+    let answers = mr
+        .answers()
+        .expect("answers failed")
+        .collect::<crate::Result<Vec<_>>>()
+        .expect("failed to read answers");
+
+    let expected = [
+        Ipv4Addr::new(151, 101, 128, 81),
+        Ipv4Addr::new(151, 101, 192, 81),
+        Ipv4Addr::new(151, 101, 64, 81),
+        Ipv4Addr::new(151, 101, 0, 81),
+    ];
+    assert_eq!(answers.len(), expected.len());
+    for ((section, record), address) in answers.iter().zip(expected) {
+        assert_eq!(*section, RecordsSection::Answer);
+        assert_eq!(record.name.as_str(), "bbc.com.");
+        assert_eq!(record.rdata, RecordData::A(A::from(address)));
+    }
+
+    // the reader is positioned at the answer section
+    assert_eq!(mr.records_count_in(RecordsSection::Answer), 4);
+    let marker = mr.record_marker().expect("failed to read record marker");
+    assert_eq!(marker.rtype(), Type::A);
+
+    let mut mr = MessageReader::new(&M0[..6]).expect("failed to create MessageReader");
+    assert!(matches!(mr.answers(), Err(Error::EndOfBuffer { .. })));
+}
+
+#[test]
+fn test_collect_addresses() {
+    let mut mr = MessageReader::new(&M0[..]).expect("failed to create MessageReader");
+    let addrs = mr.collect_addresses().expect("collect_addresses failed");
+    let expected: Vec<std::net::IpAddr> = vec![
+        Ipv4Addr::new(151, 101, 128, 81).into(),
+        Ipv4Addr::new(151, 101, 192, 81).into(),
+        Ipv4Addr::new(151, 101, 64, 81).into(),
+        Ipv4Addr::new(151, 101, 0, 81).into(),
+    ];
+    assert_eq!(addrs, expected);
+
+    // CNAME and non-IN records are skipped, A and AAAA are kept in order
+    let mut qbuf = [0u8; 512];
+    let len = crate::message::QueryWriter::new(&mut qbuf)
+        .write("example.com", Type::A, Class::IN, true, None)
+        .expect("failed to write query");
+    let mut buf = [0u8; 512];
+    let mut rb = crate::message::ResponseBuilder::new(&mut buf, &qbuf[2..len])
+        .expect("failed to create ResponseBuilder");
+    let cname = Cname {
+        cname: Name::from_str("target.example.com.").unwrap(),
+    };
+    rb.add_answer("example.com", Class::IN, 300, &cname)
+        .unwrap();
+    rb.add_answer(
+        "target.example.com",
+        Class::IN,
+        300,
+        &A::from(Ipv4Addr::new(192, 0, 2, 1)),
+    )
+    .unwrap();
+    rb.add_answer(
+        "target.example.com",
+        Class::CH,
+        300,
+        &A::from(Ipv4Addr::new(192, 0, 2, 2)),
+    )
+    .unwrap();
+    rb.add_answer(
+        "target.example.com",
+        Class::IN,
+        300,
+        &Aaaa::from(Ipv6Addr::LOCALHOST),
+    )
+    .unwrap();
+    let len = rb.finish().expect("finish failed");
+
+    let mut mr = MessageReader::new(&buf[2..len]).expect("failed to create MessageReader");
+    let addrs = mr.collect_addresses().expect("collect_addresses failed");
+    let expected: Vec<std::net::IpAddr> = vec![
+        Ipv4Addr::new(192, 0, 2, 1).into(),
+        Ipv6Addr::LOCALHOST.into(),
+    ];
+    assert_eq!(addrs, expected);
+
+    let mut mr = MessageReader::new(&M0[..40]).expect("failed to create MessageReader");
+    assert!(matches!(
+        mr.collect_addresses(),
+        Err(Error::EndOfBuffer { .. })
+    ));
+}
+
+#[test]
+fn test_message_iterator_opt() {
+    let mi = MessageIterator::new(&M0[..]).expect("failed to create MessageIterator");
+    assert!(mi.opt().expect("opt failed").is_none());
+    assert!(!mi.has_opt().expect("has_opt failed"));
+
+    let mut buf = [0u8; 512];
+    let mut qw = crate::message::QueryWriter::new(&mut buf);
+    let len = qw
+        .write(
+            "example.com",
+            Type::A,
+            Class::IN,
+            true,
+            Some(Opt::new(1, 1400)),
+        )
+        .expect("failed to write query");
+
+    let mi = MessageIterator::new(&buf[2..len]).expect("failed to create MessageIterator");
+    assert!(mi.has_opt().expect("has_opt failed"));
+    let opt = mi
+        .opt()
+        .expect("opt failed")
+        .expect("OPT record is missing");
+    assert_eq!(opt.udp_payload_size(), 1400);
+    assert_eq!(opt.version(), 1);
+
+    // a second OPT record, copied from the first one
+    buf.copy_within(len - 11..len, len);
+    buf[2 + 11] = 2;
+    let mi = MessageIterator::new(&buf[2..len + 11]).expect("failed to create MessageIterator");
+    assert!(matches!(mi.opt(), Err(Error::MultipleOptRecords)));
+}
+
+#[test]
+fn test_strict_edns() {
+    fn read_opt(msg: &[u8], strict: bool) -> crate::Result<Opt> {
+        let mut mr = MessageReader::new(msg)?;
+        mr.set_strict_edns(strict);
+        mr.header()?;
+        mr.seek(RecordsSection::Additional)?;
+        let marker = mr.record_marker()?;
+        mr.opt_record(&marker)
+    }
+
+    let mut buf = [0u8; 512];
+    for (version, flags, err) in [
+        (0, 0x8000, None),
+        (1, 0, Some(Error::UnsupportedEdnsVersion(1))),
+        (0, 0x8001, Some(Error::ReservedEdnsFlags(1))),
+        (0, 0x4000, Some(Error::ReservedEdnsFlags(0x4000))),
+    ] {
+        let mut qw = crate::message::QueryWriter::new(&mut buf);
+        let len = qw
+            .write(
+                "example.com",
+                Type::A,
+                Class::IN,
+                true,
+                Some(Opt::new(version, 1232)),
+            )
+            .expect("failed to write query");
+        // the flags of an OPT record without options precede RDLEN
+        buf[len - 4..len - 2].copy_from_slice(&u16::to_be_bytes(flags));
+        let msg = &buf[2..len];
+
+        let opt = read_opt(msg, false).expect("lenient opt_record failed");
+        assert_eq!(opt.version(), version);
+        match err {
+            None => assert!(read_opt(msg, true).unwrap().dnssec_ok()),
+            Some(e) => assert_eq!(read_opt(msg, true).unwrap_err().to_string(), e.to_string()),
+        }
+    }
+}
+
+#[test]
+fn test_message_len() {
+    let mut mr = MessageReader::new(&M0[..]).expect("failed to create MessageReader");
+    assert_eq!(mr.message_len(), M0.len());
+    mr.header().expect("failed to read the header");
+    mr.skip_questions().expect("skip_questions failed");
+    assert_eq!(mr.message_len(), M0.len());
+
+    assert!(M0.len() <= 512);
+    assert!(mr.fits_in_udp(0));
+    assert!(mr.fits_in_udp(M0.len() as u16));
+
+    let msg = [0u8; 1300];
+    let mr = MessageReader::new(&msg).expect("failed to create MessageReader");
+    assert!(!mr.fits_in_udp(0));
+    assert!(!mr.fits_in_udp(1232));
+    assert!(mr.fits_in_udp(1300));
+    assert!(mr.fits_in_udp(4096));
+}
+
+#[test]
+fn test_read_from() {
+    let mut stream = Vec::new();
+    for _ in 0..2 {
+        stream.extend_from_slice(&(M0.len() as u16).to_be_bytes());
+        stream.extend_from_slice(&M0);
+    }
+
+    let mut rd = &stream[..];
+    for _ in 0..2 {
+        let mut buf = [0u8; 512];
+        let mut mr = MessageReader::read_from(&mut rd, &mut buf).expect("read_from failed");
+        assert_eq!(mr.header().expect("failed to read the header").an_count, 4);
+        assert_eq!(mr.remaining_bytes().len(), M0.len() - 12);
+    }
+    assert!(rd.is_empty());
+
+    let mut buf = [0u8; 16];
+    let mut rd = &stream[..];
+    assert!(matches!(
+        MessageReader::read_from(&mut rd, &mut buf),
+        Err(Error::BufferTooShort(l)) if l == M0.len()
+    ));
+
+    let mut buf = [0u8; 512];
+    let mut rd = &stream[..M0.len()];
+    assert!(matches!(
+        MessageReader::read_from(&mut rd, &mut buf),
+        Err(Error::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof
+    ));
+
+    let mut rd = &b"\x00\x05hello"[..];
+    assert!(matches!(
+        MessageReader::read_from(&mut rd, &mut buf),
+        Err(Error::MessageTooShort(5))
+    ));
+}
+
+#[test]
+fn test_remaining_bytes() {
+    let mut mr = MessageReader::new(&M0[..]).expect("failed to create MessageReader");
+    assert_eq!(mr.remaining_bytes(), &M0[..]);
+    mr.header().expect("failed to read the header");
+    assert_eq!(mr.remaining_bytes(), &M0[12..]);
+    mr.skip_questions().expect("skip_questions failed");
+    assert_eq!(mr.remaining_bytes(), &M0[25..]);
+    while mr.has_records() {
+        let marker = mr.record_marker().expect("record_marker failed");
+        mr.skip_record_data(&marker)
+            .expect("skip_record_data failed");
+    }
+    assert!(mr.remaining_bytes().is_empty());
+}
+
+#[test]
+fn test_end_of_buffer_offset() {
+    // header (12 bytes), question (13 bytes), first answer record header is cut after
+    // the name (2 bytes), type (2 bytes) and one byte of the class
+    let mut mr = MessageReader::new(&M0[..30]).expect("failed to create MessageReader");
+    mr.header().expect("failed to read the header");
+    mr.skip_questions().expect("skip_questions failed");
+    let res = mr.record_marker();
+    assert!(matches!(res, Err(Error::EndOfBuffer { offset: 29 })));
+
+    let mut mr = MessageReader::new(&M0[..40]).expect("failed to create MessageReader");
+    mr.header().expect("failed to read the header");
+    mr.skip_questions().expect("skip_questions failed");
+    let marker = mr.record_marker().expect("record_marker failed");
+    let res = mr.record_data::<A>(&marker);
+    assert!(matches!(res, Err(Error::EndOfBuffer { offset: 37 })));
+}
+
 #[test]
 fn test_answer_section() {
     let mut mr = MessageReader::new(&M0[..]).expect("failed to create MessageReader");
@@ -410,3 +1020,36 @@ fn test_seek_additional() {
     let a_record = mr.record_data::<A>(record_header.marker()).unwrap();
     assert_eq!(a_record.address, Ipv4Addr::from_str("198.51.44.9").unwrap());
 }
+
+#[test]
+fn test_name_pointers() {
+    #[rustfmt::skip]
+    const MSG: [u8; 63] = [
+        0x00, 0x01, 0x81, 0x80, 0x00, 0x01, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, // header
+        0x07, b'e', b'x', b'a', b'm', b'p', b'l', b'e', 0x03, b'c', b'o', b'm', 0x00,
+        0x00, 0x01, 0x00, 0x01, // question
+        0xC0, 0x0C, 0x00, 0x05, 0x00, 0x01, 0x00, 0x00, 0x01, 0x2C, 0x00, 0x06,
+        0x03, b'w', b'w', b'w', 0xC0, 0x0C, // example.com CNAME www.example.com
+        0xC0, 0x29, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x01, 0x2C, 0x00, 0x04,
+        0xC0, 0x00, 0x02, 0x01, // www.example.com A 192.0.2.1
+    ];
+
+    let mi = MessageIterator::new(&MSG[..]).expect("failed to create MessageIterator");
+    let pointers = mi.name_pointers().expect("name_pointers failed");
+    assert_eq!(
+        pointers,
+        NamePointers {
+            names: 4,
+            total: 4,
+            max_per_name: 2,
+        }
+    );
+
+    let mi = MessageIterator::new(&M0[..]).expect("failed to create MessageIterator");
+    let pointers = mi.name_pointers().expect("name_pointers failed");
+    assert_eq!(pointers.names, 1 + 4 + 8 + 8 + 12);
+    assert!(pointers.max_per_name <= 2);
+
+    let mi = MessageIterator::new(&MSG[..60]).expect("failed to create MessageIterator");
+    assert!(matches!(mi.name_pointers(), Err(Error::EndOfBuffer { .. })));
+}
@@ -15,6 +15,9 @@ pub use questions::*;
 mod labels;
 pub use labels::*;
 
+mod name_pointers;
+pub use name_pointers::*;
+
 mod name_ref;
 pub use name_ref::*;
 
@@ -0,0 +1,34 @@
+/// Statistics of the compression pointers followed while parsing the domain names of a message.
+///
+/// A domain name may be compressed by replacing its suffix with a pointer to a prior occurrence
+/// of the same suffix in the message. Every name is limited to
+/// [`DOMAIN_NAME_MAX_POINTERS`] pointers, and a name exceeding this limit fails to parse.
+/// A message with many pointers, or with names chaining multiple pointers, is legal, but is
+/// rarely produced by real servers. The statistics allow an application to apply a stricter
+/// policy, e.g. to flag responses that look crafted to stress the parser.
+///
+/// See [`MessageIterator::name_pointers`](super::MessageIterator::name_pointers).
+///
+/// [`DOMAIN_NAME_MAX_POINTERS`]: crate::constants::DOMAIN_NAME_MAX_POINTERS
+///
+/// [RFC 1035 section 4.1.4](https://www.rfc-editor.org/rfc/rfc1035.html#section-4.1.4)
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Default, Debug)]
+pub struct NamePointers {
+    /// The number of domain names parsed.
+    pub names: usize,
+
+    /// The total number of pointers followed.
+    pub total: usize,
+
+    /// The maximal number of pointers followed while parsing a single domain name.
+    pub max_per_name: usize,
+}
+
+impl NamePointers {
+    #[inline]
+    pub(crate) fn add(&mut self, n_pointers: usize) {
+        self.names += 1;
+        self.total += n_pointers;
+        self.max_per_name = self.max_per_name.max(n_pointers);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     bytes::Cursor,
     message::reader::{read_domain_name, Labels},
-    names::{InlineName, Name},
+    names::{DName, InlineName, Name},
     Error, Result,
 };
 
@@ -72,6 +72,37 @@ impl<'a> NameRef<'a> {
     pub fn ne(&self, other: &Self) -> Result<bool> {
         Ok(!self.eq(other)?)
     }
+
+    /// Checks if this `NameRef` points to the same name as a domain name object.
+    ///
+    /// The comparison is case-insensitive and is done label by label directly on the
+    /// message bytes, following compression pointers. It doesn't allocate memory.
+    ///
+    /// This is useful for comparing the owner name of a record with the queried name,
+    /// without converting the `NameRef` to [`Name`] or [`InlineName`] first.
+    pub fn eq_name(&self, other: &impl DName) -> Result<bool> {
+        let other = other.as_str();
+        let other = other.strip_suffix('.').unwrap_or(other);
+        let mut other_labels = other.split('.').filter(|_| !other.is_empty());
+
+        for label in self.labels() {
+            let label = label?;
+            match other_labels.next() {
+                Some(ol) if label.bytes.eq_ignore_ascii_case(ol.as_bytes()) => {}
+                _ => return Ok(false),
+            }
+        }
+
+        Ok(other_labels.next().is_none())
+    }
+}
+
+impl NameRef<'_> {
+    /// Decodes the domain name into a domain name object of any type.
+    #[inline]
+    pub(crate) fn to_name<N: DName>(&self) -> Result<N> {
+        read_domain_name(&mut self.cursor.clone())
+    }
 }
 
 impl TryFrom<NameRef<'_>> for Name {
@@ -10,6 +10,33 @@ use crate::{
 /// As opposed to [`Question`], `QuestionRef` uses a `NameRef` for the domain name.
 /// It doesn't own the domain name bytes, but rather points into the message buffer.
 ///
+/// The question name is compared with record owner names of the same message using
+/// [`NameRef::eq`], and with domain names outside the message using [`NameRef::eq_name`].
+/// Neither comparison allocates memory.
+///
+/// # Examples
+///
+/// ```rust
+/// use rsdns::message::{reader::MessageReader, RecordsSection};
+///
+/// // counts the answer records owned by the question name
+/// fn count_direct_answers(msg: &[u8]) -> rsdns::Result<usize> {
+///     let mut mr = MessageReader::new(msg)?;
+///     mr.header()?;
+///     let question = mr.the_question_ref()?;
+///
+///     let mut count = 0;
+///     while mr.has_records_in(RecordsSection::Answer) {
+///         let header = mr.record_header_ref()?;
+///         if question.qname.eq(header.name())? {
+///             count += 1;
+///         }
+///         mr.skip_record_data(header.marker())?;
+///     }
+///     Ok(count)
+/// }
+/// ```
+///
 /// [`Question`]: crate::message::Question
 #[derive(Debug, Clone)]
 pub struct QuestionRef<'a> {
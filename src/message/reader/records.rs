@@ -133,6 +133,8 @@ impl<'a> Records<'a> {
                     Type::MX => rrr!(self, Type::MX, Mx, domain_name_pos, rclass, ttl, rdlen),
                     Type::TXT => rrr!(self, Type::TXT, Txt, domain_name_pos, rclass, ttl, rdlen),
                     Type::AAAA => rrr!(self, Type::AAAA, Aaaa, domain_name_pos, rclass, ttl, rdlen),
+                    Type::SPF => rrr!(self, Type::SPF, Spf, domain_name_pos, rclass, ttl, rdlen),
+                    Type::TKEY => rrr!(self, Type::TKEY, Tkey, domain_name_pos, rclass, ttl, rdlen),
                     /* Type::OPT => OPT record is supported in MessageReader only */
                     _ => {
                         return Err(Error::UnexpectedType(rtype));
@@ -149,6 +151,95 @@ impl<'a> Records<'a> {
     }
 }
 
+impl Records<'_> {
+    /// Reads the remaining records, keeping those read successfully before an error.
+    ///
+    /// This is a lenient alternative to collecting the iterator into a `Result<Vec<_>>`,
+    /// which discards all records on the first error. It is useful for analyzing truncated
+    /// messages, e.g. packets captured partially.
+    ///
+    /// Returns the records read successfully, and the error which terminated reading, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rsdns::message::reader::MessageIterator;
+    ///
+    /// fn print_records(buf: &[u8]) -> rsdns::Result<()> {
+    ///     let mi = MessageIterator::new(buf)?;
+    ///
+    ///     let (records, err) = mi.records().collect_lenient();
+    ///     for (section, record) in records {
+    ///         println!("{section}: {record:?}");
+    ///     }
+    ///     if let Some(e) = err {
+    ///         println!("message is malformed: {e}");
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn collect_lenient(self) -> (Vec<(RecordsSection, ResourceRecord)>, Option<Error>) {
+        let mut records = Vec::new();
+        for res in self {
+            match res {
+                Ok(r) => records.push(r),
+                Err(e) => return (records, Some(e)),
+            }
+        }
+        (records, None)
+    }
+
+    /// Reads the remaining records, verifying that the message contains all declared records.
+    ///
+    /// This is a strict alternative to collecting the iterator into a `Result<Vec<_>>`.
+    /// If the message ends before all records declared in the message header are read,
+    /// [`Error::CountMismatch`] is returned with the number of records declared and
+    /// the number of records read completely in the first incomplete section.
+    /// This allows detecting truncated or malformed messages, which otherwise yield fewer
+    /// records than advertised.
+    ///
+    /// Note that records of unsupported types are skipped, but are counted as read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rsdns::{message::reader::MessageIterator, Error};
+    ///
+    /// fn records_count(buf: &[u8]) -> rsdns::Result<usize> {
+    ///     let mi = MessageIterator::new(buf)?;
+    ///     match mi.records().collect_strict() {
+    ///         Ok(records) => Ok(records.len()),
+    ///         Err(Error::CountMismatch { section, claimed, actual }) => {
+    ///             println!("{section}: {actual} records out of {claimed}");
+    ///             Ok(0)
+    ///         }
+    ///         Err(e) => Err(e),
+    ///     }
+    /// }
+    /// ```
+    pub fn collect_strict(mut self) -> Result<Vec<(RecordsSection, ResourceRecord)>> {
+        let mut records = Vec::new();
+        loop {
+            match self.read_impl() {
+                Ok(Some(r)) => records.push(r),
+                Ok(None) => return Ok(records),
+                Err(e @ Error::EndOfBuffer { .. }) => {
+                    return match self.section_tracker.first_incomplete() {
+                        Some((section, claimed, actual)) => Err(Error::CountMismatch {
+                            section,
+                            claimed,
+                            actual,
+                        }),
+                        None => Err(e),
+                    };
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
 impl Iterator for Records<'_> {
     type Item = Result<(RecordsSection, ResourceRecord)>;
 
@@ -157,3 +248,53 @@ impl Iterator for Records<'_> {
         self.read()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::message::{reader::MessageIterator, RecordsSection};
+    use crate::Error;
+
+    #[rustfmt::skip]
+    const MSG: [u8; 58] = [
+        0x00, 0x01, 0x81, 0x80, 0x00, 0x01, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, // header
+        0x07, b'e', b'x', b'a', b'm', b'p', b'l', b'e', 0x03, b'c', b'o', b'm', 0x00,
+        0x00, 0x01, 0x00, 0x01, // question
+        0xC0, 0x0C, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x01, 0x2C, 0x00, 0x04,
+        0xC0, 0x00, 0x02, 0x01, // 192.0.2.1
+        0xC0, 0x0C, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x01, 0x2C, 0x00, 0x04,
+        0xC0, // truncated 192.0.2.2
+    ];
+
+    #[test]
+    fn test_collect_lenient() {
+        let mi = MessageIterator::new(&MSG[..]).unwrap();
+        let (records, err) = mi.records().collect_lenient();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].1.name.as_str(), "example.com.");
+        assert!(matches!(err, Some(Error::EndOfBuffer { offset: 57 })));
+
+        let mi = MessageIterator::new(&MSG[..45]).unwrap();
+        let (records, err) = mi.records().collect_lenient();
+        assert_eq!(records.len(), 1);
+        assert!(err.is_some());
+    }
+
+    #[test]
+    fn test_collect_strict() {
+        let mi = MessageIterator::new(&MSG[..]).unwrap();
+        assert!(matches!(
+            mi.records().collect_strict(),
+            Err(Error::CountMismatch {
+                section: RecordsSection::Answer,
+                claimed: 2,
+                actual: 1,
+            })
+        ));
+
+        let mut msg = MSG;
+        msg[7] = 1; // ANCOUNT
+        let mi = MessageIterator::new(&msg[..45]).unwrap();
+        let records = mi.records().collect_strict().unwrap();
+        assert_eq!(records.len(), 1);
+    }
+}
@@ -139,11 +139,23 @@ impl SectionTracker {
             .fold(0, |acc, c| acc + (c.total - c.read) as usize)
     }
 
+    pub fn records_in(&self, section: RecordsSection) -> usize {
+        self.sections[section as usize].total as usize
+    }
+
     pub fn records_left_in(&self, section: RecordsSection) -> usize {
         let counts = &self.sections[section as usize];
         (counts.total - counts.read) as usize
     }
 
+    /// Returns the first section with fewer records read than declared in the header.
+    pub fn first_incomplete(&self) -> Option<(RecordsSection, u16, u16)> {
+        RecordsSection::VALUES.into_iter().find_map(|s| {
+            let counts = &self.sections[s as usize];
+            (counts.read < counts.total).then_some((s, counts.total, counts.read))
+        })
+    }
+
     pub fn questions_left(&self) -> usize {
         (self.qd.total - self.qd.read) as usize
     }
@@ -0,0 +1,596 @@
+use crate::{
+    bytes::{WCursor, Writer},
+    constants::{DNS_MESSAGE_MAX_LENGTH, HEADER_LENGTH},
+    message::{reader::MessageReader, Flags, Header, MessageType, RCode},
+    records::{data::RData, Class},
+    Error, Result,
+};
+
+/// A DNS response builder.
+///
+/// `ResponseBuilder` serializes a response to a query into a caller-owned buffer.
+/// It codifies the common pattern of answering a query: the message ID, the opcode and the
+/// recursion desired flag are copied from the query, the question section is copied
+/// byte-for-byte, and the message is marked as a response. Answer records, the authoritative
+/// answer flag and the response code are then set by the caller.
+///
+/// Similarly to [`QueryWriter`], the response is written with the two-byte length prefix
+/// used by DNS over TCP ([RFC 1035 section 4.2.2]). For TCP-like transports the whole written
+/// buffer is sent. For UDP-like transports the first two bytes must be skipped.
+///
+/// # Examples
+///
+/// ```rust
+/// use rsdns::{
+///     message::{reader::MessageReader, QueryWriter, RCode, ResponseBuilder},
+///     records::{data::A, Class, Type},
+/// };
+/// use std::net::Ipv4Addr;
+///
+/// # fn foo() -> rsdns::Result<()> {
+/// // the query as received from a client over UDP
+/// let mut query = [0u8; 512];
+/// let len = QueryWriter::new(&mut query).write("example.com", Type::A, Class::IN, true, None)?;
+/// let query = &query[2..len];
+///
+/// let mut buf = [0u8; 512];
+/// let mut rb = ResponseBuilder::new(&mut buf, query)?;
+/// rb.set_authoritative_answer(true)
+///     .set_response_code(RCode::NOERROR)?
+///     .add_answer(
+///         "example.com.",
+///         Class::IN,
+///         3600,
+///         &A::from(Ipv4Addr::new(192, 0, 2, 1)),
+///     )?;
+/// let len = rb.finish()?;
+///
+/// let udp_response = &buf[2..len];
+/// let mut mr = MessageReader::new(udp_response)?;
+/// assert!(mr.header()?.flags.authoritative_answer());
+/// # Ok(())
+/// # }
+/// # foo().unwrap();
+/// ```
+///
+/// [`QueryWriter`]: crate::message::QueryWriter
+/// [RFC 1035 section 4.2.2]: https://www.rfc-editor.org/rfc/rfc1035.html#section-4.2.2
+pub struct ResponseBuilder<'a> {
+    wcursor: WCursor<'a>,
+    header: Header,
+    max_size: Option<usize>,
+}
+
+impl<'a> ResponseBuilder<'a> {
+    /// Starts a response to the `query` message.
+    ///
+    /// The query is expected without the two-byte length prefix, i.e. as received over UDP.
+    ///
+    /// # Returns
+    ///
+    /// - [`Error::BadMessageType`] - if `query` is not a query message
+    /// - [`Error::BufferTooShort`] - if the buffer is too short for the question section
+    /// - any error encountered while parsing the question section of `query`
+    pub fn new(buf: &'a mut [u8], query: &[u8]) -> Result<Self> {
+        let mut mr = MessageReader::new(query)?;
+        let qheader = mr.header()?;
+
+        if !qheader.is_query() {
+            return Err(Error::BadMessageType(qheader.message_type()));
+        }
+
+        let mut flags = Flags::new();
+        flags
+            .set_message_type(MessageType::Response)
+            .set_opcode(qheader.flags.opcode())
+            .set_recursion_desired(qheader.flags.recursion_desired());
+
+        let mut header = Header {
+            id: qheader.id,
+            flags,
+            ..Default::default()
+        };
+
+        if buf.len() < 2 + HEADER_LENGTH {
+            return Err(Error::BufferTooShort(2 + HEADER_LENGTH));
+        }
+        let mut wcursor = WCursor::new(buf);
+        wcursor.set_pos(2 + HEADER_LENGTH);
+
+        // the questions are copied verbatim, preserving the exact case of the names
+        wcursor.bytes(mr.question_bytes()?)?;
+        header.qd_count = qheader.qd_count;
+
+        Ok(Self {
+            wcursor,
+            header,
+            max_size: None,
+        })
+    }
+
+    /// Starts a response to a query parsed with `mr`.
+    ///
+    /// This is equivalent to [`new`](Self::new) with the message `mr` was created with.
+    /// The reader may be at any position, e.g. after the question was read to look up
+    /// the answer.
+    ///
+    /// # Returns
+    ///
+    /// - [`Error::BadMessageType`] - if the message is not a query message
+    /// - [`Error::BufferTooShort`] - if the buffer is too short for the question section
+    /// - any error encountered while parsing the question section of the query
+    pub fn from_reader(buf: &'a mut [u8], mr: &MessageReader<'_>) -> Result<Self> {
+        Self::new(buf, mr.message())
+    }
+
+    /// Returns the message ID of the response, copied from the query.
+    #[inline]
+    pub fn message_id(&self) -> u16 {
+        self.header.id
+    }
+
+    /// Sets the authoritative answer flag.
+    pub fn set_authoritative_answer(&mut self, value: bool) -> &mut Self {
+        self.header.flags.set_authoritative_answer(value);
+        self
+    }
+
+    /// Sets the recursion available flag.
+    pub fn set_recursion_available(&mut self, value: bool) -> &mut Self {
+        self.header.flags.set_recursion_available(value);
+        self
+    }
+
+    /// Sets the truncated flag.
+    ///
+    /// This flag should be set if not all records fit in the response buffer.
+    pub fn set_truncated(&mut self, value: bool) -> &mut Self {
+        self.header.flags.set_truncated(value);
+        self
+    }
+
+    /// Sets the maximal size of the response, excluding the two-byte length prefix.
+    ///
+    /// This is usually the UDP payload size the client advertised in the `OPT` record of the
+    /// query, or `512` bytes if the query has no `OPT` record.
+    ///
+    /// If set, the response is truncated by [`add_answer`] as a server should truncate it: a record
+    /// which doesn't fit into the maximal size, or into the buffer, is not written, and the
+    /// [truncated] flag is set instead. A record is never split. Once the response is truncated,
+    /// following records are not written either, so the response holds a prefix of the answer.
+    ///
+    /// Default: `None` - a record which doesn't fit into the buffer fails [`add_answer`]
+    ///
+    /// [`add_answer`]: Self::add_answer
+    /// [truncated]: Self::set_truncated
+    pub fn set_max_size(&mut self, max_size: Option<usize>) -> &mut Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Sets the response code.
+    ///
+    /// Only the response codes that fit into the message header are supported.
+    /// Extended response codes require an `OPT` record.
+    ///
+    /// # Returns
+    ///
+    /// - [`Error::BadParam`] - if `rcode` is an extended response code
+    pub fn set_response_code(&mut self, rcode: RCode) -> Result<&mut Self> {
+        if rcode.value() > 0xF {
+            return Err(Error::BadParam("extended response codes are not supported"));
+        }
+        self.header.flags.set_response_code(rcode);
+        Ok(self)
+    }
+
+    /// Adds a record to the answer section.
+    ///
+    /// The record name and the domain names embedded in the record data are written
+    /// uncompressed. If the record doesn't fit into the buffer, the response is left unchanged,
+    /// so it may still be finished, e.g. with the [truncated] flag set. If the maximal size of
+    /// the response is set, the truncation is done automatically, as described in
+    /// [`set_max_size`](Self::set_max_size).
+    ///
+    /// # Returns
+    ///
+    /// - [`Error::BufferTooShort`] - if the record doesn't fit into the buffer, and the maximal
+    ///   size of the response is not set
+    /// - [`Error::BadParam`] - if the record data is invalid or exceeds 65535 bytes
+    /// - [`Error::MessageTooLong`] - if the message with the record exceeds 65535 bytes
+    /// - any error encountered while encoding `name`
+    ///
+    /// [truncated]: Self::set_truncated
+    pub fn add_answer<D: RData>(
+        &mut self,
+        name: &str,
+        rclass: Class,
+        ttl: u32,
+        rdata: &D,
+    ) -> Result<&mut Self> {
+        if self.max_size.is_some() && self.header.flags.truncated() {
+            return Ok(self);
+        }
+
+        let pos = self.wcursor.pos();
+        let res = self
+            .write_record(name, rclass, ttl, rdata)
+            .and_then(|()| self.check_max_size());
+        match res {
+            Ok(()) => {
+                self.header.an_count += 1;
+                Ok(self)
+            }
+            Err(e) => {
+                self.wcursor.set_pos(pos);
+                match e {
+                    Error::BufferTooShort(_) | Error::MessageTooLong(_)
+                        if self.max_size.is_some() =>
+                    {
+                        self.header.flags.set_truncated(true);
+                        Ok(self)
+                    }
+                    e => Err(e),
+                }
+            }
+        }
+    }
+
+    /// Checks that the response written so far parses back consistently.
+    ///
+    /// The header is written, and the message is parsed with [`MessageReader`]. The question
+    /// section and all resource records are read, and the message must end right after
+    /// the last record. This catches inconsistencies between the section counts in
+    /// the header and the written records.
+    ///
+    /// The check is done by [`finish`](Self::finish) in debug builds.
+    ///
+    /// # Returns
+    ///
+    /// - [`Error::InternalError`] - if the message has trailing bytes
+    /// - any error encountered while parsing the message
+    pub fn validate(&mut self) -> Result<()> {
+        let pos = self.wcursor.pos();
+        self.wcursor.set_pos(2);
+        self.wcursor.write(&self.header)?;
+        self.wcursor.set_pos(pos);
+
+        let mut mr = MessageReader::new(self.wcursor.written_from(2))?;
+        mr.header()?;
+        mr.skip_questions()?;
+        while mr.next_record()?.is_some() {}
+        match mr.trailing_bytes() {
+            Some(0) => Ok(()),
+            _ => Err(Error::InternalError("response has trailing bytes")),
+        }
+    }
+
+    /// Finishes the response by writing the header and the length prefix.
+    ///
+    /// In debug builds the response is checked with [`validate`](Self::validate) first.
+    ///
+    /// Returns the number of bytes written, including the two-byte length prefix.
+    pub fn finish(mut self) -> Result<usize> {
+        #[cfg(debug_assertions)]
+        self.validate()?;
+
+        let pos = self.wcursor.reset_pos();
+        self.wcursor.u16_be((pos - 2) as u16)?;
+        self.wcursor.write(&self.header)?;
+        Ok(pos)
+    }
+
+    fn check_max_size(&self) -> Result<()> {
+        let msg_len = self.wcursor.pos() - 2;
+        match self.max_size {
+            Some(max_size) if msg_len > max_size => Err(Error::MessageTooLong(msg_len)),
+            _ => Ok(()),
+        }
+    }
+
+    fn write_record<D: RData>(
+        &mut self,
+        name: &str,
+        rclass: Class,
+        ttl: u32,
+        rdata: &D,
+    ) -> Result<()> {
+        let rdlen = rdata.encoded_len();
+        if rdlen > u16::MAX as usize {
+            return Err(Error::BadParam("record data exceeds 65535 bytes"));
+        }
+
+        self.wcursor.write_domain_name(name)?;
+        self.wcursor.u16_be(D::RTYPE.value())?;
+        self.wcursor.u16_be(rclass.value())?;
+        self.wcursor.u32_be(ttl)?;
+        self.wcursor.u16_be(rdlen as u16)?;
+
+        let written = rdata.encode_to(self.wcursor.slice(rdlen)?)?;
+        self.wcursor.set_pos(self.wcursor.pos() + written);
+
+        let msg_len = self.wcursor.pos() - 2;
+        if msg_len > DNS_MESSAGE_MAX_LENGTH {
+            return Err(Error::MessageTooLong(msg_len));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        message::{QueryWriter, RecordsSection},
+        records::{
+            data::{RecordData, Txt, A},
+            Type,
+        },
+    };
+    use std::net::Ipv4Addr;
+
+    fn query(buf: &mut [u8]) -> &[u8] {
+        let len = QueryWriter::with_id(buf, 0x1234)
+            .write("example.com", Type::A, Class::IN, true, None)
+            .unwrap();
+        &buf[2..len]
+    }
+
+    #[test]
+    fn test_good_flow() {
+        let mut qbuf = [0u8; 512];
+        let query = query(&mut qbuf);
+
+        let mut buf = [0u8; 512];
+        let mut rb = ResponseBuilder::new(&mut buf, query).unwrap();
+        assert_eq!(rb.message_id(), 0x1234);
+
+        for i in 1..=2 {
+            let rdata = A::from(Ipv4Addr::new(192, 0, 2, i));
+            rb.add_answer("example.com", Class::IN, 300, &rdata)
+                .unwrap();
+        }
+        rb.set_authoritative_answer(true)
+            .set_response_code(RCode::NOERROR)
+            .unwrap();
+        let len = rb.finish().unwrap();
+
+        assert_eq!(u16::from_be_bytes([buf[0], buf[1]]) as usize, len - 2);
+
+        let mut mr = MessageReader::new(&buf[2..len]).unwrap();
+        let header = mr.header().unwrap();
+        assert_eq!(header.id, 0x1234);
+        assert!(header.is_response());
+        assert!(header.flags.authoritative_answer());
+        assert!(header.flags.recursion_desired());
+        assert!(!header.flags.recursion_available());
+        assert_eq!(header.flags.response_code(), RCode::NOERROR);
+        assert_eq!(header.qd_count, 1);
+        assert_eq!(header.an_count, 2);
+
+        let question = mr.the_question().unwrap();
+        assert_eq!(question.qname.as_str(), "example.com.");
+        assert_eq!(question.qtype, Type::A);
+        assert_eq!(question.qclass, Class::IN);
+
+        for i in 1..=2 {
+            let (section, record) = mr.next_record().unwrap().unwrap();
+            assert_eq!(section, RecordsSection::Answer);
+            assert_eq!(record.name.as_str(), "example.com.");
+            assert_eq!(record.ttl, 300);
+            assert_eq!(
+                record.rdata,
+                RecordData::A(A::from(Ipv4Addr::new(192, 0, 2, i)))
+            );
+        }
+        assert!(mr.next_record().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_from_reader() {
+        let mut qbuf = [0u8; 512];
+        let query = query(&mut qbuf);
+
+        let mut mr = MessageReader::new(query).unwrap();
+        assert!(mr.header().unwrap().is_query());
+        let question = mr.the_question().unwrap();
+        assert_eq!(question.qtype, Type::A);
+
+        let mut buf = [0u8; 512];
+        let mut rb = ResponseBuilder::from_reader(&mut buf, &mr).unwrap();
+        assert_eq!(rb.message_id(), 0x1234);
+        rb.add_answer(
+            question.qname.as_str(),
+            question.qclass,
+            300,
+            &A::from(Ipv4Addr::LOCALHOST),
+        )
+        .unwrap();
+        let len = rb.finish().unwrap();
+        assert_eq!(
+            buf[2 + HEADER_LENGTH..2 + query.len()],
+            query[HEADER_LENGTH..]
+        );
+
+        let mut mr = MessageReader::new(&buf[2..len]).unwrap();
+        let header = mr.header().unwrap();
+        assert!(header.is_response());
+        assert_eq!(header.qd_count, 1);
+        assert_eq!(header.an_count, 1);
+    }
+
+    #[test]
+    fn test_question_copied_verbatim() {
+        let mut qbuf = [0u8; 512];
+        let len = QueryWriter::new(&mut qbuf)
+            .write("ExAmPlE.CoM", Type::A, Class::IN, true, None)
+            .unwrap();
+        let query = &qbuf[2..len];
+
+        let mut buf = [0u8; 512];
+        let len = ResponseBuilder::new(&mut buf, query)
+            .unwrap()
+            .finish()
+            .unwrap();
+        assert_eq!(buf[2 + HEADER_LENGTH..len], query[HEADER_LENGTH..]);
+    }
+
+    #[test]
+    fn test_error_response() {
+        let mut qbuf = [0u8; 512];
+        let query = query(&mut qbuf);
+
+        let mut buf = [0u8; 512];
+        let mut rb = ResponseBuilder::new(&mut buf, query).unwrap();
+        rb.set_response_code(RCode::NXDOMAIN).unwrap();
+        assert!(matches!(
+            rb.set_response_code(RCode::BADVERS),
+            Err(Error::BadParam(_))
+        ));
+        let len = rb.finish().unwrap();
+
+        let mut mr = MessageReader::new(&buf[2..len]).unwrap();
+        let header = mr.header().unwrap();
+        assert_eq!(header.flags.response_code(), RCode::NXDOMAIN);
+        assert_eq!(header.an_count, 0);
+        assert!(!mr.has_records());
+    }
+
+    #[test]
+    fn test_not_a_query() {
+        let mut qbuf = [0u8; 512];
+        let query = query(&mut qbuf);
+
+        let mut buf = [0u8; 512];
+        let len = ResponseBuilder::new(&mut buf, query)
+            .unwrap()
+            .finish()
+            .unwrap();
+        let response = buf[2..len].to_vec();
+
+        let mut buf = [0u8; 512];
+        assert!(matches!(
+            ResponseBuilder::new(&mut buf, &response),
+            Err(Error::BadMessageType(MessageType::Response))
+        ));
+    }
+
+    #[test]
+    fn test_record_does_not_fit() {
+        let mut qbuf = [0u8; 512];
+        let query = query(&mut qbuf);
+
+        let mut buf = [0u8; 64];
+        let mut rb = ResponseBuilder::new(&mut buf, query).unwrap();
+        let rdata = Txt {
+            text: vec![b'x'; 100],
+        };
+        assert!(matches!(
+            rb.add_answer("example.com", Class::IN, 300, &rdata),
+            Err(Error::BufferTooShort(_))
+        ));
+        rb.set_truncated(true);
+        let len = rb.finish().unwrap();
+
+        let mut mr = MessageReader::new(&buf[2..len]).unwrap();
+        let header = mr.header().unwrap();
+        assert!(header.flags.truncated());
+        assert_eq!(header.an_count, 0);
+        mr.the_question().unwrap();
+        assert!(!mr.has_records());
+        assert_eq!(mr.trailing_bytes(), Some(0));
+    }
+
+    #[test]
+    fn test_message_too_long() {
+        let mut qbuf = [0u8; 512];
+        let query = query(&mut qbuf);
+
+        let mut buf = vec![0u8; 2 * DNS_MESSAGE_MAX_LENGTH];
+        let mut rb = ResponseBuilder::new(&mut buf, query).unwrap();
+        let rdata = Txt {
+            text: vec![b'x'; 40000],
+        };
+        rb.add_answer("example.com", Class::IN, 300, &rdata)
+            .unwrap();
+        assert!(matches!(
+            rb.add_answer("example.com", Class::IN, 300, &rdata),
+            Err(Error::MessageTooLong(_))
+        ));
+        let len = rb.finish().unwrap();
+        assert!(len - 2 <= DNS_MESSAGE_MAX_LENGTH);
+
+        let mut mr = MessageReader::new(&buf[2..len]).unwrap();
+        assert_eq!(mr.header().unwrap().an_count, 1);
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut qbuf = [0u8; 512];
+        let query = query(&mut qbuf);
+
+        let mut buf = [0u8; 512];
+        let mut rb = ResponseBuilder::new(&mut buf, query).unwrap();
+        rb.validate().unwrap();
+        rb.add_answer(
+            "example.com",
+            Class::IN,
+            300,
+            &A::from(Ipv4Addr::new(192, 0, 2, 1)),
+        )
+        .unwrap();
+        rb.validate().unwrap();
+
+        rb.header.an_count = 2;
+        assert!(matches!(rb.validate(), Err(Error::EndOfBuffer { .. })));
+
+        rb.header.an_count = 0;
+        assert!(matches!(rb.validate(), Err(Error::InternalError(_))));
+
+        rb.header.an_count = 1;
+        let len = rb.finish().unwrap();
+        let mut mr = MessageReader::new(&buf[2..len]).unwrap();
+        assert_eq!(mr.header().unwrap().an_count, 1);
+    }
+
+    #[test]
+    fn test_max_size() {
+        let mut qbuf = [0u8; 512];
+        let query = query(&mut qbuf);
+
+        // header (12), question (17) and four A records (27, 27, 32 and 27 bytes)
+        for (max_size, buf_len, an_count, truncated) in [
+            (None, 512, 4, false),
+            (Some(142), 512, 4, false),
+            (Some(141), 512, 3, true),
+            // the last record fits, but follows a record which doesn't
+            (Some(114), 512, 2, true),
+            (Some(512), 2 + 83, 2, true),
+        ] {
+            let mut buf = [0u8; 512];
+            let mut rb = ResponseBuilder::new(&mut buf[..buf_len], query).unwrap();
+            rb.set_max_size(max_size);
+            for i in 1..=4 {
+                let rdata = A::from(Ipv4Addr::new(192, 0, 2, i));
+                let name = if i == 3 {
+                    "host.example.com"
+                } else {
+                    "example.com"
+                };
+                rb.add_answer(name, Class::IN, 300, &rdata).unwrap();
+            }
+            let len = rb.finish().unwrap();
+
+            let mut mr = MessageReader::new(&buf[2..len]).unwrap();
+            let header = mr.header().unwrap();
+            assert_eq!(header.an_count, an_count, "{max_size:?}");
+            assert_eq!(header.flags.truncated(), truncated, "{max_size:?}");
+            mr.skip_questions().unwrap();
+            while mr.has_records() {
+                let marker = mr.record_marker().unwrap();
+                mr.skip_record_data(&marker).unwrap();
+            }
+            assert_eq!(mr.trailing_bytes(), Some(0));
+        }
+    }
+}
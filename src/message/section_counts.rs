@@ -0,0 +1,16 @@
+/// The number of entries in each section of a message, as declared in the message header.
+///
+/// See [`Header::counts`](crate::message::Header::counts).
+///
+/// [RFC 1035 section 4.1.1](https://www.rfc-editor.org/rfc/rfc1035.html#section-4.1.1)
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SectionCounts {
+    /// Number of entries in the question section (`QDCOUNT`).
+    pub questions: u16,
+    /// Number of resource records in the answer section (`ANCOUNT`).
+    pub answers: u16,
+    /// Number of name server resource records in the authority records section (`NSCOUNT`).
+    pub authorities: u16,
+    /// Number of resource records in the additional records section (`ARCOUNT`).
+    pub additionals: u16,
+}
@@ -0,0 +1,96 @@
+use crate::constants::{DOMAIN_NAME_LABEL_MAX_LENGTH, DOMAIN_NAME_MAX_LENGTH};
+
+/// Checks if a domain name is a valid host name.
+///
+/// A host name consists of labels made of ASCII letters, digits and hyphens (LDH), where
+/// a label neither starts nor ends with a hyphen ([RFC 952], [RFC 1123 section 2.1]).
+/// The trailing dot of a fully qualified name is optional. The root name and wildcard labels
+/// are not host names.
+///
+/// DNS itself permits other characters in labels, e.g. the underscore used by service names
+/// of `SRV` and `TXT` records, like `_sip._tcp.example.com`. When `allow_underscore` is `true`,
+/// underscores are accepted anywhere in a label as well.
+///
+/// # Examples
+///
+/// ```
+/// # use rsdns::names::is_valid_hostname;
+/// assert!(is_valid_hostname("www.example.com.", false));
+/// assert!(!is_valid_hostname("www.-example.com", false));
+/// assert!(!is_valid_hostname("_sip._tcp.example.com", false));
+/// assert!(is_valid_hostname("_sip._tcp.example.com", true));
+/// ```
+///
+/// [RFC 952]: https://www.rfc-editor.org/rfc/rfc952.html
+/// [RFC 1123 section 2.1]: https://www.rfc-editor.org/rfc/rfc1123.html#section-2.1
+pub fn is_valid_hostname(name: &str, allow_underscore: bool) -> bool {
+    let name = name.strip_suffix('.').unwrap_or(name);
+    // the wire form adds a length byte to the first label and the root label
+    if name.is_empty() || name.len() + 2 > DOMAIN_NAME_MAX_LENGTH {
+        return false;
+    }
+
+    name.split('.').all(|label| {
+        let bytes = label.as_bytes();
+        !bytes.is_empty()
+            && bytes.len() <= DOMAIN_NAME_LABEL_MAX_LENGTH
+            && bytes[0] != b'-'
+            && bytes[bytes.len() - 1] != b'-'
+            && bytes.iter().all(|b| {
+                b.is_ascii_alphanumeric() || *b == b'-' || (allow_underscore && *b == b'_')
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_hostname() {
+        for name in [
+            "example.com",
+            "example.com.",
+            "a",
+            "www.EXAMPLE.com",
+            "xn--bcher-kva.example",
+            "3com.com",
+            "a-b.c-d.e",
+            "1.2.0.192.in-addr.arpa.",
+        ] {
+            assert!(is_valid_hostname(name, false), "{name}");
+            assert!(is_valid_hostname(name, true), "{name}");
+        }
+
+        let long_label = "a".repeat(64);
+        let long_name = ["a".repeat(63).as_str(); 4].join(".");
+        for name in [
+            "",
+            ".",
+            "..",
+            "example..com",
+            ".example.com",
+            "-example.com",
+            "example-.com",
+            "exa mple.com",
+            "*.example.com",
+            "example.com..",
+            long_label.as_str(),
+            long_name.as_str(),
+        ] {
+            assert!(!is_valid_hostname(name, false), "{name}");
+            assert!(!is_valid_hostname(name, true), "{name}");
+        }
+        assert!(is_valid_hostname(&long_label[1..], false));
+        assert!(is_valid_hostname(&long_name[2..], false));
+
+        for name in [
+            "_sip._tcp.example.com",
+            "_dmarc.example.com",
+            "a_b.example.com",
+        ] {
+            assert!(!is_valid_hostname(name, false), "{name}");
+            assert!(is_valid_hostname(name, true), "{name}");
+        }
+    }
+}
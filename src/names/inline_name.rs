@@ -33,6 +33,11 @@ type ArrayType = ArrayString<DOMAIN_NAME_MAX_LENGTH>;
 /// Domain name is case insensitive. Hence, when compared, both sides are converted to
 /// ASCII lowercase. Use [`InlineName::as_str`] when exact match is required.
 ///
+/// The case of labels is preserved as is. In particular, a domain name parsed from a message
+/// keeps the case sent by the server, which is required to verify
+/// [0x20 encoded](https://datatracker.ietf.org/doc/html/draft-vixie-dnsext-dns0x20-00) queries.
+/// Use [`InlineName::to_canonical`] to obtain a lowercase copy of the domain name.
+///
 /// Specifications:
 ///
 /// - [RFC 1035 section 2.3.1](https://www.rfc-editor.org/rfc/rfc1035.html#section-2.3.1)
@@ -84,6 +89,72 @@ impl InlineName {
         dn
     }
 
+    /// Creates a domain name from its wire format encoding.
+    ///
+    /// `bytes` must hold a single uncompressed domain name, i.e. a sequence of labels
+    /// each preceded by its length, terminated by the zero length root label
+    /// ([RFC 1035 section 3.1](https://www.rfc-editor.org/rfc/rfc1035.html#section-3.1)).
+    /// Compression pointers are rejected, as there is no message they could point into.
+    ///
+    /// # Returns
+    ///
+    /// - [`Error::DomainNameBadLabelType`] - if a compression pointer or a label of unknown
+    ///   type is encountered
+    /// - [`Error::EndOfBuffer`] - if `bytes` ends before the root label
+    /// - [`Error::BadParam`] - if `bytes` has data following the root label
+    /// - any error returned by label and domain name validation
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rsdns::names::InlineName;
+    /// #
+    /// # fn foo() -> rsdns::Result<()> {
+    /// let dn = InlineName::from_wire(b"\x07example\x03com\x00")?;
+    /// assert_eq!(dn.as_str(), "example.com.");
+    ///
+    /// let dn = InlineName::from_wire(b"\x00")?;
+    /// assert_eq!(dn.as_str(), ".");
+    ///
+    /// // compression pointers are not allowed
+    /// assert!(InlineName::from_wire(b"\x03www\xC0\x0C").is_err());
+    /// # Ok(())
+    /// # }
+    /// # foo().unwrap();
+    /// ```
+    pub fn from_wire(bytes: &[u8]) -> Result<Self> {
+        let mut dn = Self::new();
+        let mut pos = 0;
+
+        loop {
+            let len = *bytes.get(pos).ok_or(Error::EndOfBuffer { offset: pos })?;
+            if len & 0b1100_0000 != 0 {
+                return Err(Error::DomainNameBadLabelType(len));
+            }
+            pos += 1;
+
+            if len == 0 {
+                break;
+            }
+
+            let end = pos + len as usize;
+            let label = bytes.get(pos..end).ok_or(Error::EndOfBuffer {
+                offset: bytes.len(),
+            })?;
+            dn.append_label_bytes(label)?;
+            pos = end;
+        }
+
+        if pos != bytes.len() {
+            return Err(Error::BadParam("trailing bytes after domain name"));
+        }
+
+        if dn.is_empty() {
+            dn.set_root();
+        }
+        Ok(dn)
+    }
+
     fn from(s: &str) -> Result<Self> {
         super::check_name(s)?;
 
@@ -220,34 +291,50 @@ impl InlineName {
     }
 
     pub(crate) fn append_label_bytes(&mut self, label: &[u8]) -> Result<()> {
-        super::check_label_bytes(label)?;
+        let offset = self.arr.len();
+        super::check_label_bytes_at(label, self.arr.as_bytes())?;
 
         // at this point the label is proven to be valid,
         // which means it is sound to convert it unchecked as a valid label is ASCII
         let label_as_str = unsafe { std::str::from_utf8_unchecked(label) };
 
         if self.arr.try_push_str(label_as_str).is_err() {
-            return Err(Error::DomainNameTooLong(
-                self.arr.len() + label_as_str.len() + 1,
-            ));
+            return Err(Error::DomainNameTooLong {
+                index: super::label_index(&self.arr.as_bytes()[..offset]),
+                offset,
+                len: offset + label_as_str.len() + 1,
+            });
         }
 
         if self.arr.try_push('.').is_err() {
-            return Err(Error::DomainNameTooLong(self.arr.len() + 1));
+            return Err(Error::DomainNameTooLong {
+                index: super::label_index(&self.arr.as_bytes()[..offset]),
+                offset,
+                len: self.arr.len() + 1,
+            });
         }
 
         Ok(())
     }
 
     pub(crate) fn append_label(&mut self, label: &str) -> Result<()> {
-        super::check_label(label)?;
+        let offset = self.arr.len();
+        super::check_label_at(label, &self.arr)?;
 
         if self.arr.try_push_str(label).is_err() {
-            return Err(Error::DomainNameTooLong(self.arr.len() + label.len() + 1));
+            return Err(Error::DomainNameTooLong {
+                index: super::label_index(&self.arr.as_bytes()[..offset]),
+                offset,
+                len: offset + label.len() + 1,
+            });
         }
 
         if self.arr.try_push('.').is_err() {
-            return Err(Error::DomainNameTooLong(self.arr.len() + 1));
+            return Err(Error::DomainNameTooLong {
+                index: super::label_index(&self.arr.as_bytes()[..offset]),
+                offset,
+                len: self.arr.len() + 1,
+            });
         }
 
         Ok(())
@@ -283,6 +370,129 @@ impl InlineName {
         self.arr.clear();
         self.arr.push('.');
     }
+
+    /// Returns the number of labels in the domain name.
+    ///
+    /// The root label is not counted. This is the value used in the `Labels` field of an
+    /// `RRSIG` record ([RFC 4034 section 3.1.3]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rsdns::names::InlineName;
+    /// # use std::str::FromStr;
+    /// #
+    /// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// #
+    /// assert_eq!(InlineName::root().label_count(), 0);
+    /// assert_eq!(InlineName::from_str("example.com")?.label_count(), 2);
+    /// assert_eq!(InlineName::from_str("*.example.com")?.label_count(), 3);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// # foo().unwrap();
+    /// ```
+    ///
+    /// [RFC 4034 section 3.1.3]: https://www.rfc-editor.org/rfc/rfc4034.html#section-3.1.3
+    #[inline]
+    pub fn label_count(&self) -> usize {
+        super::label_count(self.as_str())
+    }
+
+    /// Returns the wildcard name this domain name was expanded from.
+    ///
+    /// `labels` is the `Labels` field of an `RRSIG` record covering an RRset owned by this
+    /// domain name. If it is less than [`label_count`](Self::label_count), the RRset was
+    /// synthesized from a wildcard, and the wildcard name is `*.` followed by the rightmost
+    /// `labels` labels of this domain name ([RFC 4035 section 5.3.2]).
+    ///
+    /// Returns `None` if `labels` is not less than the number of labels in the domain name,
+    /// i.e. no wildcard expansion took place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rsdns::names::InlineName;
+    /// # use std::str::FromStr;
+    /// #
+    /// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// #
+    /// let dn = InlineName::from_str("a.b.example.com")?;
+    /// assert_eq!(dn.wildcard_name(2).unwrap(), "*.example.com.");
+    /// assert_eq!(dn.wildcard_name(0).unwrap(), "*.");
+    /// assert!(dn.wildcard_name(4).is_none());
+    /// #
+    /// # Ok(())
+    /// # }
+    /// # foo().unwrap();
+    /// ```
+    ///
+    /// [RFC 4035 section 5.3.2]: https://www.rfc-editor.org/rfc/rfc4035.html#section-5.3.2
+    pub fn wildcard_name(&self, labels: u8) -> Option<Self> {
+        let suffix = super::wildcard_suffix(self.as_str(), labels as usize)?;
+        let mut dn = Self::new();
+        // the suffix is shorter than the domain name by at least one label,
+        // so the wildcard name fits into the array
+        dn.arr.push_str("*.");
+        dn.arr.push_str(suffix);
+        Some(dn)
+    }
+
+    /// Returns a copy of the domain name converted to ASCII lowercase.
+    ///
+    /// This is the canonical form of a domain name used in DNSSEC ([RFC 4034 section 6.2]).
+    /// The domain name itself is not modified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rsdns::names::InlineName;
+    /// # use std::str::FromStr;
+    /// #
+    /// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// #
+    /// let dn = InlineName::from_str("ExAmPle.COM")?;
+    /// assert_eq!(dn.as_str(), "ExAmPle.COM.");
+    /// assert_eq!(dn.to_canonical().as_str(), "example.com.");
+    /// #
+    /// # Ok(())
+    /// # }
+    /// # foo().unwrap();
+    /// ```
+    ///
+    /// [RFC 4034 section 6.2]: https://www.rfc-editor.org/rfc/rfc4034.html#section-6.2
+    pub fn to_canonical(&self) -> Self {
+        let mut dn = self.clone();
+        dn.arr.make_ascii_lowercase();
+        dn
+    }
+
+    /// Returns the canonical wire format of the domain name.
+    ///
+    /// The domain name is encoded uncompressed, with all ASCII letters converted to lowercase
+    /// ([RFC 4034 section 6.2]). These are the exact bytes a domain name contributes to
+    /// `RRSIG` signature input, canonical RR ordering and `DS` digest computation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rsdns::names::InlineName;
+    /// # use std::str::FromStr;
+    /// #
+    /// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// #
+    /// let dn = InlineName::from_str("ExAmPle.COM")?;
+    /// assert_eq!(dn.to_canonical_wire(), b"\x07example\x03com\x00");
+    /// #
+    /// # Ok(())
+    /// # }
+    /// # foo().unwrap();
+    /// ```
+    ///
+    /// [RFC 4034 section 6.2]: https://www.rfc-editor.org/rfc/rfc4034.html#section-6.2
+    pub fn to_canonical_wire(&self) -> Vec<u8> {
+        super::canonical_wire(self.as_str())
+    }
 }
 
 impl TryFrom<&str> for InlineName {
@@ -447,6 +657,61 @@ mod tests {
     use super::*;
     use std::collections::HashSet;
 
+    #[test]
+    fn test_from_wire() {
+        let good: &[(&[u8], &str)] = &[
+            (b"\x00", "."),
+            (b"\x03com\x00", "com."),
+            (b"\x07ExAmPlE\x03com\x00", "ExAmPlE.com."),
+            (b"\x01*\x07example\x03com\x00", "*.example.com."),
+        ];
+        for (wire, name) in good {
+            let dn = InlineName::from_wire(wire).unwrap();
+            assert_eq!(dn.as_str(), *name);
+        }
+
+        assert!(matches!(
+            InlineName::from_wire(b""),
+            Err(Error::EndOfBuffer { offset: 0 })
+        ));
+        assert!(matches!(
+            InlineName::from_wire(b"\x03com"),
+            Err(Error::EndOfBuffer { offset: 4 })
+        ));
+        assert!(matches!(
+            InlineName::from_wire(b"\x05com\x00"),
+            Err(Error::EndOfBuffer { .. })
+        ));
+        assert!(matches!(
+            InlineName::from_wire(b"\x03www\xC0\x0C"),
+            Err(Error::DomainNameBadLabelType(0xC0))
+        ));
+        assert!(matches!(
+            InlineName::from_wire(b"\x03www\x40"),
+            Err(Error::DomainNameBadLabelType(0x40))
+        ));
+        assert!(matches!(
+            InlineName::from_wire(b"\x03com\x00\x00"),
+            Err(Error::BadParam(_))
+        ));
+        assert!(matches!(
+            InlineName::from_wire(b"\x03c m\x00"),
+            Err(Error::DomainNameLabelInvalidChar(..))
+        ));
+
+        let label = [b'a'; 63];
+        let mut wire = Vec::new();
+        for _ in 0..4 {
+            wire.push(63);
+            wire.extend_from_slice(&label);
+        }
+        wire.push(0);
+        assert!(matches!(
+            InlineName::from_wire(&wire),
+            Err(Error::DomainNameTooLong { .. })
+        ));
+    }
+
     #[test]
     fn test_new() {
         let dn = InlineName::new();
@@ -566,13 +831,15 @@ mod tests {
 
             let res = dn.append_label(&l_63);
             assert!(
-                matches!(res, Err(Error::DomainNameTooLong(s)) if s == dn.len() + l_63.len() + 1)
+                matches!(res, Err(Error::DomainNameTooLong { index: 4, offset, len }) if offset == dn.len() && len == dn.len() + l_63.len() + 1)
             );
         }
 
         // test total size == 255
         let res = dn.clone().append_label(&l_63);
-        assert!(matches!(res, Err(Error::DomainNameTooLong(s)) if s == dn.len() + l_63.len() + 1));
+        assert!(
+            matches!(res, Err(Error::DomainNameTooLong { index: 3, offset, len }) if offset == dn.len() && len == dn.len() + l_63.len() + 1)
+        );
 
         dn.append_label(&l_62).unwrap();
         assert_eq!(dn.len(), 255);
@@ -645,6 +912,32 @@ mod tests {
         assert_ne!(InlineName::root(), "");
     }
 
+    #[test]
+    fn test_to_canonical() {
+        let dn = InlineName::from("Sub.ExAmPle.COM").unwrap();
+        let canonical = dn.to_canonical();
+
+        assert_eq!(dn.as_str(), "Sub.ExAmPle.COM.");
+        assert_eq!(canonical.as_str(), "sub.example.com.");
+        assert_eq!(dn, canonical);
+        assert_eq!(InlineName::root().to_canonical().as_str(), ".");
+    }
+
+    #[test]
+    fn test_to_canonical_wire() {
+        // the owner name of the DNSKEY record in RFC 4034 section 5.4
+        let dn = InlineName::from("DSKEY.example.com").unwrap();
+        assert_eq!(dn.to_canonical_wire(), b"\x05dskey\x07example\x03com\x00");
+        assert_eq!(InlineName::root().to_canonical_wire(), b"\x00");
+    }
+
+    #[test]
+    fn test_read_preserves_case() {
+        let msg = b"\x03Sub\x07ExAmPle\x03COM\x00";
+        let dn: InlineName = Cursor::new(&msg[..]).read().unwrap();
+        assert_eq!(dn.as_str(), "Sub.ExAmPle.COM.");
+    }
+
     #[test]
     fn test_hash() {
         let dn = InlineName::from("example.com").unwrap();
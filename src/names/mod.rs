@@ -9,9 +9,13 @@ pub use name::*;
 mod dname;
 pub use dname::*;
 
+mod reverse;
+pub use reverse::*;
+
+mod hostname;
+pub use hostname::*;
+
 mod utils;
 pub(crate) use utils::*;
 
-cfg_any_client! {
-    mod writer;
-}
+mod writer;
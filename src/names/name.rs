@@ -32,6 +32,11 @@ use std::{
 /// Domain name is case insensitive. Hence, when compared, both sides are converted to
 /// ASCII lowercase. Use [`Name::as_str`] when exact match is required.
 ///
+/// The case of labels is preserved as is. In particular, a domain name parsed from a message
+/// keeps the case sent by the server, which is required to verify
+/// [0x20 encoded](https://datatracker.ietf.org/doc/html/draft-vixie-dnsext-dns0x20-00) queries.
+/// Use [`Name::to_canonical`] to obtain a lowercase copy of the domain name.
+///
 /// Specifications:
 ///
 /// - [RFC 1035 section 2.3.1](https://www.rfc-editor.org/rfc/rfc1035.html#section-2.3.1)
@@ -222,7 +227,7 @@ impl Name {
     }
 
     pub(crate) fn append_label_bytes(&mut self, label: &[u8]) -> Result<()> {
-        super::check_label_bytes(label)?;
+        super::check_label_bytes_at(label, self.name.as_bytes())?;
 
         // at this point the label is proven to be valid,
         // which means it is sound to convert it unchecked as a valid label is ASCII
@@ -230,7 +235,11 @@ impl Name {
 
         let new_len = self.name.len() + label_as_str.len() + 1;
         if new_len > DOMAIN_NAME_MAX_LENGTH {
-            return Err(Error::DomainNameTooLong(new_len));
+            return Err(Error::DomainNameTooLong {
+                index: super::label_index(self.name.as_bytes()),
+                offset: self.name.len(),
+                len: new_len,
+            });
         }
 
         self.name.push_str(label_as_str);
@@ -240,11 +249,15 @@ impl Name {
     }
 
     pub(crate) fn append_label(&mut self, label: &str) -> Result<()> {
-        super::check_label(label)?;
+        super::check_label_at(label, &self.name)?;
 
         let new_len = self.name.len() + label.len() + 1;
         if new_len > DOMAIN_NAME_MAX_LENGTH {
-            return Err(Error::DomainNameTooLong(new_len));
+            return Err(Error::DomainNameTooLong {
+                index: super::label_index(self.name.as_bytes()),
+                offset: self.name.len(),
+                len: new_len,
+            });
         }
 
         self.name.push_str(label);
@@ -283,6 +296,126 @@ impl Name {
         self.name.clear();
         self.name.push('.');
     }
+
+    /// Returns the number of labels in the domain name.
+    ///
+    /// The root label is not counted. This is the value used in the `Labels` field of an
+    /// `RRSIG` record ([RFC 4034 section 3.1.3]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rsdns::names::Name;
+    /// # use std::str::FromStr;
+    /// #
+    /// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// #
+    /// assert_eq!(Name::root().label_count(), 0);
+    /// assert_eq!(Name::from_str("example.com")?.label_count(), 2);
+    /// assert_eq!(Name::from_str("*.example.com")?.label_count(), 3);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// # foo().unwrap();
+    /// ```
+    ///
+    /// [RFC 4034 section 3.1.3]: https://www.rfc-editor.org/rfc/rfc4034.html#section-3.1.3
+    #[inline]
+    pub fn label_count(&self) -> usize {
+        super::label_count(self.as_str())
+    }
+
+    /// Returns the wildcard name this domain name was expanded from.
+    ///
+    /// `labels` is the `Labels` field of an `RRSIG` record covering an RRset owned by this
+    /// domain name. If it is less than [`label_count`](Self::label_count), the RRset was
+    /// synthesized from a wildcard, and the wildcard name is `*.` followed by the rightmost
+    /// `labels` labels of this domain name ([RFC 4035 section 5.3.2]).
+    ///
+    /// Returns `None` if `labels` is not less than the number of labels in the domain name,
+    /// i.e. no wildcard expansion took place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rsdns::names::Name;
+    /// # use std::str::FromStr;
+    /// #
+    /// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// #
+    /// let dn = Name::from_str("a.b.example.com")?;
+    /// assert_eq!(dn.wildcard_name(2).unwrap(), "*.example.com.");
+    /// assert_eq!(dn.wildcard_name(0).unwrap(), "*.");
+    /// assert!(dn.wildcard_name(4).is_none());
+    /// #
+    /// # Ok(())
+    /// # }
+    /// # foo().unwrap();
+    /// ```
+    ///
+    /// [RFC 4035 section 5.3.2]: https://www.rfc-editor.org/rfc/rfc4035.html#section-5.3.2
+    pub fn wildcard_name(&self, labels: u8) -> Option<Self> {
+        let suffix = super::wildcard_suffix(self.as_str(), labels as usize)?;
+        Some(Self {
+            name: format!("*.{suffix}"),
+        })
+    }
+
+    /// Returns a copy of the domain name converted to ASCII lowercase.
+    ///
+    /// This is the canonical form of a domain name used in DNSSEC ([RFC 4034 section 6.2]).
+    /// The domain name itself is not modified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rsdns::names::Name;
+    /// # use std::str::FromStr;
+    /// #
+    /// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// #
+    /// let dn = Name::from_str("ExAmPle.COM")?;
+    /// assert_eq!(dn.as_str(), "ExAmPle.COM.");
+    /// assert_eq!(dn.to_canonical().as_str(), "example.com.");
+    /// #
+    /// # Ok(())
+    /// # }
+    /// # foo().unwrap();
+    /// ```
+    ///
+    /// [RFC 4034 section 6.2]: https://www.rfc-editor.org/rfc/rfc4034.html#section-6.2
+    pub fn to_canonical(&self) -> Self {
+        let mut dn = self.clone();
+        dn.name.make_ascii_lowercase();
+        dn
+    }
+
+    /// Returns the canonical wire format of the domain name.
+    ///
+    /// The domain name is encoded uncompressed, with all ASCII letters converted to lowercase
+    /// ([RFC 4034 section 6.2]). These are the exact bytes a domain name contributes to
+    /// `RRSIG` signature input, canonical RR ordering and `DS` digest computation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rsdns::names::Name;
+    /// # use std::str::FromStr;
+    /// #
+    /// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// #
+    /// let dn = Name::from_str("ExAmPle.COM")?;
+    /// assert_eq!(dn.to_canonical_wire(), b"\x07example\x03com\x00");
+    /// #
+    /// # Ok(())
+    /// # }
+    /// # foo().unwrap();
+    /// ```
+    ///
+    /// [RFC 4034 section 6.2]: https://www.rfc-editor.org/rfc/rfc4034.html#section-6.2
+    pub fn to_canonical_wire(&self) -> Vec<u8> {
+        super::canonical_wire(self.as_str())
+    }
 }
 
 impl TryFrom<&str> for Name {
@@ -562,13 +695,15 @@ mod tests {
 
             let res = dn.append_label(&l_63);
             assert!(
-                matches!(res, Err(Error::DomainNameTooLong(s)) if s == dn.len() + l_63.len() + 1)
+                matches!(res, Err(Error::DomainNameTooLong { index: 4, offset, len }) if offset == dn.len() && len == dn.len() + l_63.len() + 1)
             );
         }
 
         // test total size == 255
         let res = dn.clone().append_label(&l_63);
-        assert!(matches!(res, Err(Error::DomainNameTooLong(s)) if s == dn.len() + l_63.len() + 1));
+        assert!(
+            matches!(res, Err(Error::DomainNameTooLong { index: 3, offset, len }) if offset == dn.len() && len == dn.len() + l_63.len() + 1)
+        );
 
         dn.append_label(&l_62).unwrap();
         assert_eq!(dn.len(), 255);
@@ -635,6 +770,32 @@ mod tests {
         assert_ne!(Name::root(), "");
     }
 
+    #[test]
+    fn test_to_canonical() {
+        let dn = Name::from("Sub.ExAmPle.COM").unwrap();
+        let canonical = dn.to_canonical();
+
+        assert_eq!(dn.as_str(), "Sub.ExAmPle.COM.");
+        assert_eq!(canonical.as_str(), "sub.example.com.");
+        assert_eq!(dn, canonical);
+        assert_eq!(Name::root().to_canonical().as_str(), ".");
+    }
+
+    #[test]
+    fn test_to_canonical_wire() {
+        // the owner name of the DNSKEY record in RFC 4034 section 5.4
+        let dn = Name::from("DSKEY.example.com").unwrap();
+        assert_eq!(dn.to_canonical_wire(), b"\x05dskey\x07example\x03com\x00");
+        assert_eq!(Name::root().to_canonical_wire(), b"\x00");
+    }
+
+    #[test]
+    fn test_read_preserves_case() {
+        let msg = b"\x03Sub\x07ExAmPle\x03COM\x00";
+        let dn: Name = Cursor::new(&msg[..]).read().unwrap();
+        assert_eq!(dn.as_str(), "Sub.ExAmPle.COM.");
+    }
+
     #[test]
     fn test_hash() {
         let dn = Name::from("example.com").unwrap();
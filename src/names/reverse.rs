@@ -0,0 +1,154 @@
+use crate::{names::InlineName, Error, Result};
+use std::{
+    fmt::Write,
+    net::{IpAddr, Ipv4Addr},
+    str::FromStr,
+};
+
+/// Returns the domain name used for reverse lookup of an IP address.
+///
+/// This is the owner name of the `PTR` record of the address: a name under `in-addr.arpa.`
+/// for IPv4 addresses, and a name under `ip6.arpa.` for IPv6 addresses.
+///
+/// # Examples
+///
+/// ```
+/// # use rsdns::names::reverse_ptr_name;
+/// # use std::net::{IpAddr, Ipv4Addr};
+/// #
+/// # fn foo() -> rsdns::Result<()> {
+/// #
+/// let name = reverse_ptr_name(IpAddr::from(Ipv4Addr::new(192, 0, 2, 1)))?;
+/// assert_eq!(name.as_str(), "1.2.0.192.in-addr.arpa.");
+/// #
+/// # Ok(())
+/// # }
+/// # foo().unwrap();
+/// ```
+///
+/// - [RFC 1035 section 3.5](https://www.rfc-editor.org/rfc/rfc1035.html#section-3.5)
+/// - [RFC 3596 section 2.5](https://www.rfc-editor.org/rfc/rfc3596.html#section-2.5)
+pub fn reverse_ptr_name(ip: IpAddr) -> Result<InlineName> {
+    let mut name = String::with_capacity(73);
+    match ip {
+        IpAddr::V4(v4) => {
+            for b in v4.octets().iter().rev() {
+                let _ = write!(name, "{b}.");
+            }
+            name.push_str("in-addr.arpa.");
+        }
+        IpAddr::V6(v6) => {
+            for b in v6.octets().iter().rev() {
+                let _ = write!(name, "{:x}.{:x}.", b & 0x0F, b >> 4);
+            }
+            name.push_str("ip6.arpa.");
+        }
+    }
+    InlineName::from_str(&name)
+}
+
+/// Returns the reverse lookup zone name of an IPv4 network.
+///
+/// The network is given by an address and a prefix length. Host bits of the address are
+/// ignored.
+///
+/// If the prefix length is a multiple of `8`, the zone name consists of the network octets
+/// under `in-addr.arpa.`, e.g. `2.0.192.in-addr.arpa.` for `192.0.2.0/24`.
+///
+/// Prefixes longer than `24` bits are delegated in a classless manner ([RFC 2317]).
+/// The zone name of such a network is the first address of the network and the prefix length
+/// joined with a `-`, under the reverse name of the enclosing `/24` network,
+/// e.g. `64-26.2.0.192.in-addr.arpa.` for `192.0.2.64/26`. RFC 2317 uses a `/` as the
+/// separator in its examples, but a `/` is not a valid character of a host name label.
+///
+/// # Returns
+///
+/// - [`Error::BadParam`] - if the prefix length is greater than `32`, or it is less than `24`
+///   and not a multiple of `8`
+///
+/// # Examples
+///
+/// ```
+/// # use rsdns::names::reverse_zone_name;
+/// # use std::net::Ipv4Addr;
+/// #
+/// # fn foo() -> rsdns::Result<()> {
+/// #
+/// let addr = Ipv4Addr::new(192, 0, 2, 77);
+/// assert_eq!(reverse_zone_name(addr, 24)?.as_str(), "2.0.192.in-addr.arpa.");
+/// assert_eq!(reverse_zone_name(addr, 26)?.as_str(), "64-26.2.0.192.in-addr.arpa.");
+/// #
+/// # Ok(())
+/// # }
+/// # foo().unwrap();
+/// ```
+///
+/// [RFC 2317]: https://www.rfc-editor.org/rfc/rfc2317.html
+pub fn reverse_zone_name(addr: Ipv4Addr, prefix_len: u8) -> Result<InlineName> {
+    if prefix_len > 32 {
+        return Err(Error::BadParam("prefix length exceeds 32"));
+    }
+    if prefix_len < 24 && prefix_len & 0x7 != 0 {
+        return Err(Error::BadParam(
+            "prefix length is not a multiple of 8 and is less than 24",
+        ));
+    }
+
+    let octets = addr.octets();
+    let mut name = String::with_capacity(32);
+    if prefix_len > 24 && prefix_len < 32 {
+        let mask = u8::MAX << (32 - prefix_len);
+        let _ = write!(name, "{}-{prefix_len}.", octets[3] & mask);
+    }
+    for b in octets[..(prefix_len / 8) as usize].iter().rev() {
+        let _ = write!(name, "{b}.");
+    }
+    name.push_str("in-addr.arpa.");
+    InlineName::from_str(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv6Addr;
+
+    #[test]
+    fn test_reverse_ptr_name() {
+        assert_eq!(
+            reverse_ptr_name(IpAddr::from(Ipv4Addr::new(192, 0, 2, 1))).unwrap(),
+            "1.2.0.192.in-addr.arpa."
+        );
+        assert_eq!(
+            reverse_ptr_name(IpAddr::from(
+                Ipv6Addr::from_str("2001:db8::567:89ab").unwrap()
+            ))
+            .unwrap(),
+            "b.a.9.8.7.6.5.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.8.b.d.0.1.0.0.2.ip6.arpa."
+        );
+    }
+
+    #[test]
+    fn test_reverse_zone_name() {
+        let addr = Ipv4Addr::new(192, 0, 2, 201);
+        for (prefix_len, expected) in [
+            (0, "in-addr.arpa."),
+            (8, "192.in-addr.arpa."),
+            (16, "0.192.in-addr.arpa."),
+            (24, "2.0.192.in-addr.arpa."),
+            (25, "128-25.2.0.192.in-addr.arpa."),
+            (26, "192-26.2.0.192.in-addr.arpa."),
+            (30, "200-30.2.0.192.in-addr.arpa."),
+            (31, "200-31.2.0.192.in-addr.arpa."),
+            (32, "201.2.0.192.in-addr.arpa."),
+        ] {
+            assert_eq!(reverse_zone_name(addr, prefix_len).unwrap(), expected);
+        }
+
+        for prefix_len in [7, 20, 23, 33] {
+            assert!(matches!(
+                reverse_zone_name(addr, prefix_len),
+                Err(Error::BadParam(_))
+            ));
+        }
+    }
+}
@@ -3,15 +3,32 @@ use crate::{
     Error, Result,
 };
 
+#[inline(always)]
 pub fn check_label_bytes(label: &[u8]) -> Result<()> {
+    check_label_bytes_at(label, b"")
+}
+
+/// Checks a label following `prefix`, the preceding labels of a domain name.
+///
+/// The index and the offset of the label are reported in the error, if the label is too long.
+pub fn check_label_bytes_at(label: &[u8], prefix: &[u8]) -> Result<()> {
     if label.is_empty() {
         return Err(Error::DomainNameLabelIsEmpty);
     }
 
+    // wildcard label - RFC 4592 section 2.1.1
+    if label == b"*" {
+        return Ok(());
+    }
+
     let len = label.len();
 
     if len > DOMAIN_NAME_LABEL_MAX_LENGTH {
-        return Err(Error::DomainNameLabelTooLong(len));
+        return Err(Error::DomainNameLabelTooLong {
+            index: label_index(prefix),
+            offset: prefix.len(),
+            len,
+        });
     }
 
     for b in label.iter() {
@@ -47,8 +64,8 @@ pub fn check_label_bytes(label: &[u8]) -> Result<()> {
 }
 
 #[inline(always)]
-pub fn check_label(label: &str) -> Result<()> {
-    check_label_bytes(label.as_bytes())
+pub fn check_label_at(label: &str, prefix: &str) -> Result<()> {
+    check_label_bytes_at(label.as_bytes(), prefix.as_bytes())
 }
 
 pub fn check_name_bytes(name: &[u8]) -> Result<()> {
@@ -69,7 +86,7 @@ pub fn check_name_bytes(name: &[u8]) -> Result<()> {
         let byte = unsafe { *name.get_unchecked(j) };
         if byte == b'.' {
             let label = unsafe { name.get_unchecked(i..j) };
-            check_label_bytes(label)?;
+            check_label_bytes_at(label, unsafe { name.get_unchecked(..i) })?;
             i = j + 1;
             domain_start = Some(i);
         }
@@ -78,7 +95,7 @@ pub fn check_name_bytes(name: &[u8]) -> Result<()> {
     match domain_start {
         Some(ds) if len - ds > 0 => {
             let label = unsafe { name.get_unchecked(ds..len) };
-            check_label_bytes(label)?;
+            check_label_bytes_at(label, unsafe { name.get_unchecked(..ds) })?;
         }
         None => check_label_bytes(name)?,
         _ => (),
@@ -89,17 +106,107 @@ pub fn check_name_bytes(name: &[u8]) -> Result<()> {
     let full_length = if last_byte == b'.' { len + 1 } else { len + 2 };
 
     if full_length > DOMAIN_NAME_MAX_LENGTH {
-        return Err(Error::DomainNameTooLong(full_length));
+        return Err(name_too_long(name, full_length));
     }
 
     Ok(())
 }
 
+/// Returns [`Error::DomainNameTooLong`] for a domain name of wire length `len`.
+///
+/// The error points at the first label of the domain name, which doesn't fit in the maximal
+/// domain name length. The name is expected to be longer than the maximal length in wire format.
+pub fn name_too_long(name: &[u8], len: usize) -> Error {
+    // a label ending at offset `j` ends the name at wire length `j + 2`,
+    // so the first label which doesn't fit contains the byte at offset `max - 2`
+    let last = (DOMAIN_NAME_MAX_LENGTH - 2).min(name.len() - 1);
+    let offset = match name[..=last].iter().rposition(|b| *b == b'.') {
+        Some(p) => p + 1,
+        None => 0,
+    };
+    Error::DomainNameTooLong {
+        index: label_index(&name[..offset]),
+        offset,
+        len,
+    }
+}
+
+/// Returns the index of the label following `prefix`, the preceding labels of a domain name.
+pub fn label_index(prefix: &[u8]) -> u32 {
+    match prefix {
+        b"" | b"." => 0,
+        _ => prefix.iter().filter(|b| **b == b'.').count() as u32,
+    }
+}
+
 #[inline(always)]
 pub fn check_name(name: &str) -> Result<()> {
     check_name_bytes(name.as_bytes())
 }
 
+/// Returns the length of a valid domain name in wire format (uncompressed).
+pub fn wire_length(name: &str) -> usize {
+    match name {
+        "." => 1,
+        _ if name.ends_with('.') => name.len() + 1,
+        _ => name.len() + 2,
+    }
+}
+
+/// Returns the canonical wire format of a valid domain name.
+///
+/// The name is encoded uncompressed, with ASCII letters converted to lowercase.
+pub fn canonical_wire(name: &str) -> Vec<u8> {
+    let mut wire = Vec::with_capacity(wire_length(name));
+    for label in name.split('.').filter(|l| !l.is_empty()) {
+        wire.push(label.len() as u8);
+        wire.extend(label.bytes().map(|b| b.to_ascii_lowercase()));
+    }
+    wire.push(0);
+    wire
+}
+
+/// Returns the number of labels in a domain name in canonical form, excluding the root label.
+pub fn label_count(name: &str) -> usize {
+    match name {
+        "" | "." => 0,
+        _ => name.bytes().filter(|b| *b == b'.').count(),
+    }
+}
+
+/// Returns the suffix of a domain name (in canonical form) the wildcard name is constructed from.
+///
+/// The suffix consists of the rightmost `labels` labels of the name.
+/// Returns `None` if `labels` is not less than the number of labels in the name.
+pub fn wildcard_suffix(name: &str, labels: usize) -> Option<&str> {
+    let count = label_count(name);
+    if labels >= count {
+        return None;
+    }
+
+    let mut pos = 0;
+    for _ in 0..count - labels {
+        // name is in canonical form, so every label is terminated with a '.'
+        pos += name[pos..].find('.')? + 1;
+    }
+
+    Some(&name[pos..])
+}
+
+/// Checks if a domain name (in canonical form) is equal to, or a subdomain of, `zone`.
+///
+/// The comparison is case-insensitive.
+pub fn is_subdomain_of(name: &str, zone: &str) -> bool {
+    if zone == "." {
+        return true;
+    }
+    let Some(pos) = name.len().checked_sub(zone.len()) else {
+        return false;
+    };
+    let name = name.as_bytes();
+    (pos == 0 || name[pos - 1] == b'.') && name[pos..].eq_ignore_ascii_case(zone.as_bytes())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -121,7 +228,7 @@ mod tests {
                 )) if v == *c
             ));
 
-            let res = check_label(std::str::from_utf8(m).unwrap());
+            let res = check_label_at(std::str::from_utf8(m).unwrap(), "");
             assert!(matches!(
                 res,
                 Err(Error::DomainNameLabelInvalidChar(
@@ -143,7 +250,7 @@ mod tests {
                 )) if v == *c
             ));
 
-            let res = check_label(std::str::from_utf8(m).unwrap());
+            let res = check_label_at(std::str::from_utf8(m).unwrap(), "");
             assert!(matches!(
                 res,
                 Err(Error::DomainNameLabelInvalidChar(
@@ -153,7 +260,12 @@ mod tests {
             ));
         }
 
-        let invalid_char: &[(&[u8], u8)] = &[(b"la.el", b'.'), (b"\tabel", b'\t')];
+        let invalid_char: &[(&[u8], u8)] = &[
+            (b"la.el", b'.'),
+            (b"\tabel", b'\t'),
+            (b"*abel", b'*'),
+            (b"**", b'*'),
+        ];
         for (ic, c) in invalid_char {
             let res = check_label_bytes(ic);
             assert!(matches!(
@@ -161,7 +273,7 @@ mod tests {
                 Err(Error::DomainNameLabelInvalidChar("domain name label invalid character", v)) if v == *c
             ));
 
-            let res = check_label(std::str::from_utf8(ic).unwrap());
+            let res = check_label_at(std::str::from_utf8(ic).unwrap(), "");
             assert!(matches!(
                 res,
                 Err(Error::DomainNameLabelInvalidChar("domain name label invalid character", v)) if v == *c
@@ -172,17 +284,21 @@ mod tests {
         let too_large = &[l_64.as_bytes()];
         for tl in too_large {
             let res = check_label_bytes(tl);
-            assert!(matches!(res, Err(Error::DomainNameLabelTooLong(l)) if l == tl.len()));
-
-            let res = check_label(std::str::from_utf8(tl).unwrap());
-            assert!(matches!(res, Err(Error::DomainNameLabelTooLong(l)) if l == tl.len()));
+            assert!(
+                matches!(res, Err(Error::DomainNameLabelTooLong { index: 0, offset: 0, len }) if len == tl.len())
+            );
+
+            let res = check_label_at(std::str::from_utf8(tl).unwrap(), "");
+            assert!(
+                matches!(res, Err(Error::DomainNameLabelTooLong { index: 0, offset: 0, len }) if len == tl.len())
+            );
         }
 
         let l_63 = "a".repeat(63);
-        let good: &[&[u8]] = &[b"label", b"labe1", b"1abel", l_63.as_bytes()];
+        let good: &[&[u8]] = &[b"label", b"labe1", b"1abel", b"*", l_63.as_bytes()];
         for g in good {
             assert!(check_label_bytes(g).is_ok());
-            assert!(check_label(std::str::from_utf8(g).unwrap()).is_ok());
+            assert!(check_label_at(std::str::from_utf8(g).unwrap(), "").is_ok());
         }
     }
 
@@ -199,6 +315,7 @@ mod tests {
             b"su--b.exAmp1e.com",
             b"_example.com",
             b"example_.com",
+            b"*.example.com",
         ];
         for g in good {
             assert!(check_name_bytes(g).is_ok());
@@ -282,7 +399,7 @@ mod tests {
 
         let l_63 = "a".repeat(63);
         let l_61 = "b".repeat(61);
-        let dn_253 = [l_63.clone(), l_63.clone(), l_63].join(".") + "." + l_61.as_str();
+        let dn_253 = [l_63.clone(), l_63.clone(), l_63.clone()].join(".") + "." + l_61.as_str();
         let dn_254 = dn_253.clone() + "b";
 
         assert!(check_name_bytes(dn_253.as_bytes()).is_ok());
@@ -293,10 +410,111 @@ mod tests {
         let too_long = &[dn_254.as_str()];
         for tl in too_long {
             let res = check_name(tl);
-            assert!(matches!(res, Err(Error::DomainNameTooLong(s)) if s == tl.len() + 2));
+            assert!(
+                matches!(res, Err(Error::DomainNameTooLong { index: 3, offset: 192, len }) if len == tl.len() + 2)
+            );
 
             let res = check_name_bytes(tl.as_bytes());
-            assert!(matches!(res, Err(Error::DomainNameTooLong(s)) if s == tl.len() + 2));
+            assert!(
+                matches!(res, Err(Error::DomainNameTooLong { index: 3, offset: 192, len }) if len == tl.len() + 2)
+            );
         }
+
+        // the fourth label doesn't fit
+        let dn = [l_63.as_str(); 4].join(".") + ".";
+        assert!(matches!(
+            check_name(&dn),
+            Err(Error::DomainNameTooLong {
+                index: 3,
+                offset: 192,
+                len: 257
+            })
+        ));
+
+        // the fifth label doesn't fit
+        let dn_257 = [l_63.as_str(), l_63.as_str(), l_63.as_str(), &l_61, "a"].join(".");
+        assert!(matches!(
+            check_name(&dn_257),
+            Err(Error::DomainNameTooLong {
+                index: 4,
+                offset: 254,
+                len: 257
+            })
+        ));
+
+        let l_64 = "a".repeat(64);
+        let dn = format!("{l_63}.{l_64}.com");
+        assert!(matches!(
+            check_name(&dn),
+            Err(Error::DomainNameLabelTooLong {
+                index: 1,
+                offset: 64,
+                len: 64
+            })
+        ));
+    }
+
+    #[test]
+    fn test_wire_length() {
+        assert_eq!(wire_length("."), 1);
+        assert_eq!(wire_length("com."), 5);
+        assert_eq!(wire_length("com"), 5);
+        assert_eq!(wire_length("example.com."), 13);
+    }
+
+    #[test]
+    fn test_canonical_wire() {
+        assert_eq!(canonical_wire(""), b"\x00");
+        assert_eq!(canonical_wire("."), b"\x00");
+        assert_eq!(canonical_wire("Com"), b"\x03com\x00");
+        assert_eq!(
+            canonical_wire("a.ExAmPle.COM."),
+            b"\x01a\x07example\x03com\x00"
+        );
+        for name in ["com.", "example.com", "a.b.example.com."] {
+            assert_eq!(canonical_wire(name).len(), wire_length(name));
+        }
+    }
+
+    #[test]
+    fn test_label_count() {
+        assert_eq!(label_count(""), 0);
+        assert_eq!(label_count("."), 0);
+        assert_eq!(label_count("com."), 1);
+        assert_eq!(label_count("example.com."), 2);
+        assert_eq!(label_count("*.example.com."), 3);
+    }
+
+    #[test]
+    fn test_wildcard_suffix() {
+        let name = "a.b.example.com.";
+        assert_eq!(wildcard_suffix(name, 0), Some(""));
+        assert_eq!(wildcard_suffix(name, 1), Some("com."));
+        assert_eq!(wildcard_suffix(name, 2), Some("example.com."));
+        assert_eq!(wildcard_suffix(name, 3), Some("b.example.com."));
+        assert_eq!(wildcard_suffix(name, 4), None);
+        assert_eq!(wildcard_suffix(name, 5), None);
+        assert_eq!(wildcard_suffix(".", 0), None);
+    }
+
+    #[test]
+    fn test_label_index() {
+        assert_eq!(label_index(b""), 0);
+        assert_eq!(label_index(b"."), 0);
+        assert_eq!(label_index(b"com."), 1);
+        assert_eq!(label_index(b"www.example."), 2);
+    }
+
+    #[test]
+    fn test_is_subdomain_of() {
+        assert!(is_subdomain_of("example.com.", "."));
+        assert!(is_subdomain_of(".", "."));
+        assert!(is_subdomain_of("example.com.", "example.com."));
+        assert!(is_subdomain_of("www.Example.COM.", "example.com."));
+        assert!(is_subdomain_of("a.b.example.com.", "com."));
+        assert!(!is_subdomain_of("example.com.", "www.example.com."));
+        assert!(!is_subdomain_of("badexample.com.", "example.com."));
+        assert!(!is_subdomain_of("example.org.", "example.com."));
+        assert!(!is_subdomain_of(".", "com."));
     }
 }
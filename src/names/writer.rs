@@ -2,8 +2,8 @@ use crate::{bytes::WCursor, constants::DOMAIN_NAME_MAX_LENGTH, Error, Result};
 
 impl WCursor<'_> {
     #[inline]
-    fn write_label(&mut self, label: &[u8]) -> Result<()> {
-        super::check_label_bytes(label)?;
+    fn write_label(&mut self, label: &[u8], prefix: &[u8]) -> Result<()> {
+        super::check_label_bytes_at(label, prefix)?;
         if self.len() > label.len() {
             unsafe {
                 self.u8_unchecked(label.len() as u8);
@@ -39,7 +39,7 @@ impl WCursor<'_> {
             let byte = unsafe { *name.get_unchecked(j) };
             if byte == b'.' {
                 let label = unsafe { name.get_unchecked(i..j) };
-                self.write_label(label)?;
+                self.write_label(label, unsafe { name.get_unchecked(..i) })?;
                 i = j + 1;
                 domain_start = Some(i);
             }
@@ -48,9 +48,9 @@ impl WCursor<'_> {
         match domain_start {
             Some(ds) if len - ds > 0 => {
                 let label = unsafe { name.get_unchecked(ds..len) };
-                self.write_label(label)?;
+                self.write_label(label, unsafe { name.get_unchecked(..ds) })?;
             }
-            None => self.write_label(name)?,
+            None => self.write_label(name, b"")?,
             _ => {}
         };
 
@@ -58,7 +58,7 @@ impl WCursor<'_> {
 
         let length = self.pos() - start;
         if length > DOMAIN_NAME_MAX_LENGTH {
-            return Err(Error::DomainNameTooLong(length));
+            return Err(super::name_too_long(name, length));
         }
 
         Ok(length)
@@ -114,7 +114,7 @@ mod tests {
             let mut wcursor = WCursor::new(&mut arr[..]);
             assert!(matches!(
                 wcursor.write_domain_name(&long_label),
-                Err(Error::DomainNameTooLong(s)) if s == long_label.len() + 2
+                Err(Error::DomainNameTooLong { index: 3, offset: 192, len }) if len == long_label.len() + 2
             ));
         }
 
@@ -122,7 +122,7 @@ mod tests {
             let mut wcursor = WCursor::new(&mut arr[..]);
             assert!(matches!(
                 wcursor.write_domain_name(&long_label[..long_label.len() - 1]),
-                Err(Error::DomainNameTooLong(s)) if s == long_label.len() + 1
+                Err(Error::DomainNameTooLong { index: 3, offset: 192, len }) if len == long_label.len() + 1
             ));
         }
 
@@ -130,6 +130,21 @@ impl Class {
         Self(c)
     }
 
+    /// Creates a Class from its numerical value.
+    ///
+    /// This is equivalent to `Class::from(c)`, but may be used in const context.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use rsdns::records::Class;
+    /// const CLASSES: [Class; 2] = [Class::new_const(1), Class::new_const(3)];
+    /// assert_eq!(CLASSES, [Class::IN, Class::CH]);
+    /// ```
+    #[inline]
+    pub const fn new_const(c: u16) -> Self {
+        Self::new(c)
+    }
+
     /// Returns the name of the record class in constant time.
     ///
     /// If the value doesn't have a defined named constant the string
@@ -462,4 +477,29 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Class::IN.to_string(), "IN");
+        assert_eq!(Class::ANY.to_string(), "ANY");
+        assert_eq!(Class::from(0).to_string(), "CLASS0");
+        assert_eq!(Class::from(u16::MAX).to_string(), "CLASS65535");
+        assert_eq!(format!("{:>4}", Class::CH), "  CH");
+
+        for i in 0..=u16::MAX {
+            let class = Class::from(i);
+            assert_eq!(Class::from_str(&class.to_string()).unwrap(), class);
+        }
+    }
+
+    #[test]
+    fn test_cmp_u16() {
+        assert_eq!(Class::IN, 1);
+        assert_eq!(1, Class::IN);
+        assert_ne!(Class::IN, 2);
+        assert!(Class::IN < 2);
+        assert!(2 > Class::IN);
+        assert!(Class::ANY > 254);
+        assert!(256 > Class::ANY);
+    }
 }
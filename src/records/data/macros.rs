@@ -18,6 +18,16 @@ macro_rules! rr_data {
             fn from_cursor(c: &mut Cursor<'_>, rdlen: usize) -> crate::Result<Self> {
                 c.read_rr_data(rdlen)
             }
+
+            #[inline]
+            fn rdata_len(&self) -> usize {
+                <crate::bytes::WCursor<'_> as crate::bytes::RrDataWriter<$RR>>::rr_data_len(self)
+            }
+
+            #[inline]
+            fn write_rdata(&self, c: &mut crate::bytes::WCursor<'_>) -> crate::Result<()> {
+                crate::bytes::RrDataWriter::<$RR>::write_rr_data(c, self)
+            }
         }
 
         impl crate::records::data::RData for $RR {
@@ -37,6 +47,12 @@ macro_rules! rr_dn_data {
 
         rr_data!($RR, $RT);
 
+        impl std::fmt::Display for $RR {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                self.$DN.fmt(f)
+            }
+        }
+
         impl crate::bytes::RrDataReader<$RR> for crate::bytes::Cursor<'_> {
             fn read_rr_data(&mut self, rd_len: usize) -> crate::Result<$RR> {
                 use crate::bytes::Reader;
@@ -46,5 +62,16 @@ macro_rules! rr_dn_data {
                 rr
             }
         }
+
+        impl crate::bytes::RrDataWriter<$RR> for crate::bytes::WCursor<'_> {
+            fn rr_data_len(rd: &$RR) -> usize {
+                crate::names::wire_length(rd.$DN.as_str())
+            }
+
+            fn write_rr_data(&mut self, rd: &$RR) -> crate::Result<()> {
+                self.write_domain_name(rd.$DN.as_str())?;
+                Ok(())
+            }
+        }
     };
 }
@@ -6,13 +6,49 @@ mod macros;
 mod rfc1035;
 pub use rfc1035::*;
 
+mod rfc2930;
+pub use rfc2930::*;
+
 mod rfc3596;
 pub use rfc3596::*;
 
+mod rfc3597;
+pub use rfc3597::*;
+
+mod rfc7208;
+pub use rfc7208::*;
+
 mod rdata;
 pub use rdata::*;
 
+use crate::{
+    bytes::{Cursor, WCursor},
+    records::Type,
+    Error, Result,
+};
+
 /// Enumerates supported resource records' data.
+///
+/// # Ordering
+///
+/// [`RecordData`] implements a structural order, which is deterministic and suitable for
+/// sorting and deduplication of record data:
+///
+/// - data of different types is ordered by the numeric value of the record type, as the variants
+///   are declared in that order. [`Unknown`] data follows the data of all other types, and is
+///   ordered by record type first
+/// - data of the same type is ordered field by field, in the order the fields are written in the
+///   wire format. Integers and addresses are compared numerically, byte strings
+///   lexicographically, and domain names case-insensitively, as in [`Name`]'s [`Ord`].
+///
+/// This is not the canonical RR ordering of DNSSEC
+/// ([RFC 4034 section 6.3](https://www.rfc-editor.org/rfc/rfc4034.html#section-6.3)), which
+/// compares the canonical wire format of the data as a sequence of octets. The two orders agree
+/// for `A`, `AAAA` and `NULL` data, but may differ for other types. For example, a domain name or
+/// a *character-string* is preceded by its length in the wire format, so `HINFO` data with `cpu`
+/// equal to `"b"` precedes `"aa"` in the canonical order, and follows it in the structural order.
+///
+/// [`Name`]: crate::names::Name
 #[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub enum RecordData {
     /// A host address (IPv4).
@@ -49,4 +85,409 @@ pub enum RecordData {
     Txt(rfc1035::Txt),
     /// A host address (IPv6)
     Aaaa(rfc3596::Aaaa),
+    /// Sender Policy Framework.
+    Spf(rfc7208::Spf),
+    /// A transaction key.
+    Tkey(rfc2930::Tkey),
+    /// Data of a record type not supported by *rsdns*.
+    Unknown(rfc3597::Unknown),
+}
+
+macro_rules! record_data_types {
+    ($($V:ident),+) => {
+        impl RecordData {
+            /// Returns the record type of the data.
+            ///
+            /// This is the [`RData::RTYPE`] of the contained record data.
+            pub const fn rtype(&self) -> Type {
+                match self {
+                    $(Self::$V(_) => <$V as RData>::RTYPE,)+
+                    Self::Unknown(d) => d.rtype,
+                }
+            }
+
+            /// Checks if `rtype` has a corresponding variant in [`RecordData`].
+            ///
+            /// Records of types for which this method returns `false` are not parsed into
+            /// [`RecordData`], and can be read only as raw bytes
+            /// (see [`MessageReader::record_data_bytes`]).
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// use rsdns::records::{data::RecordData, Type};
+            ///
+            /// assert!(RecordData::is_supported_type(Type::AAAA));
+            /// assert!(!RecordData::is_supported_type(Type::OPT));
+            /// ```
+            ///
+            /// [`MessageReader::record_data_bytes`]:
+            ///     crate::message::reader::MessageReader::record_data_bytes
+            pub fn is_supported_type(rtype: Type) -> bool {
+                $(rtype == <$V as RData>::RTYPE)||+
+            }
+
+            /// Returns the length (in bytes) of the record data in wire format.
+            ///
+            /// See [`RData::encoded_len`] for more information.
+            pub fn encoded_len(&self) -> usize {
+                match self {
+                    $(Self::$V(d) => d.encoded_len(),)+
+                    Self::Unknown(d) => d.data.len(),
+                }
+            }
+
+            /// Writes the record data in wire format into `buf`.
+            ///
+            /// See [`RData::encode_to`] for more information.
+            pub fn encode_to(&self, buf: &mut [u8]) -> Result<usize> {
+                match self {
+                    $(Self::$V(d) => d.encode_to(buf),)+
+                    Self::Unknown(d) => {
+                        let mut c = WCursor::new(buf);
+                        c.bytes(&d.data)?;
+                        Ok(c.pos())
+                    }
+                }
+            }
+
+            /// Reads record data of type `rtype`.
+            ///
+            /// Returns [`Error::UnexpectedType`] if `rtype` is not supported.
+            pub(crate) fn from_cursor(rtype: Type, c: &mut Cursor<'_>, rdlen: usize) -> Result<Self> {
+                use private::RDataBase;
+                $(
+                    if rtype == <$V as RData>::RTYPE {
+                        return $V::from_cursor(c, rdlen).map(Self::$V);
+                    }
+                )+
+                Err(Error::UnexpectedType(rtype))
+            }
+        }
+
+        impl std::fmt::Display for RecordData {
+            /// Formats the record data in presentation format, as in a zone file.
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    $(Self::$V(d) => d.fmt(f),)+
+                    Self::Unknown(d) => d.fmt(f),
+                }
+            }
+        }
+    };
+}
+
+record_data_types!(
+    A, Ns, Md, Mf, Cname, Soa, Mb, Mg, Mr, Null, Wks, Ptr, Hinfo, Minfo, Mx, Txt, Aaaa, Spf, Tkey
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::names::Name;
+    use std::{
+        net::{Ipv4Addr, Ipv6Addr},
+        str::FromStr,
+    };
+
+    fn name(s: &str) -> Name {
+        Name::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        let data = [
+            RecordData::A(A::from(Ipv4Addr::new(192, 0, 2, 1))),
+            RecordData::Ns(Ns {
+                nsdname: name("ns1.example.com"),
+            }),
+            RecordData::Cname(Cname { cname: name(".") }),
+            RecordData::Soa(Soa {
+                mname: name("ns1.example.com"),
+                rname: name("hostmaster.example.com"),
+                serial: 2024010101,
+                refresh: 7200,
+                retry: 3600,
+                expire: 1209600,
+                minimum: 300,
+            }),
+            RecordData::Null(Null {
+                anything: vec![1, 2, 3],
+            }),
+            RecordData::Wks(Wks {
+                address: Ipv4Addr::new(192, 0, 2, 2),
+                protocol: 6,
+                bitmap: vec![0, 0, 0x40],
+            }),
+            RecordData::Hinfo(Hinfo {
+                cpu: b"x86_64".to_vec(),
+                os: b"linux".to_vec(),
+            }),
+            RecordData::Minfo(Minfo {
+                rmailbx: name("admin.example.com"),
+                emailbx: name("errors.example.com"),
+            }),
+            RecordData::Mx(Mx {
+                preference: 10,
+                exchange: name("mail.example.com"),
+            }),
+            RecordData::Txt(Txt { text: vec![] }),
+            RecordData::Txt(Txt {
+                text: vec![b'x'; 600],
+            }),
+            RecordData::Aaaa(Aaaa::from(Ipv6Addr::LOCALHOST)),
+            RecordData::Spf(Spf {
+                text: b"v=spf1 -all".to_vec(),
+            }),
+            RecordData::Tkey(Tkey {
+                algorithm: name("gss-tsig"),
+                inception: 1700000000,
+                expiration: 1700086400,
+                mode: 3,
+                error: 0,
+                key_data: vec![0x60, 0x82, 0x01],
+                other_data: vec![],
+            }),
+        ];
+
+        for d in data {
+            let mut buf = [0u8; 1024];
+            let len = d.encode_to(&mut buf).unwrap();
+            assert_eq!(len, d.encoded_len(), "{d:?}");
+
+            let mut c = Cursor::new(&buf[..len]);
+            let decoded = RecordData::from_cursor(d.rtype(), &mut c, len).unwrap();
+            assert_eq!(decoded, d);
+
+            assert!(matches!(
+                d.encode_to(&mut buf[..len - 1]),
+                Err(Error::BufferTooShort(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_encode_unknown() {
+        let d = RecordData::Unknown(Unknown {
+            rtype: Type::from(65280),
+            data: vec![1, b'x'],
+        });
+        assert_eq!(d.rtype(), Type::from(65280));
+        assert!(!RecordData::is_supported_type(d.rtype()));
+
+        let mut buf = [0u8; 8];
+        assert_eq!(d.encode_to(&mut buf).unwrap(), d.encoded_len());
+        assert_eq!(buf[..2], [1, b'x']);
+        assert!(matches!(
+            d.encode_to(&mut buf[..1]),
+            Err(Error::BufferTooShort(_))
+        ));
+    }
+
+    #[test]
+    fn test_ord() {
+        let data = [
+            RecordData::A(A::from(Ipv4Addr::new(192, 0, 2, 1))),
+            RecordData::A(A::from(Ipv4Addr::new(192, 0, 2, 2))),
+            RecordData::A(A::from(Ipv4Addr::new(198, 51, 100, 1))),
+            RecordData::Ns(Ns {
+                nsdname: name("a.example.com"),
+            }),
+            RecordData::Ns(Ns {
+                nsdname: name("B.example.com"),
+            }),
+            RecordData::Hinfo(Hinfo {
+                cpu: b"aa".to_vec(),
+                os: b"linux".to_vec(),
+            }),
+            RecordData::Hinfo(Hinfo {
+                cpu: b"b".to_vec(),
+                os: b"linux".to_vec(),
+            }),
+            RecordData::Mx(Mx {
+                preference: 10,
+                exchange: name("z.example.com"),
+            }),
+            RecordData::Mx(Mx {
+                preference: 20,
+                exchange: name("a.example.com"),
+            }),
+            RecordData::Txt(Txt {
+                text: b"v=spf1".to_vec(),
+            }),
+            RecordData::Aaaa(Aaaa::from(Ipv6Addr::LOCALHOST)),
+            RecordData::Spf(Spf {
+                text: b"v=spf1".to_vec(),
+            }),
+            RecordData::Unknown(Unknown {
+                rtype: Type::from(65280),
+                data: vec![2],
+            }),
+            RecordData::Unknown(Unknown {
+                rtype: Type::from(65281),
+                data: vec![1],
+            }),
+        ];
+
+        for w in data.windows(2) {
+            assert!(w[0] < w[1], "{:?} < {:?}", w[0], w[1]);
+            assert!(w[0].rtype().value() <= w[1].rtype().value());
+        }
+
+        let mut sorted = data.to_vec();
+        sorted.reverse();
+        sorted.sort();
+        assert_eq!(sorted, data);
+
+        // domain names are compared case-insensitively
+        let ns = |s| RecordData::Ns(Ns { nsdname: name(s) });
+        assert_eq!(
+            ns("ns1.example.com").cmp(&ns("NS1.Example.COM")),
+            std::cmp::Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let data = [
+            (
+                RecordData::A(A::from(Ipv4Addr::new(192, 0, 2, 1))),
+                "192.0.2.1",
+            ),
+            (
+                RecordData::Cname(Cname {
+                    cname: name("www.example.com"),
+                }),
+                "www.example.com.",
+            ),
+            (
+                RecordData::Soa(Soa {
+                    mname: name("ns1.example.com"),
+                    rname: name("hostmaster.example.com"),
+                    serial: 2024010101,
+                    refresh: 7200,
+                    retry: 3600,
+                    expire: 1209600,
+                    minimum: 300,
+                }),
+                "ns1.example.com. hostmaster.example.com. 2024010101 7200 3600 1209600 300",
+            ),
+            (
+                RecordData::Null(Null {
+                    anything: vec![1, 0xAB],
+                }),
+                "\\# 2 01ab",
+            ),
+            (RecordData::Null(Null { anything: vec![] }), "\\# 0"),
+            (
+                RecordData::Wks(Wks {
+                    address: Ipv4Addr::new(192, 0, 2, 2),
+                    protocol: 6,
+                    bitmap: vec![0, 0, 0x20, 0, 0, 0, 0, 0, 0, 0, 0x80],
+                }),
+                "192.0.2.2 6 18 80",
+            ),
+            (
+                RecordData::Hinfo(Hinfo {
+                    cpu: b"x86_64".to_vec(),
+                    os: b"linux \"6\"".to_vec(),
+                }),
+                "\"x86_64\" \"linux \\\"6\\\"\"",
+            ),
+            (
+                RecordData::Minfo(Minfo {
+                    rmailbx: name("admin.example.com"),
+                    emailbx: name("errors.example.com"),
+                }),
+                "admin.example.com. errors.example.com.",
+            ),
+            (
+                RecordData::Mx(Mx {
+                    preference: 10,
+                    exchange: name("mail.example.com"),
+                }),
+                "10 mail.example.com.",
+            ),
+            (RecordData::Txt(Txt { text: vec![] }), "\"\""),
+            (
+                RecordData::Txt(Txt {
+                    text: b"a\\b\x07".to_vec(),
+                }),
+                "\"a\\\\b\\007\"",
+            ),
+            (RecordData::Aaaa(Aaaa::from(Ipv6Addr::LOCALHOST)), "::1"),
+            (
+                RecordData::Spf(Spf {
+                    text: b"v=spf1 -all".to_vec(),
+                }),
+                "\"v=spf1 -all\"",
+            ),
+            (
+                RecordData::Tkey(Tkey {
+                    algorithm: name("gss-tsig"),
+                    inception: 1700000000,
+                    expiration: 1700086400,
+                    mode: 3,
+                    error: 0,
+                    key_data: vec![0x60, 0x82, 0x01],
+                    other_data: vec![],
+                }),
+                "gss-tsig. 1700000000 1700086400 3 0 3 608201 0",
+            ),
+            (
+                RecordData::Unknown(Unknown {
+                    rtype: Type::from(65280),
+                    data: vec![1, b'x'],
+                }),
+                "\\# 2 0178",
+            ),
+        ];
+
+        for (d, expected) in data {
+            assert_eq!(d.to_string(), expected);
+        }
+
+        let long = RecordData::Txt(Txt {
+            text: vec![b'x'; 256],
+        });
+        let expected = format!("\"{}\" \"x\"", "x".repeat(255));
+        assert_eq!(long.to_string(), expected);
+    }
+
+    #[test]
+    fn test_txt_from_bytes() {
+        let txt = Txt::from_str("v=spf1 -all").unwrap();
+        assert_eq!(txt.text, b"v=spf1 -all");
+
+        // 256 character-strings of 255 bytes take exactly 65535 bytes
+        let max = vec![b'x'; 256 * 255 - 1];
+        let txt = Txt::from_bytes(&max).unwrap();
+        assert_eq!(RecordData::Txt(txt).encoded_len(), u16::MAX as usize);
+
+        assert!(matches!(
+            Txt::from_bytes(&[b'x'; 256 * 255]),
+            Err(Error::BadParam(_))
+        ));
+    }
+
+    #[test]
+    fn test_encode_long_character_string() {
+        let hinfo = Hinfo {
+            cpu: vec![b'x'; 256],
+            os: vec![],
+        };
+        let mut buf = [0u8; 1024];
+        assert!(matches!(hinfo.encode_to(&mut buf), Err(Error::BadParam(_))));
+    }
+
+    #[test]
+    fn test_encode_long_tkey_data() {
+        let tkey = Tkey {
+            algorithm: name("gss-tsig"),
+            key_data: vec![0; 65536],
+            ..Default::default()
+        };
+        let mut buf = vec![0u8; 70000];
+        assert!(matches!(tkey.encode_to(&mut buf), Err(Error::BadParam(_))));
+    }
 }
@@ -1,20 +1,94 @@
-use crate::records::Type;
+use crate::{bytes::WCursor, records::Type, Result};
 use std::{fmt::Debug, hash::Hash};
 
 pub(super) mod private {
-    use crate::{bytes::Cursor, errors::Result};
+    use crate::{
+        bytes::{Cursor, WCursor},
+        errors::Result,
+    };
 
     pub trait RDataBase {
         fn from_cursor(c: &mut Cursor<'_>, rdlen: usize) -> Result<Self>
         where
             Self: Sized;
+
+        fn rdata_len(&self) -> usize;
+
+        fn write_rdata(&self, c: &mut WCursor<'_>) -> Result<()>;
     }
 }
 
 /// A marker trait for all record-data types.
+///
+/// The record type associated with the data is available as [`RData::RTYPE`].
+/// Generic code can use it to verify that a record is read with the right data type.
+///
+/// # Examples
+///
+/// ```rust
+/// use rsdns::{
+///     message::reader::{MessageReader, RecordMarker},
+///     records::data::RData,
+///     Result,
+/// };
+///
+/// fn read_data<D: RData>(mr: &mut MessageReader, marker: &RecordMarker) -> Result<Option<D>> {
+///     if marker.rtype() != D::RTYPE {
+///         mr.skip_record_data(marker)?;
+///         return Ok(None);
+///     }
+///     mr.record_data(marker).map(Some)
+/// }
+/// ```
 pub trait RData:
     private::RDataBase + Clone + Eq + PartialEq + Hash + Debug + Ord + PartialOrd
 {
     /// Record data type as associated constant.
     const RTYPE: Type;
+
+    /// Returns the length (in bytes) of the record data in wire format.
+    ///
+    /// Domain names are not compressed, so this is the length of the data written by
+    /// [`encode_to`](Self::encode_to).
+    #[inline]
+    fn encoded_len(&self) -> usize {
+        self.rdata_len()
+    }
+
+    /// Writes the record data in wire format into `buf`.
+    ///
+    /// Domain names are written uncompressed. Returns the number of bytes written, which equals
+    /// [`encoded_len`](Self::encoded_len).
+    ///
+    /// # Returns
+    ///
+    /// - [`Error::BufferTooShort`](crate::Error::BufferTooShort) - if `buf` is too short
+    /// - [`Error::BadParam`](crate::Error::BadParam) - if a character-string exceeds 255 bytes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rsdns::records::data::{Mx, RData};
+    /// use std::str::FromStr;
+    ///
+    /// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mx = Mx {
+    ///     preference: 10,
+    ///     exchange: FromStr::from_str("mail.example.com")?,
+    /// };
+    ///
+    /// let mut buf = [0u8; 64];
+    /// let len = mx.encode_to(&mut buf)?;
+    /// assert_eq!(len, mx.encoded_len());
+    /// assert_eq!(&buf[..len], b"\x00\x0a\x04mail\x07example\x03com\x00");
+    /// # Ok(())
+    /// # }
+    /// # foo().unwrap();
+    /// ```
+    #[inline]
+    fn encode_to(&self, buf: &mut [u8]) -> Result<usize> {
+        let mut c = WCursor::new(buf);
+        self.write_rdata(&mut c)?;
+        Ok(c.pos())
+    }
 }
@@ -1,10 +1,15 @@
 use crate::{
-    bytes::{Cursor, Reader, RrDataReader},
-    names::Name,
-    records::Type,
-    Result,
+    bytes::{Cursor, Reader, RrDataReader, RrDataWriter, WCursor},
+    message::fmt_character_string,
+    names::{wire_length, Name},
+    records::{data::rfc3597::fmt_generic, Type},
+    Error, Result,
+};
+use std::{
+    fmt::{self, Display, Formatter},
+    net::{IpAddr, Ipv4Addr},
+    str::FromStr,
 };
-use std::net::Ipv4Addr;
 
 // ------------------------------------------------------------------------------------------------
 
@@ -19,6 +24,33 @@ pub struct A {
 
 rr_data!(A, Type::A);
 
+impl Display for A {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.address.fmt(f)
+    }
+}
+
+impl From<Ipv4Addr> for A {
+    #[inline]
+    fn from(address: Ipv4Addr) -> Self {
+        A { address }
+    }
+}
+
+impl From<A> for Ipv4Addr {
+    #[inline]
+    fn from(rdata: A) -> Self {
+        rdata.address
+    }
+}
+
+impl From<A> for IpAddr {
+    #[inline]
+    fn from(rdata: A) -> Self {
+        IpAddr::from(rdata.address)
+    }
+}
+
 impl RrDataReader<A> for Cursor<'_> {
     fn read_rr_data(&mut self, rd_len: usize) -> Result<A> {
         self.window(rd_len)?;
@@ -30,6 +62,16 @@ impl RrDataReader<A> for Cursor<'_> {
     }
 }
 
+impl RrDataWriter<A> for WCursor<'_> {
+    fn rr_data_len(_: &A) -> usize {
+        4
+    }
+
+    fn write_rr_data(&mut self, rd: &A) -> Result<()> {
+        self.bytes(&rd.address.octets())
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 
 rr_dn_data!(
@@ -62,6 +104,25 @@ pub struct Hinfo {
 
 rr_data!(Hinfo, Type::HINFO);
 
+impl Display for Hinfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        fmt_character_string(f, &self.cpu)?;
+        f.write_str(" ")?;
+        fmt_character_string(f, &self.os)
+    }
+}
+
+impl Hinfo {
+    /// Checks if this is a synthesized `HINFO` record of a minimal response to an `ANY` query.
+    ///
+    /// A server that doesn't answer `ANY` queries in full may respond with a single synthesized
+    /// `HINFO` record, with the CPU field set to `"RFC8482"`
+    /// ([RFC 8482 section 4.2](https://www.rfc-editor.org/rfc/rfc8482.html#section-4.2)).
+    pub fn is_rfc8482(&self) -> bool {
+        self.cpu == b"RFC8482"
+    }
+}
+
 impl RrDataReader<Hinfo> for Cursor<'_> {
     fn read_rr_data(&mut self, rd_len: usize) -> Result<Hinfo> {
         self.window(rd_len)?;
@@ -74,6 +135,17 @@ impl RrDataReader<Hinfo> for Cursor<'_> {
     }
 }
 
+impl RrDataWriter<Hinfo> for WCursor<'_> {
+    fn rr_data_len(rd: &Hinfo) -> usize {
+        1 + rd.cpu.len() + 1 + rd.os.len()
+    }
+
+    fn write_rr_data(&mut self, rd: &Hinfo) -> Result<()> {
+        self.write_character_string(&rd.cpu)?;
+        self.write_character_string(&rd.os)
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 
 /// A well known service description.
@@ -100,6 +172,20 @@ pub struct Wks {
 
 rr_data!(Wks, Type::WKS);
 
+impl Display for Wks {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.address, self.protocol)?;
+        for (i, byte) in self.bitmap.iter().enumerate() {
+            for bit in 0..8 {
+                if byte & (0x80 >> bit) != 0 {
+                    write!(f, " {}", i * 8 + bit)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 impl RrDataReader<Wks> for Cursor<'_> {
     fn read_rr_data(&mut self, rd_len: usize) -> Result<Wks> {
         self.window(rd_len)?;
@@ -113,6 +199,18 @@ impl RrDataReader<Wks> for Cursor<'_> {
     }
 }
 
+impl RrDataWriter<Wks> for WCursor<'_> {
+    fn rr_data_len(rd: &Wks) -> usize {
+        4 + 1 + rd.bitmap.len()
+    }
+
+    fn write_rr_data(&mut self, rd: &Wks) -> Result<()> {
+        self.bytes(&rd.address.octets())?;
+        self.u8(rd.protocol)?;
+        self.bytes(&rd.bitmap)
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 
 rr_dn_data!(
@@ -188,6 +286,12 @@ pub struct Minfo {
 
 rr_data!(Minfo, Type::MINFO);
 
+impl Display for Minfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.rmailbx, self.emailbx)
+    }
+}
+
 impl RrDataReader<Minfo> for Cursor<'_> {
     fn read_rr_data(&mut self, rd_len: usize) -> Result<Minfo> {
         self.window(rd_len)?;
@@ -200,6 +304,18 @@ impl RrDataReader<Minfo> for Cursor<'_> {
     }
 }
 
+impl RrDataWriter<Minfo> for WCursor<'_> {
+    fn rr_data_len(rd: &Minfo) -> usize {
+        wire_length(rd.rmailbx.as_str()) + wire_length(rd.emailbx.as_str())
+    }
+
+    fn write_rr_data(&mut self, rd: &Minfo) -> Result<()> {
+        self.write_domain_name(rd.rmailbx.as_str())?;
+        self.write_domain_name(rd.emailbx.as_str())?;
+        Ok(())
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 
 rr_dn_data!(
@@ -228,6 +344,12 @@ pub struct Mx {
 
 rr_data!(Mx, Type::MX);
 
+impl Display for Mx {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.preference, self.exchange)
+    }
+}
+
 impl RrDataReader<Mx> for Cursor<'_> {
     fn read_rr_data(&mut self, rd_len: usize) -> Result<Mx> {
         self.window(rd_len)?;
@@ -240,6 +362,18 @@ impl RrDataReader<Mx> for Cursor<'_> {
     }
 }
 
+impl RrDataWriter<Mx> for WCursor<'_> {
+    fn rr_data_len(rd: &Mx) -> usize {
+        2 + wire_length(rd.exchange.as_str())
+    }
+
+    fn write_rr_data(&mut self, rd: &Mx) -> Result<()> {
+        self.u16_be(rd.preference)?;
+        self.write_domain_name(rd.exchange.as_str())?;
+        Ok(())
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 
 /// The Null record.
@@ -253,6 +387,12 @@ pub struct Null {
 
 rr_data!(Null, Type::NULL);
 
+impl Display for Null {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        fmt_generic(f, &self.anything)
+    }
+}
+
 impl RrDataReader<Null> for Cursor<'_> {
     fn read_rr_data(&mut self, rd_len: usize) -> Result<Null> {
         self.window(rd_len)?;
@@ -264,6 +404,16 @@ impl RrDataReader<Null> for Cursor<'_> {
     }
 }
 
+impl RrDataWriter<Null> for WCursor<'_> {
+    fn rr_data_len(rd: &Null) -> usize {
+        rd.anything.len()
+    }
+
+    fn write_rr_data(&mut self, rd: &Null) -> Result<()> {
+        self.bytes(&rd.anything)
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 
 rr_dn_data!(
@@ -317,6 +467,22 @@ pub struct Soa {
 
 rr_data!(Soa, Type::SOA);
 
+impl Display for Soa {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {} {} {}",
+            self.mname,
+            self.rname,
+            self.serial,
+            self.refresh,
+            self.retry,
+            self.expire,
+            self.minimum
+        )
+    }
+}
+
 impl RrDataReader<Soa> for Cursor<'_> {
     fn read_rr_data(&mut self, rd_len: usize) -> Result<Soa> {
         self.window(rd_len)?;
@@ -334,6 +500,22 @@ impl RrDataReader<Soa> for Cursor<'_> {
     }
 }
 
+impl RrDataWriter<Soa> for WCursor<'_> {
+    fn rr_data_len(rd: &Soa) -> usize {
+        wire_length(rd.mname.as_str()) + wire_length(rd.rname.as_str()) + 5 * 4
+    }
+
+    fn write_rr_data(&mut self, rd: &Soa) -> Result<()> {
+        self.write_domain_name(rd.mname.as_str())?;
+        self.write_domain_name(rd.rname.as_str())?;
+        self.u32_be(rd.serial)?;
+        self.u32_be(rd.refresh)?;
+        self.u32_be(rd.retry)?;
+        self.u32_be(rd.expire)?;
+        self.u32_be(rd.minimum)
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 
 /// Text strings.
@@ -348,18 +530,119 @@ pub struct Txt {
 
 rr_data!(Txt, Type::TXT);
 
+impl Txt {
+    /// Creates a `Txt` from arbitrary bytes.
+    ///
+    /// The text is not limited to 255 bytes, as it is split into character-strings of up to
+    /// 255 bytes when the record is written. However, the encoded text must fit in the record
+    /// data, which is limited to 65535 bytes.
+    ///
+    /// # Returns
+    ///
+    /// - [`Error::BadParam`] - if the encoded text exceeds the maximal record data length
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rsdns::records::data::Txt;
+    ///
+    /// let dkim = format!("v=DKIM1; k=rsa; p={}", "A".repeat(400));
+    /// let txt = Txt::from_bytes(dkim.as_bytes()).unwrap();
+    /// assert_eq!(txt.to_string().matches('"').count(), 4);
+    ///
+    /// assert!(Txt::from_bytes(&[b'x'; 65535]).is_err());
+    /// ```
+    pub fn from_bytes(text: &[u8]) -> Result<Self> {
+        if text_len(text) > u16::MAX as usize {
+            return Err(Error::BadParam(
+                "text exceeds the maximal record data length",
+            ));
+        }
+        Ok(Self {
+            text: text.to_vec(),
+        })
+    }
+}
+
+impl FromStr for Txt {
+    type Err = Error;
+
+    /// Creates a `Txt` from a string.
+    ///
+    /// See [`Txt::from_bytes`].
+    fn from_str(s: &str) -> Result<Self> {
+        Self::from_bytes(s.as_bytes())
+    }
+}
+
+impl Display for Txt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        fmt_text(f, &self.text)
+    }
+}
+
 impl RrDataReader<Txt> for Cursor<'_> {
-    fn read_rr_data(&mut self, mut rd_len: usize) -> Result<Txt> {
-        self.window(rd_len)?;
-        let mut text = Vec::with_capacity(rd_len);
-        while rd_len > 0 {
-            let len = self.u8()? as usize;
-            if len > 0 {
-                text.extend_from_slice(self.slice(len)?);
-            }
-            rd_len -= len + 1;
+    fn read_rr_data(&mut self, rd_len: usize) -> Result<Txt> {
+        Ok(Txt {
+            text: read_text(self, rd_len)?,
+        })
+    }
+}
+
+impl RrDataWriter<Txt> for WCursor<'_> {
+    fn rr_data_len(rd: &Txt) -> usize {
+        text_len(&rd.text)
+    }
+
+    fn write_rr_data(&mut self, rd: &Txt) -> Result<()> {
+        write_text(self, &rd.text)
+    }
+}
+
+/// Reads record data consisting of one or more character-strings, and concatenates them.
+pub(super) fn read_text(c: &mut Cursor<'_>, mut rd_len: usize) -> Result<Vec<u8>> {
+    c.window(rd_len)?;
+    let mut text = Vec::with_capacity(rd_len);
+    while rd_len > 0 {
+        let len = c.u8()? as usize;
+        if len > 0 {
+            text.extend_from_slice(c.slice(len)?);
         }
-        self.close_window()?;
-        Ok(Txt { text })
+        rd_len -= len + 1;
+    }
+    c.close_window()?;
+    Ok(text)
+}
+
+/// Formats `text` as a sequence of character-strings, as written by [`write_text`].
+pub(super) fn fmt_text(f: &mut Formatter<'_>, text: &[u8]) -> fmt::Result {
+    if text.is_empty() {
+        return fmt_character_string(f, text);
+    }
+    for (i, s) in text.chunks(u8::MAX as usize).enumerate() {
+        if i > 0 {
+            f.write_str(" ")?;
+        }
+        fmt_character_string(f, s)?;
+    }
+    Ok(())
+}
+
+/// Returns the length of `text` written by [`write_text`].
+pub(super) fn text_len(text: &[u8]) -> usize {
+    // the text is split into character-strings of up to 255 bytes; empty text is written
+    // as a single empty character-string
+    let strings = text.len().div_ceil(u8::MAX as usize).max(1);
+    strings + text.len()
+}
+
+/// Writes `text` as a sequence of character-strings.
+pub(super) fn write_text(c: &mut WCursor<'_>, text: &[u8]) -> Result<()> {
+    if text.is_empty() {
+        return c.u8(0);
+    }
+    for s in text.chunks(u8::MAX as usize) {
+        c.write_character_string(s)?;
     }
+    Ok(())
 }
@@ -0,0 +1,110 @@
+use crate::{
+    bytes::{Cursor, Reader, RrDataReader, RrDataWriter, WCursor},
+    names::{wire_length, Name},
+    records::Type,
+    Error, Result,
+};
+use std::fmt::{self, Display, Formatter};
+
+/// A transaction key.
+///
+/// The `TKEY` meta-record establishes shared secret keys between a resolver and a server,
+/// e.g. the keys used by `TSIG` in GSS-TSIG
+/// ([RFC 3645](https://www.rfc-editor.org/rfc/rfc3645.html)).
+/// It is sent in the additional section of a `TKEY` query, and is returned in the answer section
+/// of the response.
+///
+/// [RFC 2930 section 2](https://www.rfc-editor.org/rfc/rfc2930.html#section-2)
+#[derive(Clone, Eq, PartialEq, Hash, Default, Debug, Ord, PartialOrd)]
+pub struct Tkey {
+    /// The key algorithm, e.g. `gss-tsig.` for GSS-TSIG.
+    pub algorithm: Name,
+    /// The start of the validity interval of the key, in seconds since the epoch.
+    pub inception: u32,
+    /// The end of the validity interval of the key, in seconds since the epoch.
+    pub expiration: u32,
+    /// The key assignment mode.
+    ///
+    /// - `1` - server assignment
+    /// - `2` - Diffie-Hellman exchange
+    /// - `3` - GSS-API negotiation
+    /// - `4` - resolver assignment
+    /// - `5` - key deletion
+    pub mode: u16,
+    /// The extended response code. `0` in queries.
+    pub error: u16,
+    /// The key exchange data, interpreted according to [`mode`](Self::mode).
+    pub key_data: Vec<u8>,
+    /// Other data, reserved for future extensions.
+    pub other_data: Vec<u8>,
+}
+
+rr_data!(Tkey, Type::TKEY);
+
+impl Display for Tkey {
+    /// Formats the record data with the binary fields in hexadecimal, each preceded by its length.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {}",
+            self.algorithm, self.inception, self.expiration, self.mode, self.error
+        )?;
+        for data in [&self.key_data, &self.other_data] {
+            write!(f, " {}", data.len())?;
+            if !data.is_empty() {
+                f.write_str(" ")?;
+                for b in data {
+                    write!(f, "{b:02x}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl RrDataReader<Tkey> for Cursor<'_> {
+    fn read_rr_data(&mut self, rd_len: usize) -> Result<Tkey> {
+        self.window(rd_len)?;
+        let algorithm = self.read()?;
+        let inception = self.u32_be()?;
+        let expiration = self.u32_be()?;
+        let mode = self.u16_be()?;
+        let error = self.u16_be()?;
+        let len = self.u16_be()? as usize;
+        let key_data = Vec::from(self.slice(len)?);
+        let len = self.u16_be()? as usize;
+        let other_data = Vec::from(self.slice(len)?);
+        self.close_window()?;
+        Ok(Tkey {
+            algorithm,
+            inception,
+            expiration,
+            mode,
+            error,
+            key_data,
+            other_data,
+        })
+    }
+}
+
+impl RrDataWriter<Tkey> for WCursor<'_> {
+    fn rr_data_len(rd: &Tkey) -> usize {
+        // inception, expiration, mode, error and the two data lengths take 16 bytes
+        wire_length(rd.algorithm.as_str()) + 16 + rd.key_data.len() + rd.other_data.len()
+    }
+
+    fn write_rr_data(&mut self, rd: &Tkey) -> Result<()> {
+        self.write_domain_name(rd.algorithm.as_str())?;
+        self.u32_be(rd.inception)?;
+        self.u32_be(rd.expiration)?;
+        self.u16_be(rd.mode)?;
+        self.u16_be(rd.error)?;
+        for data in [&rd.key_data, &rd.other_data] {
+            let len = u16::try_from(data.len())
+                .map_err(|_| Error::BadParam("TKEY data exceeds 65535 bytes"))?;
+            self.u16_be(len)?;
+            self.bytes(data)?;
+        }
+        Ok(())
+    }
+}
@@ -1,9 +1,12 @@
 use crate::{
-    bytes::{Cursor, Reader, RrDataReader},
+    bytes::{Cursor, Reader, RrDataReader, RrDataWriter, WCursor},
     records::Type,
     Result,
 };
-use std::net::Ipv6Addr;
+use std::{
+    fmt::{self, Display, Formatter},
+    net::{IpAddr, Ipv6Addr},
+};
 
 /// A host address (IPv6).
 ///
@@ -16,6 +19,33 @@ pub struct Aaaa {
 
 rr_data!(Aaaa, Type::AAAA);
 
+impl Display for Aaaa {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.address.fmt(f)
+    }
+}
+
+impl From<Ipv6Addr> for Aaaa {
+    #[inline]
+    fn from(address: Ipv6Addr) -> Self {
+        Aaaa { address }
+    }
+}
+
+impl From<Aaaa> for Ipv6Addr {
+    #[inline]
+    fn from(rdata: Aaaa) -> Self {
+        rdata.address
+    }
+}
+
+impl From<Aaaa> for IpAddr {
+    #[inline]
+    fn from(rdata: Aaaa) -> Self {
+        IpAddr::from(rdata.address)
+    }
+}
+
 impl RrDataReader<Aaaa> for Cursor<'_> {
     fn read_rr_data(&mut self, rd_len: usize) -> Result<Aaaa> {
         self.window(rd_len)?;
@@ -26,3 +56,13 @@ impl RrDataReader<Aaaa> for Cursor<'_> {
         rr
     }
 }
+
+impl RrDataWriter<Aaaa> for WCursor<'_> {
+    fn rr_data_len(_: &Aaaa) -> usize {
+        16
+    }
+
+    fn write_rr_data(&mut self, rd: &Aaaa) -> Result<()> {
+        self.bytes(&rd.address.octets())
+    }
+}
@@ -0,0 +1,35 @@
+use crate::records::Type;
+use std::fmt::{self, Display, Formatter};
+
+/// Data of a record type not supported by *rsdns*.
+///
+/// The data is kept as is, in wire format. It is returned by `Client::query_any` for records
+/// of types without a dedicated [`RecordData`](super::RecordData) variant.
+///
+/// [RFC 3597](https://www.rfc-editor.org/rfc/rfc3597.html)
+#[derive(Clone, Eq, PartialEq, Hash, Default, Debug, Ord, PartialOrd)]
+pub struct Unknown {
+    /// The record type.
+    pub rtype: Type,
+    /// The record data in wire format.
+    pub data: Vec<u8>,
+}
+
+impl Display for Unknown {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        fmt_generic(f, &self.data)
+    }
+}
+
+/// Formats record data in the generic presentation format
+/// ([RFC 3597 section 5](https://www.rfc-editor.org/rfc/rfc3597.html#section-5)).
+pub(crate) fn fmt_generic(f: &mut Formatter<'_>, data: &[u8]) -> fmt::Result {
+    write!(f, "\\# {}", data.len())?;
+    if !data.is_empty() {
+        f.write_str(" ")?;
+        for b in data {
+            write!(f, "{b:02x}")?;
+        }
+    }
+    Ok(())
+}
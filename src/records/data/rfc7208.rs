@@ -0,0 +1,48 @@
+use crate::{
+    bytes::{Cursor, RrDataReader, RrDataWriter, WCursor},
+    records::{
+        data::rfc1035::{fmt_text, read_text, text_len, write_text},
+        Type,
+    },
+    Result,
+};
+use std::fmt::{self, Display, Formatter};
+
+/// Sender Policy Framework.
+///
+/// The `SPF` record type is deprecated in favor of [`Txt`](super::Txt) records, which carry
+/// the same data. It is supported to read existing records only.
+/// The record data has the same format as [`Txt`](super::Txt).
+///
+/// [RFC 7208 section 3.1](https://www.rfc-editor.org/rfc/rfc7208.html#section-3.1)
+#[derive(Clone, Eq, PartialEq, Hash, Default, Debug, Ord, PartialOrd)]
+pub struct Spf {
+    /// The SPF policy text.
+    pub text: Vec<u8>,
+}
+
+rr_data!(Spf, Type::SPF);
+
+impl Display for Spf {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        fmt_text(f, &self.text)
+    }
+}
+
+impl RrDataReader<Spf> for Cursor<'_> {
+    fn read_rr_data(&mut self, rd_len: usize) -> Result<Spf> {
+        Ok(Spf {
+            text: read_text(self, rd_len)?,
+        })
+    }
+}
+
+impl RrDataWriter<Spf> for WCursor<'_> {
+    fn rr_data_len(rd: &Spf) -> usize {
+        text_len(&rd.text)
+    }
+
+    fn write_rr_data(&mut self, rd: &Spf) -> Result<()> {
+        write_text(self, &rd.text)
+    }
+}
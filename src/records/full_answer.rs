@@ -0,0 +1,147 @@
+use crate::{
+    message::{reader::MessageIterator, Header, MessageType, RecordsSection},
+    records::ResourceRecord,
+    Error, Result,
+};
+
+/// All records of a response message, grouped by section.
+///
+/// Unlike [`RecordSet`](crate::records::RecordSet), which holds the records answering
+/// the question, `FullAnswer` holds everything the response carries: the header, the answer
+/// section, the `NS` records of the authority section and the glue of the additional section.
+/// It is intended for debugging tools, which display a response as a whole, similar to `dig`.
+///
+/// The data of each record is parsed into the [`RecordData`] enum. Records of types not
+/// supported by [`RecordData`], and the `OPT` pseudo-record, are skipped.
+///
+/// [`RecordData`]: crate::records::data::RecordData
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct FullAnswer {
+    /// The message header.
+    ///
+    /// The response code is available in the header flags. The record counts are those
+    /// declared in the header, and include records skipped while parsing.
+    pub header: Header,
+
+    /// The records of the answer section.
+    pub answers: Vec<ResourceRecord>,
+
+    /// The records of the authority section.
+    pub authorities: Vec<ResourceRecord>,
+
+    /// The records of the additional section.
+    pub additionals: Vec<ResourceRecord>,
+}
+
+impl FullAnswer {
+    /// Parses a [`FullAnswer`] from a response message.
+    ///
+    /// A response is parsed regardless of its response code, e.g. the authority section of
+    /// an `NXDOMAIN` response is returned as well.
+    ///
+    /// # Returns
+    ///
+    /// - [`Error::BadMessageType`] - if the message is not a response
+    /// - [`Error::MessageTruncated`] - if the response is truncated
+    pub fn from_msg(msg: &[u8]) -> Result<Self> {
+        let mi = MessageIterator::new(msg)?;
+
+        let header = mi.header().clone();
+        if header.flags.message_type() != MessageType::Response {
+            return Err(Error::BadMessageType(header.flags.message_type()));
+        }
+        if header.flags.truncated() {
+            return Err(Error::MessageTruncated);
+        }
+
+        let mut answer = Self {
+            header,
+            answers: Vec::new(),
+            authorities: Vec::new(),
+            additionals: Vec::new(),
+        };
+        for res in mi.records() {
+            let (section, record) = res?;
+            match section {
+                RecordsSection::Answer => answer.answers.push(record),
+                RecordsSection::Authority => answer.authorities.push(record),
+                RecordsSection::Additional => answer.additionals.push(record),
+            }
+        }
+        Ok(answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        message::RCode,
+        records::{data::RecordData, Type},
+    };
+
+    // ;; QUESTION SECTION:
+    // ;www.example.com.        IN      A
+    //
+    // ;; ANSWER SECTION:
+    // www.example.com. 300     IN      A       192.0.2.1
+    //
+    // ;; AUTHORITY SECTION:
+    // example.com.     172800  IN      NS      ns1.example.com.
+    //
+    // ;; ADDITIONAL SECTION:
+    // ns1.example.com. 172800  IN      A       192.0.2.53
+    // .                        OPT
+    #[rustfmt::skip]
+    const RESPONSE: [u8; 94] = [
+        0x00, 0x01, 0x84, 0x00, 0x00, 0x01, 0x00, 0x01, 0x00, 0x01, 0x00, 0x02, // header
+        0x03, b'w', b'w', b'w', 0x07, b'e', b'x', b'a', b'm', b'p', b'l', b'e',
+        0x03, b'c', b'o', b'm', 0x00, 0x00, 0x01, 0x00, 0x01, // question
+        0xC0, 0x0C, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x01, 0x2C, 0x00, 0x04,
+        0xC0, 0x00, 0x02, 0x01, // www.example.com A 192.0.2.1
+        0xC0, 0x10, 0x00, 0x02, 0x00, 0x01, 0x00, 0x02, 0xA3, 0x00, 0x00, 0x06,
+        0x03, b'n', b's', b'1', 0xC0, 0x10, // example.com NS ns1.example.com
+        0xC0, 0x3D, 0x00, 0x01, 0x00, 0x01, 0x00, 0x02, 0xA3, 0x00, 0x00, 0x04,
+        0xC0, 0x00, 0x02, 0x35, // ns1.example.com A 192.0.2.53
+        0x00, 0x00, 0x29, 0x04, 0xD0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // OPT
+    ];
+
+    #[test]
+    fn test_from_msg() {
+        let answer = FullAnswer::from_msg(&RESPONSE).unwrap();
+        assert_eq!(answer.header.id, 1);
+        assert_eq!(answer.header.ar_count, 2);
+
+        assert_eq!(answer.answers.len(), 1);
+        assert_eq!(answer.answers[0].name, "www.example.com.");
+        assert_eq!(answer.answers[0].ttl, 300);
+        assert_eq!(answer.authorities.len(), 1);
+        assert_eq!(answer.authorities[0].rtype, Type::NS);
+        match answer.authorities[0].rdata {
+            RecordData::Ns(ref ns) => assert_eq!(ns.nsdname, "ns1.example.com."),
+            ref rdata => panic!("unexpected record data {rdata:?}"),
+        }
+        assert_eq!(answer.additionals.len(), 1);
+        assert_eq!(answer.additionals[0].name, "ns1.example.com.");
+
+        let mut msg = RESPONSE;
+        msg[3] = 0x03; // NXDOMAIN
+        let answer = FullAnswer::from_msg(&msg).unwrap();
+        assert_eq!(answer.header.flags.response_code(), RCode::NXDOMAIN);
+        assert_eq!(answer.authorities.len(), 1);
+
+        let mut msg = RESPONSE;
+        msg[2] &= 0x7F;
+        assert!(matches!(
+            FullAnswer::from_msg(&msg),
+            Err(Error::BadMessageType(_))
+        ));
+
+        let mut msg = RESPONSE;
+        msg[2] |= 0x02;
+        assert!(matches!(
+            FullAnswer::from_msg(&msg),
+            Err(Error::MessageTruncated)
+        ));
+    }
+}
@@ -11,8 +11,20 @@ pub use record::*;
 mod record_set;
 pub use record_set::*;
 
+mod negative_answer;
+pub use negative_answer::*;
+
+mod referral;
+pub use referral::*;
+
+mod full_answer;
+pub use full_answer::*;
+
 mod class;
 pub use class::*;
 
 mod r#type;
 pub use r#type::*;
+
+#[cfg(feature = "serde")]
+mod serde_impl;
@@ -0,0 +1,185 @@
+use crate::{
+    message::{reader::MessageIterator, MessageType, RCode, RecordsSection},
+    names::Name,
+    records::{
+        data::{RecordData, Soa},
+        RecordSet, Type,
+    },
+    Error, Result,
+};
+
+/// Information found in a negative response.
+///
+/// A negative response either reports a non-existent domain name (`NXDOMAIN`), or a name
+/// which has no records of the requested type (`NODATA`, i.e. `NOERROR` with an empty answer).
+/// Such a response usually carries the `SOA` record of the zone in its authority section.
+/// The `SOA` record is required to cache the negative response and to find the zone which
+/// denied the name.
+///
+/// [RFC 2308](https://www.rfc-editor.org/rfc/rfc2308.html)
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct NegativeAnswer {
+    /// The response code: `NXDOMAIN`, or `NOERROR` for `NODATA`.
+    pub rcode: RCode,
+
+    /// The `SOA` record of the authority section, if present.
+    ///
+    /// The record set name is the zone apex.
+    pub soa: Option<RecordSet<Soa>>,
+}
+
+impl NegativeAnswer {
+    /// Parses a [`NegativeAnswer`] from a response message.
+    ///
+    /// Returns `None` if the response is not negative, i.e. its response code is neither
+    /// `NXDOMAIN` nor `NOERROR`, or it answers the question. A response with an answer section
+    /// made of `CNAME` records only is negative, as it denies the last name of the
+    /// *CNAME chain*.
+    pub fn from_msg(msg: &[u8]) -> Result<Option<Self>> {
+        let mi = MessageIterator::new(msg)?;
+
+        let flags = mi.header().flags;
+        if flags.message_type() != MessageType::Response {
+            return Err(Error::BadMessageType(flags.message_type()));
+        }
+        if flags.truncated() {
+            return Err(Error::MessageTruncated);
+        }
+
+        let rcode = flags.response_code();
+        if rcode != RCode::NXDOMAIN && rcode != RCode::NOERROR {
+            return Ok(None);
+        }
+
+        let qtype = mi.question()?.qtype;
+
+        let mut soa = None;
+        for res in mi.records() {
+            let (section, record) = res?;
+            match section {
+                RecordsSection::Answer => {
+                    if record.rtype != Type::CNAME || qtype == Type::CNAME {
+                        return Ok(None);
+                    }
+                }
+                RecordsSection::Authority => {
+                    if let RecordData::Soa(data) = record.rdata {
+                        soa = Some(RecordSet {
+                            name: Name::from(record.name),
+                            rclass: record.rclass,
+                            ttl: record.ttl,
+                            rdata: vec![data],
+                        });
+                        break;
+                    }
+                }
+                RecordsSection::Additional => break,
+            }
+        }
+
+        Ok(Some(Self { rcode, soa }))
+    }
+
+    /// Returns the TTL of the negative response.
+    ///
+    /// This is the minimum between the TTL of the `SOA` record and its `minimum` field,
+    /// as defined in [RFC 2308 section 5]. Returns `None` if no `SOA` record is present,
+    /// in which case the negative response should not be cached.
+    ///
+    /// [RFC 2308 section 5]: https://www.rfc-editor.org/rfc/rfc2308.html#section-5
+    pub fn ttl(&self) -> Option<u32> {
+        let soa = self.soa.as_ref()?;
+        soa.rdata.first().map(|d| soa.ttl.min(d.minimum))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        message::{QueryWriter, ResponseBuilder},
+        records::{
+            data::{Cname, A},
+            Class,
+        },
+    };
+    use std::{net::Ipv4Addr, str::FromStr};
+
+    fn response(buf: &mut [u8], rcode: RCode, answer: Option<Type>, soa: bool) -> usize {
+        let mut qbuf = [0u8; 512];
+        let len = QueryWriter::new(&mut qbuf)
+            .write("a.example.com", Type::A, Class::IN, true, None)
+            .unwrap();
+
+        let mut rb = ResponseBuilder::new(buf, &qbuf[2..len]).unwrap();
+        rb.set_response_code(rcode).unwrap();
+        match answer {
+            Some(Type::A) => {
+                let rdata = A::from(Ipv4Addr::new(192, 0, 2, 1));
+                rb.add_answer("a.example.com", Class::IN, 300, &rdata)
+                    .unwrap();
+            }
+            Some(Type::CNAME) => {
+                let rdata = Cname {
+                    cname: Name::from_str("b.example.com").unwrap(),
+                };
+                rb.add_answer("a.example.com", Class::IN, 300, &rdata)
+                    .unwrap();
+            }
+            _ => {}
+        }
+        if soa {
+            let rdata = Soa {
+                mname: Name::from_str("ns.example.com").unwrap(),
+                rname: Name::from_str("hostmaster.example.com").unwrap(),
+                serial: 1,
+                refresh: 7200,
+                retry: 3600,
+                expire: 1209600,
+                minimum: 60,
+            };
+            rb.add_answer("example.com", Class::IN, 3600, &rdata)
+                .unwrap();
+        }
+        let len = rb.finish().unwrap();
+
+        // the builder writes the answer section only, move the SOA record to the authority
+        if soa {
+            buf[9] -= 1;
+            buf[11] = 1;
+        }
+        len
+    }
+
+    #[test]
+    fn test_from_msg() {
+        let mut buf = [0u8; 512];
+
+        for rcode in [RCode::NXDOMAIN, RCode::NOERROR] {
+            let len = response(&mut buf, rcode, None, true);
+            let na = NegativeAnswer::from_msg(&buf[2..len]).unwrap().unwrap();
+            assert_eq!(na.rcode, rcode);
+            let soa = na.soa.as_ref().unwrap();
+            assert_eq!(soa.name, "example.com.");
+            assert_eq!(soa.rdata[0].serial, 1);
+            assert_eq!(na.ttl(), Some(60));
+
+            let len = response(&mut buf, rcode, None, false);
+            let na = NegativeAnswer::from_msg(&buf[2..len]).unwrap().unwrap();
+            assert_eq!(na.rcode, rcode);
+            assert_eq!(na.soa, None);
+            assert_eq!(na.ttl(), None);
+        }
+
+        // the CNAME chain ends at a name with no records of the requested type
+        let len = response(&mut buf, RCode::NOERROR, Some(Type::CNAME), true);
+        let na = NegativeAnswer::from_msg(&buf[2..len]).unwrap().unwrap();
+        assert_eq!(na.soa.unwrap().name, "example.com.");
+
+        let len = response(&mut buf, RCode::NOERROR, Some(Type::A), false);
+        assert_eq!(NegativeAnswer::from_msg(&buf[2..len]).unwrap(), None);
+
+        let len = response(&mut buf, RCode::SERVFAIL, None, false);
+        assert_eq!(NegativeAnswer::from_msg(&buf[2..len]).unwrap(), None);
+    }
+}
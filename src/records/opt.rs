@@ -1,5 +1,109 @@
-cfg_any_client! {
-    use crate::{records::Type, Result};
+use crate::{
+    names::{InlineName, Name},
+    records::Type,
+    Error, Result,
+};
+use std::borrow::Cow;
+
+/// The option code of the name server identifier option ([RFC 5001]).
+///
+/// [RFC 5001]: https://www.rfc-editor.org/rfc/rfc5001.html
+const OPTION_CODE_NSID: u16 = 3;
+
+/// The option code of the DNSSEC algorithm understood option ([RFC 6975]).
+///
+/// [RFC 6975]: https://www.rfc-editor.org/rfc/rfc6975.html
+const OPTION_CODE_DAU: u16 = 5;
+
+/// The option code of the DS hash understood option ([RFC 6975]).
+///
+/// [RFC 6975]: https://www.rfc-editor.org/rfc/rfc6975.html
+const OPTION_CODE_DHU: u16 = 6;
+
+/// The option code of the NSEC3 hash understood option ([RFC 6975]).
+///
+/// [RFC 6975]: https://www.rfc-editor.org/rfc/rfc6975.html
+const OPTION_CODE_N3U: u16 = 7;
+
+/// The option code of the expire option ([RFC 7314]).
+///
+/// [RFC 7314]: https://www.rfc-editor.org/rfc/rfc7314.html
+const OPTION_CODE_EXPIRE: u16 = 9;
+
+/// The option code of the padding option ([RFC 7830]).
+///
+/// [RFC 7830]: https://www.rfc-editor.org/rfc/rfc7830.html
+const OPTION_CODE_PADDING: u16 = 12;
+
+/// The option code of the chain query requests option ([RFC 7901]).
+///
+/// [RFC 7901]: https://www.rfc-editor.org/rfc/rfc7901.html
+const OPTION_CODE_CHAIN: u16 = 13;
+
+/// An EDNS option ([RFC 6891 section 6.1.2]).
+///
+/// [RFC 6891 section 6.1.2]: https://www.rfc-editor.org/rfc/rfc6891.html#section-6.1.2
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct EdnsOption {
+    code: u16,
+    data: Vec<u8>,
+}
+
+impl EdnsOption {
+    /// The option code of the name server identifier option ([RFC 5001]).
+    ///
+    /// [RFC 5001]: https://www.rfc-editor.org/rfc/rfc5001.html
+    pub const NSID: u16 = OPTION_CODE_NSID;
+
+    /// The option code of the DNSSEC algorithm understood option ([RFC 6975]).
+    ///
+    /// [RFC 6975]: https://www.rfc-editor.org/rfc/rfc6975.html
+    pub const DAU: u16 = OPTION_CODE_DAU;
+
+    /// The option code of the DS hash understood option ([RFC 6975]).
+    ///
+    /// [RFC 6975]: https://www.rfc-editor.org/rfc/rfc6975.html
+    pub const DHU: u16 = OPTION_CODE_DHU;
+
+    /// The option code of the NSEC3 hash understood option ([RFC 6975]).
+    ///
+    /// [RFC 6975]: https://www.rfc-editor.org/rfc/rfc6975.html
+    pub const N3U: u16 = OPTION_CODE_N3U;
+
+    /// The option code of the expire option ([RFC 7314]).
+    ///
+    /// [RFC 7314]: https://www.rfc-editor.org/rfc/rfc7314.html
+    pub const EXPIRE: u16 = OPTION_CODE_EXPIRE;
+
+    /// The option code of the padding option ([RFC 7830]).
+    ///
+    /// [RFC 7830]: https://www.rfc-editor.org/rfc/rfc7830.html
+    pub const PADDING: u16 = OPTION_CODE_PADDING;
+
+    /// The option code of the chain query requests option ([RFC 7901]).
+    ///
+    /// [RFC 7901]: https://www.rfc-editor.org/rfc/rfc7901.html
+    pub const CHAIN: u16 = OPTION_CODE_CHAIN;
+
+    /// Creates an option with arbitrary code and data.
+    ///
+    /// See [`Opt::add_option`].
+    #[inline]
+    pub fn new(code: u16, data: Vec<u8>) -> Self {
+        Self { code, data }
+    }
+
+    /// Returns the option code.
+    #[inline]
+    pub fn code(&self) -> u16 {
+        self.code
+    }
+
+    /// Returns the option data.
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
 }
 
 /// OPT pseudo-record.
@@ -12,23 +116,31 @@ pub struct Opt {
     rcode_extension: u8,
     version: u8,
     flags: u16,
+    nsid: Option<Vec<u8>>,
+    dau: Option<Vec<u8>>,
+    dhu: Option<Vec<u8>>,
+    n3u: Option<Vec<u8>>,
+    expire: Option<Option<u32>>,
+    chain: Option<Name>,
+    custom: Vec<EdnsOption>,
+    padding_first: bool,
 }
 
 impl Opt {
-    cfg_any_client! {
-        #[allow(dead_code)]
-        #[inline]
-        pub(crate) fn new(version: u8, udp_payload_size: u16) -> Opt {
-            Opt {
-                udp_payload_size,
-                version,
-                ..Default::default()
-            }
+    /// Creates a new `OPT` pseudo-record.
+    ///
+    /// The `RCODE` extension and flags are cleared.
+    #[inline]
+    pub fn new(version: u8, udp_payload_size: u16) -> Opt {
+        Opt {
+            udp_payload_size,
+            version,
+            ..Default::default()
         }
+    }
 
-        fn ttl(&self) -> u32 {
-            ((self.rcode_extension as u32) << 24) | ((self.version as u32) << 16) | self.flags as u32
-        }
+    fn ttl(&self) -> u32 {
+        ((self.rcode_extension as u32) << 24) | ((self.version as u32) << 16) | self.flags as u32
     }
 
     #[inline]
@@ -38,6 +150,14 @@ impl Opt {
             rcode_extension: ((ttl & 0xFF000000u32) >> 24) as u8,
             version: ((ttl & 0x00FF0000u32) >> 16) as u8,
             flags: (ttl & 0x0000FFFF) as u16,
+            nsid: None,
+            dau: None,
+            dhu: None,
+            n3u: None,
+            expire: None,
+            chain: None,
+            custom: Vec::new(),
+            padding_first: false,
         }
     }
 
@@ -73,17 +193,519 @@ impl Opt {
     pub fn dnssec_ok(&self) -> bool {
         (self.flags & 0b1000_0000_0000_0000) != 0
     }
+
+    /// Checks that the record uses EDNS version `0` and no reserved flags.
+    ///
+    /// The only flag defined for EDNS version `0` is the `DO` bit ([RFC 3225]); the other
+    /// bits of the flags field must be zero ([RFC 6891 section 6.1.4]).
+    ///
+    /// [RFC 3225]: https://www.rfc-editor.org/rfc/rfc3225.html
+    /// [RFC 6891 section 6.1.4]: https://www.rfc-editor.org/rfc/rfc6891.html#section-6.1.4
+    pub(crate) fn check_strict(&self) -> Result<()> {
+        if self.version != 0 {
+            return Err(Error::UnsupportedEdnsVersion(self.version));
+        }
+        let reserved = self.flags & 0b0111_1111_1111_1111;
+        if reserved != 0 {
+            return Err(Error::ReservedEdnsFlags(reserved));
+        }
+        Ok(())
+    }
+
+    /// Returns the name server identifier ([RFC 5001]).
+    ///
+    /// In a query, an `NSID` option (which is always empty) requests the server to return its
+    /// identifier. In a response, the option holds the identifier of the server that
+    /// answered, often an ASCII string identifying a node of an anycast service.
+    ///
+    /// Returns `None` if the `NSID` option is absent.
+    ///
+    /// [RFC 5001]: https://www.rfc-editor.org/rfc/rfc5001.html
+    #[inline]
+    pub fn nsid(&self) -> Option<&[u8]> {
+        self.nsid.as_deref()
+    }
+
+    /// Requests the server to return its name server identifier ([RFC 5001]).
+    ///
+    /// When `request` is `true`, an empty `NSID` option is included in the record.
+    ///
+    /// See [`nsid`](Self::nsid) for more information.
+    ///
+    /// [RFC 5001]: https://www.rfc-editor.org/rfc/rfc5001.html
+    #[inline]
+    pub fn set_nsid_request(&mut self, request: bool) -> &mut Self {
+        self.nsid = if request { Some(Vec::new()) } else { None };
+        self
+    }
+
+    /// Returns the DNSSEC algorithms understood by the client ([RFC 6975]).
+    ///
+    /// The `DAU` option lists the numbers of the `DNSKEY`/`RRSIG` signing algorithms a
+    /// validating client understands, one octet each. It is sent in queries only, and lets
+    /// zone operators measure algorithm support before an algorithm rollover.
+    ///
+    /// Returns `None` if the `DAU` option is absent.
+    ///
+    /// [RFC 6975]: https://www.rfc-editor.org/rfc/rfc6975.html
+    #[inline]
+    pub fn dau(&self) -> Option<&[u8]> {
+        self.dau.as_deref()
+    }
+
+    /// Sets the DNSSEC algorithms understood by the client ([RFC 6975]).
+    ///
+    /// When `algorithms` is not `None`, a `DAU` option holding the algorithm numbers is
+    /// included in the record.
+    ///
+    /// See [`dau`](Self::dau) for more information.
+    ///
+    /// [RFC 6975]: https://www.rfc-editor.org/rfc/rfc6975.html
+    #[inline]
+    pub fn set_dau(&mut self, algorithms: Option<Vec<u8>>) -> &mut Self {
+        self.dau = algorithms;
+        self
+    }
+
+    /// Returns the `DS` hash algorithms understood by the client ([RFC 6975]).
+    ///
+    /// The `DHU` option lists the numbers of the `DS` digest algorithms a validating client
+    /// understands, one octet each.
+    ///
+    /// Returns `None` if the `DHU` option is absent.
+    ///
+    /// [RFC 6975]: https://www.rfc-editor.org/rfc/rfc6975.html
+    #[inline]
+    pub fn dhu(&self) -> Option<&[u8]> {
+        self.dhu.as_deref()
+    }
+
+    /// Sets the `DS` hash algorithms understood by the client ([RFC 6975]).
+    ///
+    /// When `algorithms` is not `None`, a `DHU` option holding the algorithm numbers is
+    /// included in the record.
+    ///
+    /// See [`dhu`](Self::dhu) for more information.
+    ///
+    /// [RFC 6975]: https://www.rfc-editor.org/rfc/rfc6975.html
+    #[inline]
+    pub fn set_dhu(&mut self, algorithms: Option<Vec<u8>>) -> &mut Self {
+        self.dhu = algorithms;
+        self
+    }
+
+    /// Returns the `NSEC3` hash algorithms understood by the client ([RFC 6975]).
+    ///
+    /// The `N3U` option lists the numbers of the `NSEC3` hash algorithms a validating client
+    /// understands, one octet each.
+    ///
+    /// Returns `None` if the `N3U` option is absent.
+    ///
+    /// [RFC 6975]: https://www.rfc-editor.org/rfc/rfc6975.html
+    #[inline]
+    pub fn n3u(&self) -> Option<&[u8]> {
+        self.n3u.as_deref()
+    }
+
+    /// Sets the `NSEC3` hash algorithms understood by the client ([RFC 6975]).
+    ///
+    /// When `algorithms` is not `None`, an `N3U` option holding the algorithm numbers is
+    /// included in the record.
+    ///
+    /// See [`n3u`](Self::n3u) for more information.
+    ///
+    /// [RFC 6975]: https://www.rfc-editor.org/rfc/rfc6975.html
+    #[inline]
+    pub fn set_n3u(&mut self, algorithms: Option<Vec<u8>>) -> &mut Self {
+        self.n3u = algorithms;
+        self
+    }
+
+    /// Returns the zone expire value in seconds ([RFC 7314]).
+    ///
+    /// In a response to a query with an `EXPIRE` option, an authoritative server of a zone
+    /// returns the number of seconds remaining before the zone expires, e.g. for a secondary
+    /// server, the time left until the zone data is considered stale.
+    ///
+    /// Returns `None` if the `EXPIRE` option is absent, or has no value, as in a query.
+    ///
+    /// [RFC 7314]: https://www.rfc-editor.org/rfc/rfc7314.html
+    #[inline]
+    pub fn expire(&self) -> Option<u32> {
+        self.expire.flatten()
+    }
+
+    /// Requests the server to return the zone expire value ([RFC 7314]).
+    ///
+    /// When `request` is `true`, an empty `EXPIRE` option is included in the record.
+    ///
+    /// See [`expire`](Self::expire) for more information.
+    ///
+    /// [RFC 7314]: https://www.rfc-editor.org/rfc/rfc7314.html
+    #[inline]
+    pub fn set_expire_request(&mut self, request: bool) -> &mut Self {
+        self.expire = if request { Some(None) } else { None };
+        self
+    }
+
+    /// Returns the closest trust point of a chain query request ([RFC 7901]).
+    ///
+    /// In a query, a `CHAIN` option asks a validating resolver to include in the response all
+    /// records needed to validate the answer, starting from the closest trust point known to
+    /// the client, e.g. the root zone. In a response, the option echoes the trust point the
+    /// chain of records starts from. The records themselves are found in the answer and
+    /// authority sections of the response.
+    ///
+    /// Returns `None` if the `CHAIN` option is absent, or holds a malformed domain name.
+    ///
+    /// [RFC 7901]: https://www.rfc-editor.org/rfc/rfc7901.html
+    #[inline]
+    pub fn chain(&self) -> Option<&Name> {
+        self.chain.as_ref()
+    }
+
+    /// Requests the server to return the chain of records from a trust point ([RFC 7901]).
+    ///
+    /// When `trust_point` is not `None`, a `CHAIN` option holding the domain name is included
+    /// in the record. The domain name is written in canonical form (lowercase).
+    ///
+    /// See [`chain`](Self::chain) for more information.
+    ///
+    /// [RFC 7901]: https://www.rfc-editor.org/rfc/rfc7901.html
+    #[inline]
+    pub fn set_chain_request(&mut self, trust_point: Option<Name>) -> &mut Self {
+        self.chain = trust_point;
+        self
+    }
+
+    /// Adds an arbitrary option to the record.
+    ///
+    /// This allows sending options not supported by *rsdns* explicitly, e.g. a
+    /// padding option ([RFC 7830]) or a cookie ([RFC 7873]). The options are written after
+    /// the supported ones, in the order they are added. See [`options`](Self::options) for
+    /// the placement of a padding option.
+    ///
+    /// Note that options not supported by *rsdns* are ignored when a record is read from
+    /// a message.
+    ///
+    /// [RFC 7830]: https://www.rfc-editor.org/rfc/rfc7830.html
+    /// [RFC 7873]: https://www.rfc-editor.org/rfc/rfc7873.html
+    #[inline]
+    pub fn add_option(&mut self, option: EdnsOption) -> &mut Self {
+        self.custom.push(option);
+        self
+    }
+
+    /// Sets the placement policy of the padding option.
+    ///
+    /// By default, a padding option is written after all other options, as padding is meant
+    /// to pad the message as a whole ([RFC 7830]). When `padding_last` is `false`, all options
+    /// are written in the order described in [`options`](Self::options), without moving
+    /// the padding option. This is useful to test server handling of unusual option placement.
+    ///
+    /// [RFC 7830]: https://www.rfc-editor.org/rfc/rfc7830.html
+    #[inline]
+    pub fn set_padding_last(&mut self, padding_last: bool) -> &mut Self {
+        self.padding_first = !padding_last;
+        self
+    }
+
+    /// Returns the options of the record, in the order they are written to a message.
+    ///
+    /// The options are ordered deterministically: the options supported by *rsdns*
+    /// (`NSID`, `DAU`, `DHU`, `N3U`, `EXPIRE`, `CHAIN`) come first, followed by the options
+    /// added with [`add_option`](Self::add_option) in the order they were added. A padding
+    /// option is moved to the end, unless disabled with
+    /// [`set_padding_last`](Self::set_padding_last).
+    pub fn options(&self) -> Vec<EdnsOption> {
+        self.iter_options()
+            .map(|(code, data)| EdnsOption {
+                code,
+                data: data.into_owned(),
+            })
+            .collect()
+    }
+
+    /// Iterates over the codes and data of the options, in the order of [`options`].
+    ///
+    /// The data is borrowed from the record, except for the `EXPIRE` and `CHAIN` options.
+    ///
+    /// [`options`]: Self::options
+    fn iter_options(&self) -> impl Iterator<Item = (u16, Cow<'_, [u8]>)> {
+        let nsid = self.nsid.as_deref().map(|nsid| (OPTION_CODE_NSID, nsid));
+        let algorithms = [
+            (OPTION_CODE_DAU, &self.dau),
+            (OPTION_CODE_DHU, &self.dhu),
+            (OPTION_CODE_N3U, &self.n3u),
+        ]
+        .into_iter()
+        .filter_map(|(code, algorithms)| algorithms.as_deref().map(|a| (code, a)));
+        let expire = self.expire.map(|expire| {
+            let data = match expire {
+                Some(v) => Cow::Owned(v.to_be_bytes().to_vec()),
+                None => Cow::Borrowed(&[][..]),
+            };
+            (OPTION_CODE_EXPIRE, data)
+        });
+        let chain = self
+            .chain
+            .as_ref()
+            .map(|chain| (OPTION_CODE_CHAIN, Cow::Owned(chain.to_canonical_wire())));
+
+        // only the custom options may be padding
+        let padding_last = !self.padding_first;
+        let custom = self
+            .custom
+            .iter()
+            .filter(move |o| !padding_last || o.code != OPTION_CODE_PADDING);
+        let padding = self
+            .custom
+            .iter()
+            .filter(move |o| padding_last && o.code == OPTION_CODE_PADDING);
+
+        nsid.into_iter()
+            .chain(algorithms)
+            .map(|(code, data)| (code, Cow::Borrowed(data)))
+            .chain(expire)
+            .chain(chain)
+            .chain(
+                custom
+                    .chain(padding)
+                    .map(|o| (o.code, Cow::Borrowed(o.data.as_slice()))),
+            )
+    }
+}
+
+impl crate::bytes::WCursor<'_> {
+    pub(crate) fn write_opt(&mut self, opt: &Opt) -> Result<()> {
+        self.u8(0)?; // DNAME
+        self.u16_be(Type::OPT.value())?; // TYPE
+        self.u16_be(opt.udp_payload_size)?; // CLASS
+        self.u32_be(opt.ttl())?; // TTL
+        let rdlen_pos = self.pos();
+        self.u16_be(0)?; // RDLEN, written after the options
+        for (code, data) in opt.iter_options() {
+            let len = u16::try_from(data.len())
+                .map_err(|_| Error::BadParam("EDNS option data exceeds 65535 bytes"))?;
+            self.u16_be(code)?;
+            self.u16_be(len)?;
+            self.bytes(&data)?;
+        }
+        let pos = self.pos();
+        let rdlen = u16::try_from(pos - rdlen_pos - 2)
+            .map_err(|_| Error::BadParam("OPT record data exceeds 65535 bytes"))?;
+        self.set_pos(rdlen_pos);
+        self.u16_be(rdlen)?;
+        self.set_pos(pos);
+        Ok(())
+    }
 }
 
-cfg_any_client! {
-    impl crate::bytes::WCursor<'_> {
-        pub(crate) fn write_opt(&mut self, opt: &Opt) -> Result<()> {
-            self.u8(0)?; // DNAME
-            self.u16_be(Type::OPT.value())?; // TYPE
-            self.u16_be(opt.udp_payload_size)?; // CLASS
-            self.u32_be(opt.ttl())?; // TTL
-            self.u16_be(0)?; // RDLEN
-            Ok(())
+impl crate::bytes::Cursor<'_> {
+    pub(crate) fn read_opt(&mut self, rclass: u16, ttl: u32, rdlen: usize) -> Result<Opt> {
+        let mut opt = Opt::from_msg(rclass, ttl);
+        self.window(rdlen)?;
+        while !self.is_empty() {
+            let code = self.u16_be()?;
+            let len = self.u16_be()? as usize;
+            let data = self.slice(len)?;
+            match code {
+                OPTION_CODE_NSID => opt.nsid = Some(data.to_vec()),
+                OPTION_CODE_DAU => opt.dau = Some(data.to_vec()),
+                OPTION_CODE_DHU => opt.dhu = Some(data.to_vec()),
+                OPTION_CODE_N3U => opt.n3u = Some(data.to_vec()),
+                OPTION_CODE_EXPIRE => {
+                    opt.expire = Some(<[u8; 4]>::try_from(data).ok().map(u32::from_be_bytes));
+                }
+                OPTION_CODE_CHAIN => {
+                    opt.chain = InlineName::from_wire(data).ok().map(Name::from);
+                }
+                _ => {}
+            }
         }
+        self.close_window()?;
+        Ok(opt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bytes::{Cursor, WCursor};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_write_nsid_request() {
+        let mut opt = Opt::new(0, 1232);
+        assert_eq!(opt.nsid(), None);
+        opt.set_nsid_request(true);
+        assert_eq!(opt.nsid(), Some(&[][..]));
+
+        let mut buf = [0u8; 32];
+        let mut wc = WCursor::new(&mut buf);
+        wc.write_opt(&opt).unwrap();
+        let len = wc.pos();
+        assert_eq!(
+            buf[..len],
+            [0, 0, 41, 0x04, 0xD0, 0, 0, 0, 0, 0, 4, 0, 3, 0, 0]
+        );
+
+        opt.set_nsid_request(false);
+        assert_eq!(opt.nsid(), None);
+    }
+
+    #[test]
+    fn test_options() {
+        let mut opt = Opt::new(0, 1232);
+        assert!(opt.options().is_empty());
+
+        opt.set_nsid_request(true);
+        let options = opt.options();
+        assert_eq!(options.len(), 1);
+        assert_eq!(options[0].code(), EdnsOption::NSID);
+        assert!(options[0].data().is_empty());
+    }
+
+    #[test]
+    fn test_expire() {
+        let mut opt = Opt::new(0, 1232);
+        opt.set_nsid_request(true).set_expire_request(true);
+        assert_eq!(opt.expire(), None);
+
+        let mut buf = [0u8; 32];
+        let mut wc = WCursor::new(&mut buf);
+        wc.write_opt(&opt).unwrap();
+        let len = wc.pos();
+        assert_eq!(
+            buf[..len],
+            [0, 0, 41, 0x04, 0xD0, 0, 0, 0, 0, 0, 8, 0, 3, 0, 0, 0, 9, 0, 0]
+        );
+
+        // NSID followed by EXPIRE with a value of 3600
+        let rdata = [0, 3, 0, 0, 0, 9, 0, 4, 0, 0, 0x0E, 0x10];
+        let mut c = Cursor::new(&rdata);
+        let opt = c.read_opt(1232, 0, rdata.len()).unwrap();
+        assert_eq!(opt.expire(), Some(3600));
+        assert_eq!(opt.options()[1].code(), EdnsOption::EXPIRE);
+        assert_eq!(opt.options()[1].data(), [0, 0, 0x0E, 0x10]);
+
+        let mut c = Cursor::new(&rdata[..4]);
+        let opt = c.read_opt(1232, 0, 4).unwrap();
+        assert_eq!(opt.expire(), None);
+    }
+
+    #[test]
+    fn test_chain() {
+        let mut opt = Opt::new(0, 1232);
+        opt.set_chain_request(Some(Name::from_str("Example.com").unwrap()));
+        assert_eq!(opt.chain().unwrap().as_str(), "Example.com.");
+
+        let mut buf = [0u8; 32];
+        let mut wc = WCursor::new(&mut buf);
+        wc.write_opt(&opt).unwrap();
+        let len = wc.pos();
+        assert_eq!(buf[len - 17..len - 13], [0, 13, 0, 13]);
+        assert_eq!(&buf[len - 13..len], b"\x07example\x03com\x00");
+
+        // the root zone trust point
+        let rdata = [0, 13, 0, 1, 0];
+        let mut c = Cursor::new(&rdata);
+        let opt = c.read_opt(1232, 0, rdata.len()).unwrap();
+        assert_eq!(opt.chain().unwrap().as_str(), ".");
+
+        // a malformed trust point is ignored
+        let rdata = [0, 13, 0, 2, 3, b'c', 0, 3, 0, 0];
+        let mut c = Cursor::new(&rdata);
+        let opt = c.read_opt(1232, 0, rdata.len()).unwrap();
+        assert_eq!(opt.chain(), None);
+        assert_eq!(opt.nsid(), Some(&[][..]));
+    }
+
+    #[test]
+    fn test_understood_algorithms() {
+        let mut opt = Opt::new(0, 1232);
+        opt.set_dau(Some(vec![8, 13, 15]))
+            .set_dhu(Some(vec![2]))
+            .set_n3u(Some(vec![1]))
+            .set_expire_request(true);
+        assert_eq!(opt.dau(), Some(&[8, 13, 15][..]));
+
+        let codes: Vec<u16> = opt.options().iter().map(|o| o.code()).collect();
+        assert_eq!(
+            codes,
+            [
+                EdnsOption::DAU,
+                EdnsOption::DHU,
+                EdnsOption::N3U,
+                EdnsOption::EXPIRE
+            ]
+        );
+
+        let rdata = [0, 5, 0, 2, 8, 13, 0, 6, 0, 1, 2, 0, 7, 0, 1, 1];
+        let mut c = Cursor::new(&rdata);
+        let opt = c.read_opt(1232, 0, rdata.len()).unwrap();
+        assert_eq!(opt.dau(), Some(&[8, 13][..]));
+        assert_eq!(opt.dhu(), Some(&[2][..]));
+        assert_eq!(opt.n3u(), Some(&[1][..]));
+
+        let mut opt = Opt::new(0, 1232);
+        opt.set_dau(Some(vec![8])).set_dau(None);
+        assert_eq!(opt.dau(), None);
+        assert!(opt.options().is_empty());
+    }
+
+    #[test]
+    fn test_option_order() {
+        let mut opt = Opt::new(0, 1232);
+        opt.add_option(EdnsOption::new(EdnsOption::PADDING, vec![0; 4]))
+            .add_option(EdnsOption::new(10, vec![1; 8]))
+            .set_nsid_request(true);
+
+        let codes: Vec<u16> = opt.options().iter().map(|o| o.code()).collect();
+        assert_eq!(codes, [EdnsOption::NSID, 10, EdnsOption::PADDING]);
+
+        let mut buf = [0u8; 64];
+        let mut wc = WCursor::new(&mut buf);
+        wc.write_opt(&opt).unwrap();
+        let len = wc.pos();
+        assert_eq!(buf[len - 8..len], [0, 12, 0, 4, 0, 0, 0, 0]);
+
+        opt.set_padding_last(false);
+        let codes: Vec<u16> = opt.options().iter().map(|o| o.code()).collect();
+        assert_eq!(codes, [EdnsOption::NSID, EdnsOption::PADDING, 10]);
+    }
+
+    #[test]
+    fn test_write_oversized() {
+        let mut buf = vec![0u8; 3 * 65536];
+
+        let mut opt = Opt::new(0, 1232);
+        opt.add_option(EdnsOption::new(10, vec![0; 65536]));
+        let mut wc = WCursor::new(&mut buf);
+        assert!(matches!(wc.write_opt(&opt), Err(Error::BadParam(_))));
+
+        let mut opt = Opt::new(0, 1232);
+        opt.add_option(EdnsOption::new(10, vec![0; 40000]))
+            .add_option(EdnsOption::new(10, vec![0; 40000]));
+        let mut wc = WCursor::new(&mut buf);
+        assert!(matches!(wc.write_opt(&opt), Err(Error::BadParam(_))));
+    }
+
+    #[test]
+    fn test_read_nsid() {
+        // a cookie option followed by NSID "ns1"
+        let rdata = [0, 10, 0, 2, 1, 2, 0, 3, 0, 3, b'n', b's', b'1'];
+        let mut c = Cursor::new(&rdata);
+        let opt = c.read_opt(1232, 0, rdata.len()).unwrap();
+        assert_eq!(opt.udp_payload_size(), 1232);
+        assert_eq!(opt.nsid(), Some(&b"ns1"[..]));
+        assert!(c.is_empty());
+
+        let mut c = Cursor::new(&rdata[..4]);
+        let opt = c.read_opt(1232, 0, 0).unwrap();
+        assert_eq!(opt.nsid(), None);
+
+        let mut c = Cursor::new(&rdata);
+        assert!(c.read_opt(1232, 0, 12).is_err());
     }
 }
@@ -1,6 +1,8 @@
 use crate::{
-    names::InlineName,
+    bytes::WCursor,
+    names::{wire_length, InlineName},
     records::{data::RecordData, Class, Type},
+    Error, Result,
 };
 
 /// A resource record.
@@ -22,3 +24,275 @@ pub struct ResourceRecord {
     /// The record data.
     pub rdata: RecordData,
 }
+
+impl std::fmt::Display for ResourceRecord {
+    /// Formats the record in zone file style: name, TTL, class, type and data.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rsdns::{
+    ///     names::InlineName,
+    ///     records::{data::{Mx, RecordData}, Class, ResourceRecord, Type},
+    /// };
+    /// use std::str::FromStr;
+    ///
+    /// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let rr = ResourceRecord {
+    ///     name: InlineName::from_str("example.com")?,
+    ///     rclass: Class::IN,
+    ///     rtype: Type::MX,
+    ///     ttl: 300,
+    ///     rdata: RecordData::Mx(Mx {
+    ///         preference: 10,
+    ///         exchange: "mail.example.com".parse()?,
+    ///     }),
+    /// };
+    /// assert_eq!(rr.to_string(), "example.com. 300 IN MX 10 mail.example.com.");
+    /// # Ok(())
+    /// # }
+    /// # foo().unwrap();
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {}",
+            self.name, self.ttl, self.rclass, self.rtype, self.rdata
+        )
+    }
+}
+
+impl ResourceRecord {
+    /// Compares two records ignoring the TTL.
+    ///
+    /// Records are considered equal if they have the same name, class, type and data.
+    /// This is useful for detecting content changes between two snapshots of a zone,
+    /// where TTL may legitimately differ.
+    ///
+    /// Note that the [`PartialEq`] implementation compares all fields, including the TTL.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsdns::{
+    ///     names::InlineName,
+    ///     records::{data::{RecordData, A}, Class, ResourceRecord, Type},
+    /// };
+    /// use std::{net::Ipv4Addr, str::FromStr};
+    ///
+    /// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let rr1 = ResourceRecord {
+    ///     name: InlineName::from_str("example.com")?,
+    ///     rclass: Class::IN,
+    ///     rtype: Type::A,
+    ///     ttl: 300,
+    ///     rdata: RecordData::A(A::from(Ipv4Addr::new(192, 0, 2, 1))),
+    /// };
+    /// let rr2 = ResourceRecord { ttl: 60, ..rr1.clone() };
+    ///
+    /// assert_ne!(rr1, rr2);
+    /// assert!(rr1.equal_ignoring_ttl(&rr2));
+    /// # Ok(())
+    /// # }
+    /// # foo().unwrap();
+    /// ```
+    pub fn equal_ignoring_ttl(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.rclass == other.rclass
+            && self.rtype == other.rtype
+            && self.rdata == other.rdata
+    }
+
+    /// Returns the length (in bytes) of the record in wire format.
+    ///
+    /// This is the length of the data written by [`encode_to`](Self::encode_to).
+    pub fn encoded_len(&self) -> usize {
+        wire_length(self.name.as_str()) + 10 + self.rdata.encoded_len()
+    }
+
+    /// Writes the record in wire format into `buf`.
+    ///
+    /// The record is written with its name, type, class, TTL and data, and is suitable for
+    /// inclusion in one of the record sections of a message. Together with
+    /// [`MessageReader::next_record`] it allows a record to be parsed, modified and written back.
+    ///
+    /// # Domain name compression
+    ///
+    /// Domain names, both the record name and those embedded in the record data, are written
+    /// uncompressed. Compression of names in the record data is allowed only for the
+    /// well-known record types of [RFC 1035], and is forbidden for all other types
+    /// ([RFC 3597 section 4]). Compression is never required, so writing all names
+    /// uncompressed is correct for every record type, at the cost of a larger message.
+    ///
+    /// Returns the number of bytes written, which equals [`encoded_len`](Self::encoded_len).
+    ///
+    /// # Returns
+    ///
+    /// - [`Error::BufferTooShort`] - if `buf` is too short
+    /// - [`Error::BadParam`] - if the record data is invalid or exceeds 65535 bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsdns::{
+    ///     names::{InlineName, Name},
+    ///     records::{data::{Cname, RecordData}, Class, ResourceRecord, Type},
+    /// };
+    /// use std::str::FromStr;
+    ///
+    /// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let rr = ResourceRecord {
+    ///     name: InlineName::from_str("www.example.com")?,
+    ///     rclass: Class::IN,
+    ///     rtype: Type::CNAME,
+    ///     ttl: 300,
+    ///     rdata: RecordData::Cname(Cname { cname: Name::from_str("example.com")? }),
+    /// };
+    ///
+    /// let mut buf = [0u8; 512];
+    /// let len = rr.encode_to(&mut buf)?;
+    /// assert_eq!(len, 17 + 10 + 13);
+    /// assert_eq!(len, rr.encoded_len());
+    /// # Ok(())
+    /// # }
+    /// # foo().unwrap();
+    /// ```
+    ///
+    /// [`MessageReader::next_record`]: crate::message::reader::MessageReader::next_record
+    /// [RFC 1035]: https://www.rfc-editor.org/rfc/rfc1035.html
+    /// [RFC 3597 section 4]: https://www.rfc-editor.org/rfc/rfc3597.html#section-4
+    pub fn encode_to(&self, buf: &mut [u8]) -> Result<usize> {
+        let rdlen = self.rdata.encoded_len();
+        if rdlen > u16::MAX as usize {
+            return Err(Error::BadParam("record data exceeds 65535 bytes"));
+        }
+
+        let mut c = WCursor::new(buf);
+        c.write_domain_name(self.name.as_str())?;
+        c.u16_be(self.rtype.value())?;
+        c.u16_be(self.rclass.value())?;
+        c.u32_be(self.ttl)?;
+        c.u16_be(rdlen as u16)?;
+
+        let pos = c.pos();
+        let written = self.rdata.encode_to(&mut buf[pos..])?;
+        Ok(pos + written)
+    }
+}
+
+/// Checks if the answer to an `ANY` query is a minimal response.
+///
+/// A server may refuse to answer `ANY` queries in full, and respond instead with a single
+/// synthesized `HINFO` record ([RFC 8482]). Such an answer doesn't reflect the records of the
+/// queried name, and shouldn't be interpreted as real data.
+///
+/// Returns `true` if `records` consists of a single `HINFO` record with the CPU field set to
+/// `"RFC8482"` (see [`Hinfo::is_rfc8482`]).
+///
+/// [RFC 8482]: https://www.rfc-editor.org/rfc/rfc8482.html
+/// [`Hinfo::is_rfc8482`]: crate::records::data::Hinfo::is_rfc8482
+pub fn is_rfc8482_answer(records: &[ResourceRecord]) -> bool {
+    match records {
+        [record] => matches!(&record.rdata, RecordData::Hinfo(hinfo) if hinfo.is_rfc8482()),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        bytes::Writer,
+        message::{reader::MessageReader, Header, RecordsSection},
+        names::Name,
+        records::data::{Hinfo, Mx, A},
+    };
+    use std::{net::Ipv4Addr, str::FromStr};
+
+    #[test]
+    fn test_is_rfc8482_answer() {
+        let hinfo = |cpu: &[u8]| ResourceRecord {
+            name: InlineName::from_str("example.com").unwrap(),
+            rclass: Class::IN,
+            rtype: Type::HINFO,
+            ttl: 3600,
+            rdata: RecordData::Hinfo(Hinfo {
+                cpu: cpu.to_vec(),
+                os: Vec::new(),
+            }),
+        };
+        let a = ResourceRecord {
+            name: InlineName::from_str("example.com").unwrap(),
+            rclass: Class::IN,
+            rtype: Type::A,
+            ttl: 300,
+            rdata: RecordData::A(A::from(Ipv4Addr::new(192, 0, 2, 1))),
+        };
+
+        assert!(is_rfc8482_answer(&[hinfo(b"RFC8482")]));
+        assert!(!is_rfc8482_answer(&[hinfo(b"INTEL-386")]));
+        assert!(!is_rfc8482_answer(&[hinfo(b"RFC8482"), a.clone()]));
+        assert!(!is_rfc8482_answer(&[a]));
+        assert!(!is_rfc8482_answer(&[]));
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        let records = [
+            ResourceRecord {
+                name: InlineName::from_str("example.com").unwrap(),
+                rclass: Class::IN,
+                rtype: Type::A,
+                ttl: 300,
+                rdata: RecordData::A(A::from(Ipv4Addr::new(192, 0, 2, 1))),
+            },
+            ResourceRecord {
+                name: InlineName::from_str("example.com").unwrap(),
+                rclass: Class::IN,
+                rtype: Type::MX,
+                ttl: 3600,
+                rdata: RecordData::Mx(Mx {
+                    preference: 10,
+                    exchange: Name::from_str("mail.example.com").unwrap(),
+                }),
+            },
+        ];
+
+        let mut msg = [0u8; 512];
+        let header = Header {
+            an_count: records.len() as u16,
+            ..Default::default()
+        };
+        let mut len = WCursor::new(&mut msg).write(&header).unwrap();
+        for rr in &records {
+            let written = rr.encode_to(&mut msg[len..]).unwrap();
+            assert_eq!(written, rr.encoded_len());
+            len += written;
+        }
+
+        let mut mr = MessageReader::new(&msg[..len]).unwrap();
+        mr.header().unwrap();
+        for rr in &records {
+            let (section, parsed) = mr.next_record().unwrap().unwrap();
+            assert_eq!(section, RecordsSection::Answer);
+            assert_eq!(&parsed, rr);
+        }
+        assert!(mr.next_record().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_encode_buffer_too_short() {
+        let rr = ResourceRecord {
+            name: InlineName::from_str("example.com").unwrap(),
+            rclass: Class::IN,
+            rtype: Type::A,
+            ttl: 300,
+            rdata: RecordData::A(A::from(Ipv4Addr::new(192, 0, 2, 1))),
+        };
+        let mut buf = [0u8; 64];
+        for len in 0..rr.encoded_len() {
+            assert!(rr.encode_to(&mut buf[..len]).is_err());
+        }
+        assert!(rr.encode_to(&mut buf[..rr.encoded_len()]).is_ok());
+    }
+}
@@ -4,7 +4,10 @@ use crate::{
         MessageType, RCode, RecordsSection,
     },
     names::Name,
-    records::{data::RData, Class, Opt, Type},
+    records::{
+        data::{RData, Txt},
+        Class, Opt, Type,
+    },
     Error, Result,
 };
 
@@ -33,10 +36,98 @@ pub struct RecordSet<D: RData> {
     pub rdata: Vec<D>,
 }
 
+/// The result of parsing a [`RecordSet`] from a response message.
+pub(crate) enum RrsetOrCname<D: RData> {
+    /// The record set was found.
+    Rrset(RecordSet<D>),
+    /// The *CNAME chain* ends at this name, which has no records of the requested type.
+    #[allow(dead_code)] // clients module may be disabled
+    Cname(Name),
+}
+
 impl<D: RData> RecordSet<D> {
     /// Record type as associated constant.
     pub const RTYPE: Type = D::RTYPE;
 
+    /// Compares two record sets ignoring the order of records and the TTL.
+    ///
+    /// The record data of both sets is compared as a multiset, i.e. the sets are equal if they
+    /// have the same name and class, and contain the same record data the same number of times.
+    /// This is useful to compare answers to the same question obtained from different
+    /// nameservers, where the order of records and the remaining TTL usually differ.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rsdns::records::{data::A, Class, RecordSet};
+    /// use std::net::Ipv4Addr;
+    ///
+    /// let rrset = |ttl, addrs: &[Ipv4Addr]| RecordSet {
+    ///     name: "example.com.".parse().unwrap(),
+    ///     rclass: Class::IN,
+    ///     ttl,
+    ///     rdata: addrs.iter().map(|a| A { address: *a }).collect(),
+    /// };
+    ///
+    /// let a = rrset(300, &[Ipv4Addr::new(192, 0, 2, 1), Ipv4Addr::new(192, 0, 2, 2)]);
+    /// let b = rrset(120, &[Ipv4Addr::new(192, 0, 2, 2), Ipv4Addr::new(192, 0, 2, 1)]);
+    ///
+    /// assert_ne!(a, b);
+    /// assert!(a.equal_unordered(&b));
+    /// ```
+    pub fn equal_unordered(&self, other: &Self) -> bool {
+        if self.name != other.name
+            || self.rclass != other.rclass
+            || self.rdata.len() != other.rdata.len()
+        {
+            return false;
+        }
+
+        let mut lhs: Vec<&D> = self.rdata.iter().collect();
+        let mut rhs: Vec<&D> = other.rdata.iter().collect();
+        lhs.sort_unstable();
+        rhs.sort_unstable();
+        lhs == rhs
+    }
+
+    /// Removes duplicate record data, keeping the first occurrence of each.
+    ///
+    /// Misconfigured nameservers may return the same record more than once. Since the record set
+    /// already holds the minimum TTL of its records, only the record data is compared.
+    /// The order of the remaining records is preserved.
+    ///
+    /// De-duplication is never done implicitly while parsing a message.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rsdns::records::{data::A, Class, RecordSet};
+    /// use std::net::Ipv4Addr;
+    ///
+    /// let mut rrset = RecordSet {
+    ///     name: "example.com.".parse().unwrap(),
+    ///     rclass: Class::IN,
+    ///     ttl: 60,
+    ///     rdata: [1, 2, 1]
+    ///         .into_iter()
+    ///         .map(|i| A { address: Ipv4Addr::new(192, 0, 2, i) })
+    ///         .collect(),
+    /// };
+    ///
+    /// rrset.dedup();
+    /// assert_eq!(rrset.rdata.len(), 2);
+    /// ```
+    pub fn dedup(&mut self) {
+        let mut i = 1;
+        while i < self.rdata.len() {
+            if self.rdata[..i].contains(&self.rdata[i]) {
+                self.rdata.remove(i);
+            } else {
+                i += 1;
+            }
+        }
+    }
+
     /// Parses a [`RecordSet`] from a response message.
     ///
     /// This method performs *CNAME flattening*, which is the process of traversing a *chain* of
@@ -47,8 +138,25 @@ impl<D: RData> RecordSet<D> {
     /// creating a *chain*. The record set belongs to the last name in the *chain*,
     /// which is reflected in the returned record set's [`name`](RecordSet::name) attribute.
     ///
+    /// # Returns
+    ///
+    /// - [`Error::MultipleOptRecords`] - if the message contains more than one `OPT`
+    ///   pseudo-record ([RFC 6891 section 6.1.1])
+    ///
     /// [`CNAME`]: crate::records::Type::CNAME
+    /// [RFC 6891 section 6.1.1]: https://www.rfc-editor.org/rfc/rfc6891.html#section-6.1.1
     pub fn from_msg(msg: &[u8]) -> Result<Self> {
+        match Self::from_msg_or_cname(msg)? {
+            RrsetOrCname::Rrset(rrset) => Ok(rrset),
+            RrsetOrCname::Cname(_) => Err(Error::NoAnswer),
+        }
+    }
+
+    /// Parses a [`RecordSet`] from a response message, like [`from_msg`](Self::from_msg).
+    ///
+    /// If the *CNAME chain* ends at a name with no records of the requested type,
+    /// the last name in the chain is returned instead of [`Error::NoAnswer`].
+    pub(crate) fn from_msg_or_cname(msg: &[u8]) -> Result<RrsetOrCname<D>> {
         let mut mr = MessageReader::new(msg)?;
         let header = mr.header()?;
 
@@ -77,6 +185,7 @@ impl<D: RData> RecordSet<D> {
 
         let rclass = question.qclass;
         let mut name = question.qname;
+        let mut cname_found = false;
 
         let mut rrset = loop {
             match Self::extract_rrset(&mr, &mut headers, &name, rclass)? {
@@ -84,6 +193,9 @@ impl<D: RData> RecordSet<D> {
                 None => {
                     if let Some(n) = Self::extract_cname(&mr, &mut headers, &name, rclass)? {
                         name = n;
+                        cname_found = true;
+                    } else if cname_found {
+                        return Ok(RrsetOrCname::Cname(Name::try_from(name)?));
                     } else {
                         return Err(Error::NoAnswer);
                     }
@@ -92,7 +204,7 @@ impl<D: RData> RecordSet<D> {
         };
 
         rrset.name = Name::try_from(name)?;
-        Ok(rrset)
+        Ok(RrsetOrCname::Rrset(rrset))
     }
 
     #[inline(always)]
@@ -166,8 +278,10 @@ impl<D: RData> RecordSet<D> {
         while mr.has_records() {
             let marker = mr.record_marker()?;
             if marker.rtype == Type::OPT {
+                if opt.is_some() {
+                    return Err(Error::MultipleOptRecords);
+                }
                 opt = Some(mr.opt_record(&marker)?);
-                break;
             } else {
                 mr.skip_record_data(&marker)?;
             }
@@ -175,3 +289,287 @@ impl<D: RData> RecordSet<D> {
         Ok(opt)
     }
 }
+
+impl RecordSet<Txt> {
+    /// Returns the text of every record in the set.
+    ///
+    /// The character-strings of a record are concatenated, as read into [`Txt::text`],
+    /// and every record is returned separately in the order of the set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rsdns::records::{data::Txt, Class, RecordSet};
+    ///
+    /// let rrset = RecordSet {
+    ///     name: "example.com.".parse().unwrap(),
+    ///     rclass: Class::IN,
+    ///     ttl: 300,
+    ///     rdata: vec![Txt::from_bytes(b"v=spf1 -all")?, Txt::from_bytes(b"hello")?],
+    /// };
+    ///
+    /// assert_eq!(rrset.all_strings(), [b"v=spf1 -all".to_vec(), b"hello".to_vec()]);
+    /// # Ok::<(), rsdns::Error>(())
+    /// ```
+    pub fn all_strings(&self) -> Vec<Vec<u8>> {
+        self.rdata.iter().map(|txt| txt.text.clone()).collect()
+    }
+
+    /// Returns the SPF record of the set.
+    ///
+    /// An SPF record is a record with text starting with the version section `v=spf1`,
+    /// followed by a space or the end of the text. The version section is compared
+    /// case-insensitively. Other records are ignored.
+    ///
+    /// Returns `None` if no SPF record is found.
+    ///
+    /// [RFC 7208 section 4.5](https://www.rfc-editor.org/rfc/rfc7208.html#section-4.5)
+    ///
+    /// # Returns
+    ///
+    /// - [`Error::MultipleSpfRecords`] - if the set contains more than one SPF record,
+    ///   which is a permanent error of SPF evaluation
+    pub fn spf_record(&self) -> Result<Option<&[u8]>> {
+        const VERSION: &[u8] = b"v=spf1";
+
+        let mut records = self
+            .rdata
+            .iter()
+            .map(|txt| txt.text.as_slice())
+            .filter(|text| {
+                text.len() >= VERSION.len()
+                    && text[..VERSION.len()].eq_ignore_ascii_case(VERSION)
+                    && matches!(text.get(VERSION.len()), None | Some(b' '))
+            });
+
+        let spf = records.next();
+        match records.count() {
+            0 => Ok(spf),
+            n => Err(Error::MultipleSpfRecords(n + 1)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        message::{QueryWriter, ResponseBuilder},
+        records::{
+            data::{Cname, Ns, A},
+            Type,
+        },
+    };
+    use std::{net::Ipv4Addr, str::FromStr};
+
+    fn rrset(name: &str, ttl: u32, addrs: &[[u8; 4]]) -> RecordSet<A> {
+        RecordSet {
+            name: Name::from_str(name).unwrap(),
+            rclass: Class::IN,
+            ttl,
+            rdata: addrs
+                .iter()
+                .map(|a| A {
+                    address: Ipv4Addr::from(*a),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_equal_unordered() {
+        let a = rrset("example.com.", 300, &[[192, 0, 2, 1], [192, 0, 2, 2]]);
+        let b = rrset("example.com.", 60, &[[192, 0, 2, 2], [192, 0, 2, 1]]);
+        assert!(a.equal_unordered(&b));
+        assert!(b.equal_unordered(&a));
+        assert!(a.equal_unordered(&a));
+
+        let c = rrset("example.org.", 300, &[[192, 0, 2, 1], [192, 0, 2, 2]]);
+        assert!(!a.equal_unordered(&c));
+
+        let d = rrset("example.com.", 300, &[[192, 0, 2, 1]]);
+        assert!(!a.equal_unordered(&d));
+
+        // multiset semantics: duplicates are counted
+        let e = rrset("example.com.", 300, &[[192, 0, 2, 1], [192, 0, 2, 1]]);
+        assert!(!a.equal_unordered(&e));
+
+        let mut f = a.clone();
+        f.rclass = Class::CH;
+        assert!(!a.equal_unordered(&f));
+    }
+
+    #[test]
+    fn test_dedup() {
+        let mut a = rrset(
+            "example.com.",
+            60,
+            &[
+                [192, 0, 2, 2],
+                [192, 0, 2, 1],
+                [192, 0, 2, 2],
+                [192, 0, 2, 2],
+            ],
+        );
+        a.dedup();
+        assert_eq!(
+            a,
+            rrset("example.com.", 60, &[[192, 0, 2, 2], [192, 0, 2, 1]])
+        );
+
+        let mut b = rrset("example.com.", 60, &[]);
+        b.dedup();
+        assert!(b.rdata.is_empty());
+    }
+
+    fn response(buf: &mut [u8], cnames: &[(&str, &str)], addr: Option<(&str, [u8; 4])>) -> usize {
+        let mut qbuf = [0u8; 512];
+        let len = QueryWriter::new(&mut qbuf)
+            .write("a.example.com", Type::A, Class::IN, true, None)
+            .unwrap();
+
+        let mut rb = ResponseBuilder::new(buf, &qbuf[2..len]).unwrap();
+        for (name, cname) in cnames {
+            let rdata = Cname {
+                cname: Name::from_str(cname).unwrap(),
+            };
+            rb.add_answer(name, Class::IN, 300, &rdata).unwrap();
+        }
+        if let Some((name, a)) = addr {
+            rb.add_answer(name, Class::IN, 300, &A::from(Ipv4Addr::from(a)))
+                .unwrap();
+        }
+        rb.finish().unwrap()
+    }
+
+    #[test]
+    fn test_from_msg_or_cname() {
+        let mut buf = [0u8; 512];
+
+        let chain = [
+            ("a.example.com", "b.example.com."),
+            ("b.example.com", "c.example.net."),
+        ];
+        let len = response(&mut buf, &chain, Some(("c.example.net", [192, 0, 2, 1])));
+        match RecordSet::<A>::from_msg_or_cname(&buf[2..len]).unwrap() {
+            RrsetOrCname::Rrset(rrset) => {
+                assert_eq!(rrset.name.as_str(), "c.example.net.");
+                assert_eq!(rrset.rdata, [A::from(Ipv4Addr::new(192, 0, 2, 1))]);
+            }
+            RrsetOrCname::Cname(_) => panic!("rrset expected"),
+        }
+
+        let len = response(&mut buf, &chain, None);
+        match RecordSet::<A>::from_msg_or_cname(&buf[2..len]).unwrap() {
+            RrsetOrCname::Cname(name) => assert_eq!(name.as_str(), "c.example.net."),
+            RrsetOrCname::Rrset(_) => panic!("cname expected"),
+        }
+        assert!(matches!(
+            RecordSet::<A>::from_msg(&buf[2..len]),
+            Err(Error::NoAnswer)
+        ));
+
+        let len = response(&mut buf, &[], None);
+        assert!(matches!(
+            RecordSet::<A>::from_msg_or_cname(&buf[2..len]),
+            Err(Error::NoAnswer)
+        ));
+    }
+
+    #[test]
+    fn test_from_msg_root_ns() {
+        let mut qbuf = [0u8; 512];
+        let len = QueryWriter::new(&mut qbuf)
+            .write("", Type::NS, Class::IN, true, None)
+            .unwrap();
+
+        let mut buf = [0u8; 512];
+        let mut rb = ResponseBuilder::new(&mut buf, &qbuf[2..len]).unwrap();
+        for ns in ["a.root-servers.net.", "b.root-servers.net."] {
+            let rdata = Ns {
+                nsdname: Name::from_str(ns).unwrap(),
+            };
+            rb.add_answer(".", Class::IN, 518400, &rdata).unwrap();
+        }
+        let len = rb.finish().unwrap();
+
+        let rrset = RecordSet::<Ns>::from_msg(&buf[2..len]).unwrap();
+        assert_eq!(rrset.name, ".");
+        assert_eq!(rrset.ttl, 518400);
+        assert_eq!(rrset.rdata.len(), 2);
+        assert_eq!(rrset.rdata[1].nsdname, "b.root-servers.net.");
+    }
+
+    #[test]
+    fn test_from_msg_multiple_opt() {
+        const OPT: [u8; 11] = [0, 0, 41, 4, 208, 0, 0, 0, 0, 0, 0];
+
+        let mut qbuf = [0u8; 512];
+        let len = QueryWriter::new(&mut qbuf)
+            .write("example.com", Type::A, Class::IN, true, None)
+            .unwrap();
+
+        let mut buf = [0u8; 512];
+        let mut rb = ResponseBuilder::new(&mut buf, &qbuf[2..len]).unwrap();
+        rb.add_answer(
+            "example.com",
+            Class::IN,
+            300,
+            &A::from(Ipv4Addr::new(192, 0, 2, 1)),
+        )
+        .unwrap();
+        let mut len = rb.finish().unwrap();
+
+        for count in 1..=2 {
+            buf[len..len + OPT.len()].copy_from_slice(&OPT);
+            len += OPT.len();
+            buf[2 + 11] = count;
+            let res = RecordSet::<A>::from_msg(&buf[2..len]);
+            if count == 1 {
+                assert_eq!(res.unwrap().rdata.len(), 1);
+            } else {
+                assert!(matches!(res, Err(Error::MultipleOptRecords)), "{res:?}");
+            }
+        }
+    }
+
+    fn txt_rrset(texts: &[&[u8]]) -> RecordSet<Txt> {
+        RecordSet {
+            name: Name::from_str("example.com.").unwrap(),
+            rclass: Class::IN,
+            ttl: 300,
+            rdata: texts.iter().map(|t| Txt::from_bytes(t).unwrap()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_all_strings() {
+        assert!(txt_rrset(&[]).all_strings().is_empty());
+
+        let long = [b'x'; 300];
+        let rrset = txt_rrset(&[b"", &long, b"hello"]);
+        assert_eq!(
+            rrset.all_strings(),
+            [b"".to_vec(), long.to_vec(), b"hello".to_vec()]
+        );
+    }
+
+    #[test]
+    fn test_spf_record() {
+        assert_eq!(txt_rrset(&[]).spf_record().unwrap(), None);
+
+        for text in [&b"v=spf1 -all"[..], b"V=SPF1 -all", b"v=spf1"] {
+            let rrset = txt_rrset(&[b"google-site-verification=x", text]);
+            assert_eq!(rrset.spf_record().unwrap(), Some(text));
+        }
+
+        let rrset = txt_rrset(&[b"v=spf10 -all", b"v=spf", b"xv=spf1 -all", b"spf1"]);
+        assert_eq!(rrset.spf_record().unwrap(), None);
+
+        let rrset = txt_rrset(&[b"v=spf1 -all", b"hello", b"v=spf1 +all"]);
+        assert!(matches!(
+            rrset.spf_record(),
+            Err(Error::MultipleSpfRecords(2))
+        ));
+    }
+}
@@ -0,0 +1,229 @@
+use crate::{
+    message::{reader::MessageIterator, MessageType, RCode, RecordsSection},
+    names::{is_subdomain_of, InlineName, Name},
+    records::data::RecordData,
+    Error, Result,
+};
+use std::net::IpAddr;
+
+/// A delegation of a zone to its nameservers.
+///
+/// A nameserver which is not authoritative for a queried name, but knows the zone the name
+/// is delegated to, responds with a referral: the `NS` records of the child zone in the
+/// authority section, and the addresses of the nameservers (glue) in the additional section.
+/// Following referrals from the root zone down to the zone of a name reproduces the iterative
+/// resolution process, as done by `dig +trace`.
+///
+/// [RFC 1034 section 4.3.1](https://www.rfc-editor.org/rfc/rfc1034.html#section-4.3.1)
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Referral {
+    /// The delegated zone, i.e. the owner name of the `NS` records.
+    pub zone: Name,
+
+    /// The nameservers of the zone, each with the glue addresses found in the response.
+    ///
+    /// A nameserver without glue has an empty list of addresses. Its addresses must be
+    /// resolved separately.
+    pub nameservers: Vec<(Name, Vec<IpAddr>)>,
+}
+
+impl Referral {
+    /// Parses a [`Referral`] from a response message.
+    ///
+    /// The `NS` records are taken from the authority section. An authoritative answer to an
+    /// `NS` query holds the `NS` records in the answer section, and is parsed as a referral as
+    /// well, which is the last step of a delegation chain.
+    ///
+    /// Only `NS` records owned by the question name or one of its ancestors are accepted,
+    /// as a nameserver can't delegate an unrelated zone. Similarly, glue addresses are accepted
+    /// only for nameservers within the delegated zone (in-bailiwick). The addresses of other
+    /// nameservers must be resolved separately.
+    ///
+    /// Returns [`Error::BadResponseCode`] if the response code is not `NOERROR`, and `None`
+    /// if the response has no matching `NS` records in the answer and authority sections.
+    pub fn from_msg(msg: &[u8]) -> Result<Option<Self>> {
+        let mi = MessageIterator::new(msg)?;
+        let qname = mi.question()?.qname;
+
+        let flags = mi.header().flags;
+        if flags.message_type() != MessageType::Response {
+            return Err(Error::BadMessageType(flags.message_type()));
+        }
+        if flags.truncated() {
+            return Err(Error::MessageTruncated);
+        }
+        if flags.response_code() != RCode::NOERROR {
+            return Err(Error::BadResponseCode(flags.response_code()));
+        }
+
+        let mut zone: Option<Name> = None;
+        let mut nameservers: Vec<(Name, Vec<IpAddr>)> = Vec::new();
+        for res in mi.records() {
+            let (section, record) = res?;
+            match (section, record.rdata) {
+                (RecordsSection::Answer | RecordsSection::Authority, RecordData::Ns(ns)) => {
+                    if !is_subdomain_of(qname.as_str(), record.name.as_str()) {
+                        continue;
+                    }
+                    match zone {
+                        Some(ref z) if record.name != *z => continue,
+                        Some(_) => {}
+                        None => zone = Some(Name::from(record.name)),
+                    }
+                    nameservers.push((ns.nsdname, Vec::new()));
+                }
+                (RecordsSection::Additional, RecordData::A(a)) => {
+                    glue(&mut nameservers, &zone, &record.name, IpAddr::from(a));
+                }
+                (RecordsSection::Additional, RecordData::Aaaa(aaaa)) => {
+                    glue(&mut nameservers, &zone, &record.name, IpAddr::from(aaaa));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(zone.map(|zone| Self { zone, nameservers }))
+    }
+}
+
+fn glue(
+    nameservers: &mut [(Name, Vec<IpAddr>)],
+    zone: &Option<Name>,
+    name: &InlineName,
+    addr: IpAddr,
+) {
+    // glue of a nameserver outside of the zone is not trusted
+    match zone {
+        Some(zone) if is_subdomain_of(name.as_str(), zone.as_str()) => {}
+        _ => return,
+    }
+    for (ns, addrs) in nameservers.iter_mut() {
+        if *name == *ns {
+            addrs.push(addr);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{net::Ipv4Addr, str::FromStr};
+
+    // Builds a referral response with uncompressed names.
+    fn response(qname: &str, ns: &[(&str, &str)], glue: &[(&str, [u8; 4])]) -> Vec<u8> {
+        let wire = |name: &str| Name::from_str(name).unwrap().to_canonical_wire();
+        let mut msg = vec![0x00, 0x01, 0x80, 0x00, 0x00, 0x01, 0x00, 0x00];
+        msg.extend([0x00, ns.len() as u8, 0x00, glue.len() as u8]);
+        msg.extend(wire(qname));
+        msg.extend([0x00, 0x02, 0x00, 0x01]);
+        for (owner, target) in ns {
+            let target = wire(target);
+            msg.extend(wire(owner));
+            msg.extend([0x00, 0x02, 0x00, 0x01, 0x00, 0x00, 0x0E, 0x10]);
+            msg.extend([0x00, target.len() as u8]);
+            msg.extend(target);
+        }
+        for (owner, addr) in glue {
+            msg.extend(wire(owner));
+            msg.extend([0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x0E, 0x10, 0x00, 0x04]);
+            msg.extend(addr);
+        }
+        msg
+    }
+
+    // ;; QUESTION SECTION:
+    // ;example.com.            IN      NS
+    //
+    // ;; AUTHORITY SECTION:
+    // example.com.     172800  IN      NS      ns1.example.com.
+    // example.com.     172800  IN      NS      ns2.example.com.
+    //
+    // ;; ADDITIONAL SECTION:
+    // ns1.example.com. 172800  IN      A       192.0.2.53
+    #[rustfmt::skip]
+    const REFERRAL: [u8; 81] = [
+        0x00, 0x01, 0x80, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x02, 0x00, 0x01, // header
+        0x07, b'e', b'x', b'a', b'm', b'p', b'l', b'e', 0x03, b'c', b'o', b'm', 0x00,
+        0x00, 0x02, 0x00, 0x01, // question
+        0xC0, 0x0C, 0x00, 0x02, 0x00, 0x01, 0x00, 0x02, 0xA3, 0x00, 0x00, 0x06,
+        0x03, b'n', b's', b'1', 0xC0, 0x0C, // NS ns1.example.com
+        0xC0, 0x0C, 0x00, 0x02, 0x00, 0x01, 0x00, 0x02, 0xA3, 0x00, 0x00, 0x06,
+        0x03, b'n', b's', b'2', 0xC0, 0x0C, // NS ns2.example.com
+        0xC0, 0x29, 0x00, 0x01, 0x00, 0x01, 0x00, 0x02, 0xA3, 0x00, 0x00, 0x04,
+        0xC0, 0x00, 0x02, 0x35, // ns1.example.com A 192.0.2.53
+    ];
+
+    #[test]
+    fn test_from_msg() {
+        let referral = Referral::from_msg(&REFERRAL).unwrap().unwrap();
+        assert_eq!(referral.zone, "example.com.");
+        assert_eq!(referral.nameservers.len(), 2);
+        assert_eq!(referral.nameservers[0].0, "ns1.example.com.");
+        assert_eq!(
+            referral.nameservers[0].1,
+            [IpAddr::from(Ipv4Addr::new(192, 0, 2, 53))]
+        );
+        assert_eq!(referral.nameservers[1].0, "ns2.example.com.");
+        assert!(referral.nameservers[1].1.is_empty());
+
+        // an authoritative answer with the NS records in the answer section
+        let mut msg = REFERRAL;
+        msg[2] |= 0x04;
+        msg[7] = 2;
+        msg[9] = 0;
+        let referral = Referral::from_msg(&msg).unwrap().unwrap();
+        assert_eq!(referral.zone, "example.com.");
+        assert_eq!(referral.nameservers.len(), 2);
+
+        let mut msg = REFERRAL;
+        msg[3] = 0x03; // NXDOMAIN
+        let res = Referral::from_msg(&msg);
+        assert!(
+            matches!(res, Err(Error::BadResponseCode(RCode::NXDOMAIN))),
+            "{res:?}"
+        );
+
+        // no NS records
+        let mut msg = REFERRAL;
+        msg[9] = 0;
+        msg[11] = 0;
+        assert_eq!(Referral::from_msg(&msg[..29]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_from_msg_bailiwick() {
+        let msg = response(
+            "www.example.com",
+            &[
+                ("example.org", "ns.example.org"),
+                ("example.com", "ns1.example.com"),
+                ("example.com", "ns.example.net"),
+            ],
+            &[
+                ("ns.example.org", [192, 0, 2, 1]),
+                ("ns1.example.com", [192, 0, 2, 2]),
+                ("ns.example.net", [192, 0, 2, 3]),
+            ],
+        );
+        let referral = Referral::from_msg(&msg).unwrap().unwrap();
+        assert_eq!(referral.zone, "example.com.");
+        assert_eq!(
+            referral.nameservers,
+            [
+                (
+                    Name::from_str("ns1.example.com").unwrap(),
+                    vec![IpAddr::from(Ipv4Addr::new(192, 0, 2, 2))]
+                ),
+                (Name::from_str("ns.example.net").unwrap(), vec![]),
+            ]
+        );
+
+        // NS records of a zone which is not an ancestor of the question name
+        let msg = response(
+            "example.com",
+            &[("www.example.com", "ns1.example.com")],
+            &[("ns1.example.com", [192, 0, 2, 2])],
+        );
+        assert_eq!(Referral::from_msg(&msg).unwrap(), None);
+    }
+}
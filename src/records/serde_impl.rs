@@ -0,0 +1,77 @@
+use crate::records::{Class, Type};
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{
+    fmt::{self, Formatter, Write},
+    marker::PhantomData,
+    str::FromStr,
+};
+
+type DisplayBuf = arrayvec::ArrayString<32>;
+
+struct FromStrVisitor<T>(&'static str, PhantomData<T>);
+
+impl<T: FromStr> Visitor<'_> for FromStrVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        T::from_str(v).map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+    }
+}
+
+macro_rules! serde_as_str {
+    ($T:ident, $expecting:literal) => {
+        impl Serialize for $T {
+            /// Serializes as a string, as returned from the `Display` implementation.
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut buf = DisplayBuf::new();
+                write!(&mut buf, "{}", self).map_err(serde::ser::Error::custom)?;
+                serializer.serialize_str(buf.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $T {
+            /// Deserializes from a string, as accepted by the `FromStr` implementation.
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserializer.deserialize_str(FromStrVisitor($expecting, PhantomData))
+            }
+        }
+    };
+}
+
+serde_as_str!(Type, "a record type name");
+serde_as_str!(Class, "a record class name");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+    #[test]
+    fn test_type() {
+        assert_tokens(&Type::A, &[Token::Str("A")]);
+        assert_tokens(&Type::AAAA, &[Token::Str("AAAA")]);
+        assert_tokens(&Type::from(65280), &[Token::Str("TYPE65280")]);
+        assert_de_tokens_error::<Type>(
+            &[Token::Str("a")],
+            "invalid value: string \"a\", expected a record type name",
+        );
+    }
+
+    #[test]
+    fn test_class() {
+        assert_tokens(&Class::IN, &[Token::Str("IN")]);
+        assert_tokens(&Class::ANY, &[Token::Str("ANY")]);
+        assert_tokens(&Class::from(0), &[Token::Str("CLASS0")]);
+        assert_de_tokens_error::<Class>(
+            &[Token::Str("in")],
+            "invalid value: string \"in\", expected a record class name",
+        );
+    }
+}
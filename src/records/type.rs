@@ -20,7 +20,7 @@ static NAMES: [&str; 256] = [
     /*  3 */ "", "", "", "", "", "", "", "", "", "", "", "", "", "", "", "",
     /*  4 */ "", "", "", "", "", "", "", "", "", "", "", "", "", "", "", "",
     /*  5 */ "", "", "", "", "", "", "", "", "", "", "", "", "", "", "", "",
-    /*  6 */ "", "", "", "", "", "", "", "", "", "", "", "", "", "", "", "",
+    /*  6 */ "", "", "", "SPF", "", "", "", "", "", "", "", "", "", "", "", "",
     /*  7 */ "", "", "", "", "", "", "", "", "", "", "", "", "", "", "", "",
     /*  8 */ "", "", "", "", "", "", "", "", "", "", "", "", "", "", "", "",
     /*  9 */ "", "", "", "", "", "", "", "", "", "", "", "", "", "", "", "",
@@ -29,7 +29,7 @@ static NAMES: [&str; 256] = [
     /* 12 */ "", "", "", "", "", "", "", "", "", "", "", "", "", "", "", "",
     /* 13 */ "", "", "", "", "", "", "", "", "", "", "", "", "", "", "", "",
     /* 14 */ "", "", "", "", "", "", "", "", "", "", "", "", "", "", "", "",
-    /* 15 */ "", "", "", "", "", "", "", "", "", "", "", "", "AXFR", "MAILB", "MAILA", "ANY",
+    /* 15 */ "", "", "", "", "", "", "", "", "", "TKEY", "", "", "AXFR", "MAILB", "MAILA", "ANY",
 ];
 
 #[rustfmt::skip]
@@ -40,6 +40,7 @@ static KNOWN: [u8; 256] = [
     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
@@ -48,8 +49,7 @@ static KNOWN: [u8; 256] = [
     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 1, 1, 1, 1,
 ];
 
 /// DNS record type.
@@ -163,6 +163,13 @@ impl Type {
     /// EDNS(0) OPT pseudo-record [RFC 6891](https://www.rfc-editor.org/rfc/rfc6891.html#section-6)
     pub const OPT: Type = Type::new(41);
 
+    /// sender policy framework (Deprecated - see [`Type::TXT`])
+    /// [RFC 7208 section 3.1](https://www.rfc-editor.org/rfc/rfc7208.html#section-3.1)
+    pub const SPF: Type = Type::new(99);
+
+    /// transaction key [RFC 2930 section 2](https://www.rfc-editor.org/rfc/rfc2930.html#section-2)
+    pub const TKEY: Type = Type::new(249);
+
     /// a request for a transfer of an entire zone
     pub const AXFR: Type = Type::new(252);
 
@@ -177,7 +184,7 @@ impl Type {
 
     #[cfg(test)]
     #[allow(missing_docs)]
-    pub const VALUES: [Type; 22] = [
+    pub const VALUES: [Type; 24] = [
         Self::A,
         Self::NS,
         Self::MD,
@@ -196,6 +203,8 @@ impl Type {
         Self::TXT,
         Self::AAAA,
         Self::OPT,
+        Self::SPF,
+        Self::TKEY,
         Self::AXFR,
         Self::MAILB,
         Self::MAILA,
@@ -207,6 +216,21 @@ impl Type {
         Self(v)
     }
 
+    /// Creates a Type from its numerical value.
+    ///
+    /// This is equivalent to `Type::from(v)`, but may be used in const context.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use rsdns::records::Type;
+    /// const TYPES: [Type; 3] = [Type::new_const(1), Type::new_const(28), Type::new_const(65280)];
+    /// assert_eq!(TYPES[1], Type::AAAA);
+    /// ```
+    #[inline]
+    pub const fn new_const(v: u16) -> Self {
+        Self::new(v)
+    }
+
     /// Returns the name of a Type in constant time.
     ///
     /// If the Type value doesn't have a defined named constant, the string
@@ -322,12 +346,14 @@ impl Type {
                 "PTR" => Ok(Type::PTR),
                 "ANY" => Ok(Type::ANY),
                 "WKS" => Ok(Type::WKS),
+                "SPF" => Ok(Type::SPF),
                 _ => Err(UnknownTypeName),
             },
             4 => match name {
                 "AAAA" => Ok(Type::AAAA),
                 "NULL" => Ok(Type::NULL),
                 "AXFR" => Ok(Type::AXFR),
+                "TKEY" => Ok(Type::TKEY),
                 _ => Err(UnknownTypeName),
             },
             5 => match name {
@@ -520,6 +546,8 @@ mod tests {
                 Type::TXT => assert_eq!(Type::TXT.name(), *name),
                 Type::AAAA => assert_eq!(Type::AAAA.name(), *name),
                 Type::OPT => assert_eq!(Type::OPT.name(), *name),
+                Type::SPF => assert_eq!(Type::SPF.name(), *name),
+                Type::TKEY => assert_eq!(Type::TKEY.name(), *name),
                 Type::AXFR => assert_eq!(Type::AXFR.name(), *name),
                 Type::MAILB => assert_eq!(Type::MAILB.name(), *name),
                 Type::MAILA => assert_eq!(Type::MAILA.name(), *name),
@@ -549,6 +577,8 @@ mod tests {
         assert_eq!(Type::from_name("TXT").unwrap(), Type::TXT);
         assert_eq!(Type::from_name("AAAA").unwrap(), Type::AAAA);
         assert_eq!(Type::from_name("OPT").unwrap(), Type::OPT);
+        assert_eq!(Type::from_name("SPF").unwrap(), Type::SPF);
+        assert_eq!(Type::from_name("TKEY").unwrap(), Type::TKEY);
         assert_eq!(Type::from_name("AXFR").unwrap(), Type::AXFR);
         assert_eq!(Type::from_name("MAILB").unwrap(), Type::MAILB);
         assert_eq!(Type::from_name("MAILA").unwrap(), Type::MAILA);
@@ -582,6 +612,8 @@ mod tests {
         assert_eq!(Type::from_str("TXT").unwrap(), Type::TXT);
         assert_eq!(Type::from_str("AAAA").unwrap(), Type::AAAA);
         assert_eq!(Type::from_str("OPT").unwrap(), Type::OPT);
+        assert_eq!(Type::from_str("SPF").unwrap(), Type::SPF);
+        assert_eq!(Type::from_str("TKEY").unwrap(), Type::TKEY);
         assert_eq!(Type::from_str("AXFR").unwrap(), Type::AXFR);
         assert_eq!(Type::from_str("MAILB").unwrap(), Type::MAILB);
         assert_eq!(Type::from_str("MAILA").unwrap(), Type::MAILA);
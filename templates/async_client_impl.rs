@@ -1,11 +1,21 @@
+#[cfg(feature = "socks5")]
+use crate::clients::{socks5, Socks5Proxy};
 use crate::{
-    clients::config::{ProtocolStrategy, Recursion, ClientConfig, EDns},
-    constants::DNS_MESSAGE_BUFFER_MIN_LENGTH,
+    clients::{
+        config::{ProtocolStrategy, Recursion, ClientConfig, QUERY_OPT_MAX_LENGTH},
+        lookup::answer_records,
+        QueryOptions,
+    },
+    constants::{DNS_MESSAGE_BUFFER_MIN_LENGTH, DOMAIN_NAME_MAX_LENGTH, HEADER_LENGTH},
     message::{reader::MessageReader, Flags, QueryWriter},
-    records::{data::RData, Class, RecordSet, Opt, Type},
+    records::{data::RData, Class, RecordSet, ResourceRecord, RrsetOrCname, Type},
     Error, Result,
 };
 
+use std::{net::SocketAddr, time::Duration};
+#[cfg(feature = "tracing")]
+use std::time::Instant;
+
 {% if crate_name == "tokio" %}
 
     use tokio::{
@@ -15,7 +25,7 @@ use crate::{
     };
 
     #[cfg(all(target_os = "linux", feature = "net-tokio", feature = "socket2"))]
-    use std::os::unix::io::{IntoRawFd, FromRawFd};
+    use std::os::unix::io::{AsRawFd, IntoRawFd, FromRawFd};
 
     #[cfg(all(target_os = "linux", feature = "net-tokio", feature = "socket2"))]
     use tokio::net::TcpSocket;
@@ -38,7 +48,9 @@ use crate::{
 
 {% endif %}
 
-const QUERY_BUFFER_SIZE: usize = 288;
+// the length prefix, the header, the question and the OPT record
+const QUERY_BUFFER_SIZE: usize =
+    2 + HEADER_LENGTH + DOMAIN_NAME_MAX_LENGTH + 4 + QUERY_OPT_MAX_LENGTH;
 type MsgBuf = arrayvec::ArrayVec<u8, QUERY_BUFFER_SIZE>;
 
 pub struct ClientImpl {
@@ -61,42 +73,83 @@ impl ClientImpl {
         &self.config
     }
 
-    pub async fn query_raw(&self, qname: &str, qtype: Type, qclass: Class, buf: &mut [u8]) -> Result<usize> {
+    pub async fn query_raw(&self, qname: &str, qtype: Type, qclass: Class, opts: QueryOptions, buf: &mut [u8]) -> Result<usize> {
+        self.query_raw_via(qname, qtype, qclass, opts, buf).await.map(|(size, _)| size)
+    }
+
+    pub async fn query_raw_via(&self, qname: &str, qtype: Type, qclass: Class, opts: QueryOptions, buf: &mut [u8]) -> Result<(usize, bool)> {
         if buf.len() < DNS_MESSAGE_BUFFER_MIN_LENGTH {
             return Err(Error::BufferTooShort(DNS_MESSAGE_BUFFER_MIN_LENGTH));
         }
+        let lifetime = self.config.lifetime_until(opts.deadline_)?;
+        // the question name of the response is compared with `qname`
+        let qname = if qname.is_empty() { "." } else { qname };
+        self.config.check_qname(qname)?;
         let mut ctx = ClientCtx {
             qname,
             qtype,
             qclass,
             sock: &self.sock,
             config: &self.config,
+            protocol_strategy: opts.protocol_strategy_.unwrap_or(self.config.protocol_strategy_),
+            via_tcp: false,
             msg_id: 0,
+            fixed_msg_id: opts.msg_id_,
             msg: MsgBuf::default(),
-            buf
+            buf,
+            lifetime,
+            #[cfg(feature = "tracing")]
+            start: Instant::now(),
         };
-        ctx.prepare_message()?;
-        ctx.query_raw().await
+        ctx.prepare_message(opts.msg_id_, true)?;
+        let size = ctx.query_raw().await?;
+        Ok((size, ctx.via_tcp))
     }
 
     #[allow(clippy::await_holding_refcell_ref)]
-    pub async fn query_rrset<D: RData>(&mut self, qname: &str, qclass: Class) -> Result<RecordSet<D>> {
-        if self.config.buffer_size() == 0 {
-            return Err(Error::BadParam("non-zero buffer_size is required"));
+    pub async fn query_rrset<D: RData>(&mut self, qname: &str, qclass: Class, opts: QueryOptions) -> Result<RecordSet<D>> {
+        if !qclass.is_data_class() {
+            return Err(Error::UnsupportedClass(qclass));
+        }
+        let mut hops = 0;
+        let mut result = self.query_rrset_or_cname::<D>(qname, qclass, opts).await?;
+        loop {
+            match result {
+                RrsetOrCname::Rrset(rrset) => return Ok(rrset),
+                RrsetOrCname::Cname(cname) => {
+                    if !self.config.follow_cname_ || hops >= self.config.max_cname_hops_ {
+                        return Err(Error::NoAnswer);
+                    }
+                    hops += 1;
+                    result = self.query_rrset_or_cname::<D>(cname.as_str(), qclass, opts).await?;
+                }
+            }
         }
+    }
+
+    async fn query_rrset_or_cname<D: RData>(&mut self, qname: &str, qclass: Class, opts: QueryOptions) -> Result<RrsetOrCname<D>> {
+        self.query_parsed(qname, D::RTYPE, qclass, opts, |msg, _| RecordSet::from_msg_or_cname(msg)).await
+    }
+
+    pub async fn query_any(&mut self, qname: &str, qclass: Class) -> Result<Vec<ResourceRecord>> {
         if !qclass.is_data_class() {
             return Err(Error::UnsupportedClass(qclass));
         }
+        self.query_parsed(qname, Type::ANY, qclass, QueryOptions::default(), |msg, _| answer_records(msg)).await
+    }
+
+    /// Issues a DNS query into the internal buffer, and parses the response with `parse`.
+    ///
+    /// `parse` is called with the response message, and whether it was received via TCP.
+    pub async fn query_parsed<T>(&mut self, qname: &str, qtype: Type, qclass: Class, opts: QueryOptions, parse: impl FnOnce(&[u8], bool) -> Result<T>) -> Result<T> {
+        if self.config.buffer_size() == 0 {
+            return Err(Error::BadParam("non-zero buffer_size is required"));
+        }
         let mut buf = unsafe { self.take_buf() };
-        let response_len = match self.query_raw(qname, D::RTYPE, qclass, &mut buf).await {
-            Ok(v) => v,
-            Err(e) => {
-                std::mem::swap(&mut self.buf, &mut buf);
-                return Err(e);
-            }
+        let result = match self.query_raw_via(qname, qtype, qclass, opts, &mut buf).await {
+            Ok((len, via_tcp)) => parse(&buf[..len], via_tcp),
+            Err(e) => Err(e),
         };
-        unsafe { buf.set_len(response_len) };
-        let result = RecordSet::from_msg(&buf);
         std::mem::swap(&mut self.buf, &mut buf);
         result
     }
@@ -117,17 +170,35 @@ struct ClientCtx<'a, 'b, 'c, 'd> {
     qclass: Class,
     sock: &'b UdpSocket,
     config: &'c ClientConfig,
+    protocol_strategy: ProtocolStrategy,
+    via_tcp: bool,
     msg_id: u16,
+    fixed_msg_id: Option<u16>,
     msg: MsgBuf,
     buf: &'d mut [u8],
+    lifetime: Duration,
+    #[cfg(feature = "tracing")]
+    start: Instant,
 }
 
 impl ClientCtx<'_, '_, '_, '_> {
     async fn query_raw(&mut self) -> Result<usize> {
-        let query_lifetime = self.config.query_lifetime();
+        let query_lifetime = self.lifetime;
+
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!(
+            "query",
+            qname = self.qname,
+            qtype = %self.qtype,
+            qclass = %self.qclass,
+            nameserver = %self.config.nameserver_,
+        );
 
         let future = self.query_raw_impl();
 
+        #[cfg(feature = "tracing")]
+        let future = tracing::Instrument::instrument(future, span);
+
         {% if crate_name == "tokio" or crate_name == "async-std" %}
 
         match timeout(query_lifetime, future).await {
@@ -146,10 +217,28 @@ impl ClientCtx<'_, '_, '_, '_> {
     }
 
     async fn query_raw_impl(&mut self) -> Result<usize> {
+        let mut size = self.exchange().await?;
+        if self.config.edns_fallback_required(&self.buf[..size]) {
+            trace_event!(elapsed = ?self.start.elapsed(), "EDNS not supported, retrying without EDNS");
+            // a different ID discards late responses to the first query, unless the ID is fixed
+            let msg_id = self
+                .fixed_msg_id
+                .unwrap_or_else(|| self.msg_id.wrapping_add(rand::random::<u16>().max(1)));
+            self.prepare_message(Some(msg_id), false)?;
+            size = self.exchange().await?;
+        }
+        self.config.check_strict_edns(&self.buf[..size])?;
+        self.config.check_owner_names(&self.buf[..size])?;
+        Ok(size)
+    }
+
+    async fn exchange(&mut self) -> Result<usize> {
+        self.via_tcp = false;
         if self.udp_first() {
             let (size, flags) = self.udp_exchange_loop().await?;
 
             if flags.truncated() && self.tcp_allowed() {
+                trace_event!(elapsed = ?self.start.elapsed(), "truncated response, falling back to TCP");
                 self.tcp_exchange().await
             } else {
                 Ok(size)
@@ -160,29 +249,51 @@ impl ClientCtx<'_, '_, '_, '_> {
     }
 
     async fn tcp_exchange(&mut self) -> Result<usize> {
-        let mut sock = tcp_socket(self.config).await?;
+        self.via_tcp = true;
+        let mut sock = tcp_connect(self.config).await?;
 
         sock.write_all(&self.msg).await?;
+        trace_event!(elapsed = ?self.start.elapsed(), protocol = "tcp", "query sent");
 
         let mut response_size_buf = [0u8; 2];
         sock.read_exact(&mut response_size_buf).await?;
 
         let response_size = u16::from_be_bytes(response_size_buf) as usize;
+        if response_size < HEADER_LENGTH {
+            return Err(Error::MessageTooShort(response_size));
+        }
+        if response_size > self.config.max_tcp_response_size_ {
+            return Err(Error::ResponseTooLong {
+                size: response_size,
+                max: self.config.max_tcp_response_size_,
+            });
+        }
 
         if response_size > self.buf.len() {
             return Err(Error::BufferTooShort(response_size));
         }
 
         sock.read_exact(&mut self.buf[..response_size]).await?;
+        trace_event!(
+            elapsed = ?self.start.elapsed(),
+            protocol = "tcp",
+            size = response_size,
+            "response received"
+        );
 
         Ok(response_size)
     }
 
     async fn udp_exchange_loop(&mut self) -> Result<(usize, Flags)> {
         loop {
-            self.sock.send(&self.msg[2..]).await?;
+            if self.config.connect_udp_ {
+                self.sock.send(&self.msg[2..]).await?;
+            } else {
+                self.sock.send_to(&self.msg[2..], self.config.nameserver_).await?;
+            }
+            trace_event!(elapsed = ?self.start.elapsed(), protocol = "udp", "query sent");
 
-            let query_timeout = self.config.query_timeout();
+            let query_timeout = self.config.retransmit_timeout();
 
             let future = self.udp_receive_loop();
 
@@ -191,14 +302,20 @@ impl ClientCtx<'_, '_, '_, '_> {
 
                 match timeout(query_timeout, future).await {
                     Ok(res) => return res,
-                    Err(_) => continue,
+                    Err(_) => {
+                        trace_event!(elapsed = ?self.start.elapsed(), "query timed out, retransmitting");
+                        continue;
+                    }
                 };
 
                 {% elif crate_name == "smol" %}
 
                 match future.timeout(query_timeout).await {
                     Some(res) => return res,
-                    None => continue,
+                    None => {
+                        trace_event!(elapsed = ?self.start.elapsed(), "query timed out, retransmitting");
+                        continue;
+                    }
                 };
 
                 {% endif %}
@@ -210,19 +327,35 @@ impl ClientCtx<'_, '_, '_, '_> {
 
     async fn udp_receive_loop(&mut self) -> Result<(usize, Flags)> {
         loop {
-            let size = self.sock.recv(self.buf).await?;
+            let size = if self.config.connect_udp_ {
+                self.sock.recv(self.buf).await?
+            } else {
+                let (size, source) = self.sock.recv_from(self.buf).await?;
+                if source != self.config.nameserver_ {
+                    trace_event!(%source, "response discarded: source address mismatch");
+                    continue;
+                }
+                size
+            };
 
             let response = &self.buf[..size];
             let mut mr = match MessageReader::new(response) {
                 Ok(mr) => mr,
-                Err(_) => continue,
+                Err(_e) => {
+                    trace_event!(error = %_e, "response discarded: parse error");
+                    continue;
+                }
             };
             let header = match mr.header() {
                 Ok(h) => h,
-                Err(_) => continue,
+                Err(_e) => {
+                    trace_event!(error = %_e, "response discarded: parse error");
+                    continue;
+                }
             };
 
-            if header.id != self.msg_id {
+            if self.config.id_check_ && header.id != self.msg_id {
+                trace_event!(id = header.id, "response discarded: message ID mismatch");
                 continue;
             }
 
@@ -230,26 +363,34 @@ impl ClientCtx<'_, '_, '_, '_> {
                 if question.qtype == self.qtype
                     && question.qclass == self.qclass
                     && question.qname == self.qname {
+                    trace_event!(
+                        elapsed = ?self.start.elapsed(),
+                        protocol = "udp",
+                        size,
+                        "response received"
+                    );
+                    self.config.check_trailing_bytes(response)?;
                     return Ok((size, header.flags));
                 }
             }
+            trace_event!("response discarded: question mismatch");
         }
     }
 
-    fn prepare_message(&mut self) -> Result<()> {
-        let opt = match self.config.edns_ {
-            EDns::On {
-                version,
-                udp_payload_size
-            } => {
-                let ups = (udp_payload_size as usize).min(self.buf.len());
-                Some(Opt::new(version, ups as u16))
-            },
-            EDns::Off => None,
+    fn prepare_message(&mut self, msg_id: Option<u16>, edns: bool) -> Result<()> {
+        let opt = if edns {
+            self.config.opt(self.buf.len())
+        } else {
+            None
         };
         unsafe { self.msg.set_len(self.msg.capacity()); }
-        let mut qw = QueryWriter::new(&mut self.msg);
+        let mut qw = match msg_id {
+            Some(id) => QueryWriter::with_id(&mut self.msg, id),
+            None => QueryWriter::new(&mut self.msg),
+        };
         self.msg_id = qw.message_id();
+        qw.set_lowercase_qname(self.config.lowercase_qname_)
+            .set_require_fqdn(self.config.require_fqdn_);
         let msg_len = qw.write(self.qname, self.qtype, self.qclass,
                                self.config.recursion_ == Recursion::On, opt)?;
         unsafe { self.msg.set_len(msg_len); }
@@ -258,7 +399,12 @@ impl ClientCtx<'_, '_, '_, '_> {
 
     #[inline]
     fn udp_first(&self) -> bool {
-        match self.config.protocol_strategy_ {
+        #[cfg(feature = "socks5")]
+        if self.config.socks5_proxy_.is_some() {
+            return false;
+        }
+
+        match self.protocol_strategy {
             ProtocolStrategy::Udp | ProtocolStrategy::NoTcp => true,
             ProtocolStrategy::Tcp => false,
         }
@@ -266,7 +412,7 @@ impl ClientCtx<'_, '_, '_, '_> {
 
     #[inline]
     fn tcp_allowed(&self) -> bool {
-        self.config.protocol_strategy_ != ProtocolStrategy::NoTcp
+        self.protocol_strategy != ProtocolStrategy::NoTcp
     }
 }
 
@@ -274,26 +420,40 @@ impl ClientCtx<'_, '_, '_, '_> {
 
 #[cfg(all(target_os = "linux", feature = "net-tokio", feature = "socket2"))]
 async fn udp_socket2(config: &ClientConfig) -> Result<UdpSocket> {
-    if config.interface_.is_empty() {
+    if config.interface_.is_empty()
+        && config.socket_recv_buffer_.is_none()
+        && config.socket_send_buffer_.is_none()
+    {
         return udp_socket_simple(config).await;
     }
 
-    let mut interface = config.interface_;
-    interface.try_push(char::default()).ok(); // add terminating null
-
     let sock = socket2::Socket::new(
         socket2::Domain::for_address(config.nameserver_),
         socket2::Type::DGRAM.nonblocking().cloexec(),
         Some(socket2::Protocol::UDP)
     )?;
 
-    sock.bind_device(Some(interface.as_bytes()))?;
+    if !config.interface_.is_empty() {
+        let mut interface = config.interface_;
+        interface.try_push(char::default()).ok(); // add terminating null
+        sock.bind_device(Some(interface.as_bytes()))?;
+    }
+
+    if let Some(size) = config.socket_recv_buffer_ {
+        sock.set_recv_buffer_size(size)?;
+    }
+
+    if let Some(size) = config.socket_send_buffer_ {
+        sock.set_send_buffer_size(size)?;
+    }
 
     let sockaddr = socket2::SockAddr::from(config.bind_addr_);
     sock.bind(&sockaddr)?;
 
-    let sockaddr = socket2::SockAddr::from(config.nameserver_);
-    sock.connect(&sockaddr)?;
+    if config.connect_udp_ {
+        let sockaddr = socket2::SockAddr::from(config.nameserver_);
+        sock.connect(&sockaddr)?;
+    }
 
     let std_sock = unsafe { std::net::UdpSocket::from_raw_fd(sock.into_raw_fd()) };
 
@@ -301,26 +461,52 @@ async fn udp_socket2(config: &ClientConfig) -> Result<UdpSocket> {
 }
 
 #[cfg(all(target_os = "linux", feature = "net-tokio", feature = "socket2"))]
-async fn tcp_socket2(config: &ClientConfig) -> Result<TcpStream> {
-    if config.interface_.is_empty() {
-        return tcp_socket_simple(config).await;
+async fn tcp_socket2(config: &ClientConfig, addr: SocketAddr) -> Result<TcpStream> {
+    if config.interface_.is_empty() && !config.tcp_fast_open_ {
+        return tcp_socket_simple(addr).await;
     }
 
-    let mut interface = config.interface_;
-    interface.try_push(char::default()).ok(); // add terminating null
-
     let sock = socket2::Socket::new(
-        socket2::Domain::for_address(config.nameserver_),
+        socket2::Domain::for_address(addr),
         socket2::Type::STREAM.nonblocking().cloexec(),
         Some(socket2::Protocol::TCP)
     )?;
 
-    sock.bind_device(Some(interface.as_bytes()))?;
+    if !config.interface_.is_empty() {
+        let mut interface = config.interface_;
+        interface.try_push(char::default()).ok(); // add terminating null
+        sock.bind_device(Some(interface.as_bytes()))?;
+    }
+
+    if config.tcp_fast_open_ {
+        // errors are ignored to fall back to a regular connection on older kernels
+        set_tcp_fastopen_connect(&sock).ok();
+    }
+
     sock.set_nodelay(true)?;
 
     let tcp_socket = unsafe { TcpSocket::from_raw_fd(sock.into_raw_fd()) };
 
-    Ok(tcp_socket.connect(config.nameserver_).await?)
+    Ok(tcp_socket.connect(addr).await?)
+}
+
+#[cfg(all(target_os = "linux", feature = "net-tokio", feature = "socket2"))]
+fn set_tcp_fastopen_connect(sock: &socket2::Socket) -> std::io::Result<()> {
+    let enable: libc::c_int = 1;
+    let res = unsafe {
+        libc::setsockopt(
+            sock.as_raw_fd(),
+            libc::IPPROTO_TCP,
+            libc::TCP_FASTOPEN_CONNECT,
+            &enable as *const libc::c_int as *const libc::c_void,
+            std::mem::size_of::<libc::c_int>() as libc::socklen_t,
+        )
+    };
+    if res == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
 }
 
 {% endif %}
@@ -328,22 +514,57 @@ async fn tcp_socket2(config: &ClientConfig) -> Result<TcpStream> {
 #[inline(always)]
 async fn udp_socket_simple(config: &ClientConfig) -> Result<UdpSocket> {
     let sock = UdpSocket::bind(config.bind_addr_).await?;
-    sock.connect(config.nameserver_).await?;
+    if config.connect_udp_ {
+        sock.connect(config.nameserver_).await?;
+    }
     Ok(sock)
 }
 
 #[inline(always)]
-async fn tcp_socket_simple(config: &ClientConfig) -> Result<TcpStream> {
-    let sock = TcpStream::connect(config.nameserver_).await?;
+async fn tcp_socket_simple(addr: SocketAddr) -> Result<TcpStream> {
+    let sock = TcpStream::connect(addr).await?;
     sock.set_nodelay(true)?;
     Ok(sock)
 }
 
+{% if crate_name != "tokio" %}
+
+#[cfg(all(target_os = "linux", feature = "socket2"))]
+async fn udp_socket_std(config: &ClientConfig) -> Result<UdpSocket> {
+    if config.socket_recv_buffer_.is_none() && config.socket_send_buffer_.is_none() {
+        return udp_socket_simple(config).await;
+    }
+
+    let std_sock = std::net::UdpSocket::bind(config.bind_addr_)?;
+    config.set_socket_buffers(&std_sock)?;
+    std_sock.set_nonblocking(true)?;
+
+    {% if crate_name == "async-std" %}
+    let sock = UdpSocket::from(std_sock);
+    {% else %}
+    let sock = UdpSocket::try_from(std_sock)?;
+    {% endif %}
+
+    if config.connect_udp_ {
+        sock.connect(config.nameserver_).await?;
+    }
+    Ok(sock)
+}
+
+{% endif %}
+
 #[inline(always)]
 async fn udp_socket(config: &ClientConfig) -> Result<UdpSocket> {
     {% if crate_name != "tokio" %}
 
-    udp_socket_simple(config).await
+    cfg_if::cfg_if!{
+        if #[cfg(all(target_os = "linux", feature = "socket2"))] {
+            udp_socket_std(config).await
+        }
+        else {
+            udp_socket_simple(config).await
+        }
+    }
 
     {% else %}
 
@@ -360,21 +581,66 @@ async fn udp_socket(config: &ClientConfig) -> Result<UdpSocket> {
 }
 
 #[inline(always)]
-async fn tcp_socket(config: &ClientConfig) -> Result<TcpStream> {
+async fn tcp_socket(config: &ClientConfig, addr: SocketAddr) -> Result<TcpStream> {
     {% if crate_name != "tokio" %}
 
-    tcp_socket_simple(config).await
+    let _ = config;
+    tcp_socket_simple(addr).await
 
     {% else %}
 
     cfg_if::cfg_if!{
         if #[cfg(all(target_os = "linux", feature = "net-tokio", feature = "socket2"))] {
-            tcp_socket2(config).await
+            tcp_socket2(config, addr).await
         }
         else {
-            tcp_socket_simple(config).await
+            let _ = config;
+            tcp_socket_simple(addr).await
         }
     }
 
     {% endif %}
 }
+
+#[inline(always)]
+async fn tcp_connect(config: &ClientConfig) -> Result<TcpStream> {
+    #[cfg(feature = "socks5")]
+    if let Some(proxy) = &config.socks5_proxy_ {
+        return socks5_connect(config, proxy).await;
+    }
+
+    tcp_socket(config, config.nameserver_).await
+}
+
+#[cfg(feature = "socks5")]
+async fn socks5_connect(config: &ClientConfig, proxy: &Socks5Proxy) -> Result<TcpStream> {
+    // the proxy is connected as the nameserver would be, e.g. with the configured bind device
+    let mut sock = tcp_socket(config, proxy.addr_).await?;
+
+    sock.write_all(&socks5::greeting(proxy)).await?;
+    let mut reply = [0u8; 2];
+    sock.read_exact(&mut reply).await?;
+    socks5::check_greeting_reply(proxy, reply)?;
+
+    if let Some(request) = socks5::auth_request(proxy) {
+        sock.write_all(&request).await?;
+        sock.read_exact(&mut reply).await?;
+        socks5::check_auth_reply(reply)?;
+    }
+
+    sock.write_all(&socks5::connect_request(config.nameserver_)).await?;
+    let mut head = [0u8; socks5::CONNECT_REPLY_HEAD_LENGTH];
+    sock.read_exact(&mut head).await?;
+    let bound_addr_len = match socks5::check_connect_reply(head)? {
+        Some(len) => len,
+        None => {
+            let mut len = [0u8; 1];
+            sock.read_exact(&mut len).await?;
+            len[0] as usize + 2
+        }
+    };
+    let mut bound_addr = [0u8; u8::MAX as usize + 2];
+    sock.read_exact(&mut bound_addr[..bound_addr_len]).await?;
+
+    Ok(sock)
+}
@@ -1,10 +1,22 @@
 use crate::{
     clients::{
         {{ crate_module_name }}::ClientImpl,
-        config::ClientConfig,
+        config::{ClientConfig, Recursion},
+        DetailedAnswer, HostRecordSets, QueryOptions,
+        lookup::{
+            answer_records, cname_target, mail_exchanges, merge_addrs, optional_addrs, optional_rrset, ptr_names,
+            referral, rrset_or_negative,
+        },
     },
-    records::{data::RData, Class, RecordSet, Type},
-    Result
+    message::reader::MessageIterator,
+    names::reverse_ptr_name,
+    records::{data::{Aaaa, Cname, Ptr, RData, A}, Class, FullAnswer, NegativeAnswer, RecordSet, Referral, ResourceRecord, Type},
+    Error, Result
+};
+use std::{
+    collections::HashMap,
+    net::{IpAddr, SocketAddr},
+    time::Instant,
 };
 
 {% if async == "true" -%}
@@ -12,6 +24,16 @@ use crate::{
 {% set aw = ".await" %}
 /// Asynchronous client for the [`{{ crate_name }}`] async runtime.
 ///
+/// # Cancellation safety
+///
+/// The query methods are cancellation safe. A query future may be dropped at any point,
+/// e.g. when it loses a race in a `select!`, and the client remains usable for the following
+/// queries:
+///
+/// - every TCP query uses a connection of its own, which is closed when the future is dropped
+/// - the UDP socket is shared by all queries, but a late response to a cancelled query is
+///   discarded by the following queries, as it doesn't match their message ID and question
+///
 /// [`{{ crate_name }}`]: https://docs.rs/{{ crate_name }}
 {% else -%}
 {% set as = "" %}
@@ -22,6 +44,10 @@ use crate::{
 {% endif -%}
 pub struct Client {
     internal: ClientImpl,
+{%- if async == "true" %}
+    // the clients issuing the concurrent queries of the multi-query methods, created on first use
+    spares: Vec<ClientImpl>,
+{%- endif %}
 }
 
 impl Client {
@@ -31,8 +57,21 @@ impl Client {
         conf.check()?;
         Ok(Self {
             internal: ClientImpl::new(conf){{ aw }}?,
+{%- if async == "true" %}
+            spares: Vec::new(),
+{%- endif %}
         })
     }
+{%- if async == "true" %}
+
+    // Returns the internal client and `count` spare clients, creating the missing ones.
+    async fn clients(&mut self, count: usize) -> Result<(&mut ClientImpl, &mut [ClientImpl])> {
+        while self.spares.len() < count {
+            self.spares.push(ClientImpl::new(self.config().clone()).await?);
+        }
+        Ok((&mut self.internal, &mut self.spares[..count]))
+    }
+{%- endif %}
 
     /// Returns the client configuration.
     #[inline(always)]
@@ -54,11 +93,73 @@ impl Client {
     /// The minimum size of `buf` is 512 bytes.
     /// When EDNS is enabled, the UDP payload size sent in the `OPT` record is the minimum between
     /// `udp_payload_size` configured in [`ClientConfig::edns`] and the size of `buf`.
+    /// The UDP payload size advertised by the server is available in the `OPT` record of the
+    /// response (see [`MessageIterator::opt`]).
     ///
     /// [`message::reader`]: crate::message::reader
+    /// [`MessageIterator::opt`]: crate::message::reader::MessageIterator::opt
     #[inline(always)]
     pub {{ as }} fn query_raw(&mut self, qname: &str, qtype: Type, qclass: Class, buf: &mut [u8]) -> Result<usize> {
-        self.internal.query_raw(qname, qtype, qclass, buf){{ aw }}
+        self.internal.query_raw(qname, qtype, qclass, QueryOptions::default(), buf){{ aw }}
+    }
+
+    /// Issues a DNS query and writes the response into a caller-owned, reusable vector.
+    ///
+    /// This method is similar to [`query_raw`], except that `buf` is sized by the method.
+    /// Before the query `buf` is resized to [`ClientConfig::buffer_size`], and on success it is
+    /// truncated to the length of the response message. On failure `buf` is cleared.
+    ///
+    /// Reusing the same vector in consecutive calls avoids memory allocation, as its capacity
+    /// is kept. Only the first call, or a call after [`ClientConfig::buffer_size`] is
+    /// increased, allocates.
+    ///
+    /// [`query_raw`]: Self::query_raw
+    pub {{ as }} fn query_raw_into(&mut self, qname: &str, qtype: Type, qclass: Class, buf: &mut Vec<u8>) -> Result<()> {
+        if self.config().buffer_size() == 0 {
+            return Err(Error::BadParam("non-zero buffer_size is required"));
+        }
+        buf.resize(self.config().buffer_size(), 0);
+        match self.query_raw(qname, qtype, qclass, buf){{ aw }} {
+            Ok(len) => {
+                buf.truncate(len);
+                Ok(())
+            }
+            Err(e) => {
+                buf.clear();
+                Err(e)
+            }
+        }
+    }
+
+    /// Issues a DNS query with per-query options and writes the response into caller-owned
+    /// buffer.
+    ///
+    /// This method is equivalent to [`query_raw`], except that the options set in `opts`
+    /// override the client's configuration for this query only. See [`QueryOptions`] for
+    /// the available options.
+    ///
+    /// [`query_raw`]: Self::query_raw
+    #[inline(always)]
+    pub {{ as }} fn query_raw_with(&mut self, qname: &str, qtype: Type, qclass: Class, opts: QueryOptions, buf: &mut [u8]) -> Result<usize> {
+        self.internal.query_raw(qname, qtype, qclass, opts, buf){{ aw }}
+    }
+
+    /// Issues a DNS query with a specific message ID and writes the response into caller-owned
+    /// buffer.
+    ///
+    /// This method is equivalent to [`query_raw`], except that the query message ID is set to
+    /// `msg_id` instead of a random value. Only a response with the same message ID is accepted.
+    ///
+    /// This is useful when a query is forwarded on behalf of another client, and the response
+    /// must be correlated with the original query.
+    ///
+    /// This is a shorthand for [`query_raw_with`] and [`QueryOptions::set_msg_id`].
+    ///
+    /// [`query_raw`]: Self::query_raw
+    /// [`query_raw_with`]: Self::query_raw_with
+    #[inline(always)]
+    pub {{ as }} fn query_raw_with_id(&mut self, msg_id: u16, qname: &str, qtype: Type, qclass: Class, buf: &mut [u8]) -> Result<usize> {
+        self.query_raw_with(qname, qtype, qclass, QueryOptions::new().set_msg_id(msg_id), buf){{ aw }}
     }
 
     /// Issues a DNS query and returns the resulting [`RecordSet`].
@@ -75,6 +176,465 @@ impl Client {
     /// [`CNAME`]: crate::records::data::Cname
     /// [`query_raw`]: Self::query_raw
     pub {{ as }} fn query_rrset<D: RData>(&mut self, qname: &str, qclass: Class) -> Result<RecordSet<D>> {
-        self.internal.query_rrset(qname, qclass){{ aw }}
+        self.internal.query_rrset(qname, qclass, QueryOptions::default()){{ aw }}
+    }
+
+    /// Issues a DNS query with per-query options and returns the resulting [`RecordSet`].
+    ///
+    /// This method is equivalent to [`query_rrset`], except that the options set in `opts`
+    /// override the client's configuration for this query and its follow-up queries only.
+    /// See [`QueryOptions`] for the available options.
+    ///
+    /// This method allocates.
+    ///
+    /// [`query_rrset`]: Self::query_rrset
+    pub {{ as }} fn query_rrset_with<D: RData>(&mut self, qname: &str, qclass: Class, opts: QueryOptions) -> Result<RecordSet<D>> {
+        self.internal.query_rrset(qname, qclass, opts){{ aw }}
+    }
+
+    /// Issues a DNS query and returns the resulting [`RecordSet`], bounded by an absolute deadline.
+    ///
+    /// This method is equivalent to [`query_rrset`], except that the whole resolution,
+    /// including retransmissions, TCP fallback and the follow-up queries of
+    /// [`ClientConfig::follow_cname`], must complete before `deadline`.
+    /// Every query is bounded by the minimum between [`ClientConfig::query_lifetime`]
+    /// and the time left until `deadline`.
+    ///
+    /// [`Error::Timeout`] is returned if `deadline` is reached.
+    ///
+    /// This is a shorthand for [`query_rrset_with`] and [`QueryOptions::set_deadline`].
+    ///
+    /// This method allocates.
+    ///
+    /// [`query_rrset`]: Self::query_rrset
+    /// [`query_rrset_with`]: Self::query_rrset_with
+    /// [`Error::Timeout`]: crate::Error::Timeout
+    pub {{ as }} fn query_rrset_deadline<D: RData>(&mut self, qname: &str, qclass: Class, deadline: Instant) -> Result<RecordSet<D>> {
+        self.query_rrset_with(qname, qclass, QueryOptions::new().set_deadline(deadline)){{ aw }}
+    }
+
+    /// Issues a DNS query and returns the resulting [`RecordSet`], or the information found in
+    /// a negative response.
+    ///
+    /// This method is similar to [`query_rrset`], except that a response reporting
+    /// a non-existent name (`NXDOMAIN`) or an empty record set (`NODATA`) is returned as
+    /// [`NegativeAnswer`] instead of an error. The negative answer holds the `SOA` record of
+    /// the authority section, which is required for negative caching ([RFC 2308]).
+    ///
+    /// *CNAME flattening* is applied to the response as described in [`query_rrset`], but
+    /// no follow-up queries are issued, regardless of [`ClientConfig::follow_cname`].
+    ///
+    /// This method allocates.
+    ///
+    /// [`query_rrset`]: Self::query_rrset
+    /// [RFC 2308]: https://www.rfc-editor.org/rfc/rfc2308.html
+    pub {{ as }} fn query_with_negative_info<D: RData>(&mut self, qname: &str, qclass: Class) -> Result<std::result::Result<RecordSet<D>, NegativeAnswer>> {
+        if !qclass.is_data_class() {
+            return Err(Error::UnsupportedClass(qclass));
+        }
+        self.internal.query_parsed(qname, D::RTYPE, qclass, QueryOptions::default(), |msg, _| rrset_or_negative(msg)){{ aw }}
+    }
+
+    /// Issues a DNS query and returns the resulting [`RecordSet`] together with the details of
+    /// the response.
+    ///
+    /// This method is similar to [`query_rrset`], and additionally returns the header and
+    /// the `OPT` record of the response, the protocol and the nameserver used, and the time
+    /// the query took. See [`DetailedAnswer`] for details.
+    ///
+    /// A single query is issued. *CNAME flattening* is applied to the response as described
+    /// in [`query_rrset`], but no follow-up queries are issued, regardless of
+    /// [`ClientConfig::follow_cname`].
+    ///
+    /// This method allocates.
+    ///
+    /// [`query_rrset`]: Self::query_rrset
+    pub {{ as }} fn query_rrset_detailed<D: RData>(&mut self, qname: &str, qclass: Class) -> Result<DetailedAnswer<D>> {
+        if !qclass.is_data_class() {
+            return Err(Error::UnsupportedClass(qclass));
+        }
+        let server = self.config().nameserver();
+        let start = Instant::now();
+        self.internal.query_parsed(qname, D::RTYPE, qclass, QueryOptions::default(), |msg, via_tcp| {
+            let elapsed = start.elapsed();
+            let rrset = RecordSet::from_msg(msg)?;
+            let mi = MessageIterator::new(msg)?;
+            Ok(DetailedAnswer {
+                rrset,
+                header: mi.header().clone(),
+                opt: mi.opt()?,
+                via_tcp,
+                server,
+                elapsed,
+            })
+        }){{ aw }}
+    }
+
+    /// Issues a DNS query and returns all records of the response, grouped by section.
+    ///
+    /// Unlike [`query_rrset`], no *CNAME flattening* is applied, and the records of the
+    /// authority and additional sections are returned as well, e.g. the `NS` records and glue
+    /// of a referral, or the `SOA` record of a negative response. The response is returned
+    /// regardless of its response code, which is available in [`FullAnswer::header`].
+    /// See [`FullAnswer`] for details.
+    ///
+    /// This method is intended for debugging. It allocates.
+    ///
+    /// [`query_rrset`]: Self::query_rrset
+    pub {{ as }} fn query_full(&mut self, qname: &str, qtype: Type, qclass: Class) -> Result<FullAnswer> {
+        self.internal.query_parsed(qname, qtype, qclass, QueryOptions::default(), |msg, _| FullAnswer::from_msg(msg)){{ aw }}
+    }
+
+    /// Issues an `ANY` query and returns the records of the answer section.
+    ///
+    /// Unlike [`query_rrset`], the answer may contain records of different types.
+    /// The data of each record is parsed into the [`RecordData`] enum. The data of records of
+    /// types not supported by *rsdns* is returned as raw bytes, in [`RecordData::Unknown`].
+    /// No *CNAME flattening* is applied.
+    ///
+    /// Note that servers may respond to `ANY` queries with a minimal answer, e.g. a single
+    /// record set or a synthesized `HINFO` record ([RFC 8482]). Thus this method is suitable for
+    /// exploration and debugging, and should not be relied upon to retrieve all records of a name.
+    /// Use [`is_rfc8482_answer`] to detect a minimal answer.
+    ///
+    /// [`Error::NoAnswer`] is returned if the answer section contains no records.
+    ///
+    /// This method allocates.
+    ///
+    /// [`query_rrset`]: Self::query_rrset
+    /// [`RecordData`]: crate::records::data::RecordData
+    /// [`RecordData::Unknown`]: crate::records::data::RecordData::Unknown
+    /// [`Error::NoAnswer`]: crate::Error::NoAnswer
+    /// [RFC 8482]: https://www.rfc-editor.org/rfc/rfc8482.html
+    /// [`is_rfc8482_answer`]: crate::records::is_rfc8482_answer
+    pub {{ as }} fn query_any(&mut self, qname: &str, qclass: Class) -> Result<Vec<ResourceRecord>> {
+        self.internal.query_any(qname, qclass){{ aw }}
+    }
+
+    /// Looks up the IP addresses of a host.
+    ///
+    /// This method issues both `A` and `AAAA` queries for `qname` and merges the resulting
+    /// addresses.
+{%- if async == "true" %}
+    /// When both address families are looked up, the queries are issued concurrently.
+    /// The `AAAA` query uses a second socket, which is opened on first use and kept by
+    /// the client for the following calls.
+{%- else %}
+    /// The queries are issued one after another.
+{%- endif %}
+    /// The order of the addresses is determined by [`ClientConfig::ip_preference`].
+    /// If [`ClientConfig::address_family`] restricts lookups to a single address family,
+    /// the query of the other family is not issued.
+    ///
+    /// *CNAME flattening* is applied to both queries, as described in [`query_rrset`].
+    ///
+    /// A missing record set of one of the address families is not considered an error.
+    /// [`Error::NoAnswer`] is returned only if both record sets are missing.
+    ///
+    /// Use [`partition_by_family`] to split the addresses by family, and
+    /// [`lookup_host_rrsets`] to get the TTLs of the addresses.
+    ///
+    /// This method allocates.
+    ///
+    /// [`query_rrset`]: Self::query_rrset
+    /// [`Error::NoAnswer`]: crate::Error::NoAnswer
+    /// [`partition_by_family`]: crate::clients::partition_by_family
+    /// [`lookup_host_rrsets`]: Self::lookup_host_rrsets
+    pub {{ as }} fn lookup_host(&mut self, qname: &str) -> Result<Vec<IpAddr>> {
+        let (v4, v6) = self.lookup_host_rrsets(qname){{ aw }}?;
+        merge_addrs(v4, v6, self.config().ip_preference())
+    }
+
+    /// Looks up the `A` and `AAAA` record sets of a host.
+    ///
+    /// This method issues the same queries as [`lookup_host`], but returns the record sets
+    /// instead of merging the addresses. The TTL of each record set allows caching
+    /// the addresses of each family with its own expiry.
+    ///
+    /// A record set which is missing, or was not queried due to
+    /// [`ClientConfig::address_family`], is `None`. [`Error::NoAnswer`] is returned only if both
+    /// record sets are missing.
+    ///
+    /// This method allocates.
+    ///
+    /// [`lookup_host`]: Self::lookup_host
+    /// [`Error::NoAnswer`]: crate::Error::NoAnswer
+    pub {{ as }} fn lookup_host_rrsets(&mut self, qname: &str) -> Result<HostRecordSets> {
+        let family = self.config().address_family();
+{%- if async == "true" %}
+        let (v4, v6) = match (family.has_ipv4(), family.has_ipv6()) {
+            (true, true) => {
+                let (internal, spares) = self.clients(1).await?;
+                let (v4, v6) = crate::clients::join::join(
+                    internal.query_rrset::<A>(qname, Class::IN, QueryOptions::default()),
+                    spares[0].query_rrset::<Aaaa>(qname, Class::IN, QueryOptions::default()),
+                )
+                .await;
+                (optional_rrset(v4)?, optional_rrset(v6)?)
+            }
+            (true, false) => (optional_rrset(self.query_rrset::<A>(qname, Class::IN).await)?, None),
+            (false, true) => (None, optional_rrset(self.query_rrset::<Aaaa>(qname, Class::IN).await)?),
+            (false, false) => (None, None),
+        };
+{%- else %}
+        let v4 = if family.has_ipv4() {
+            optional_rrset(self.query_rrset::<A>(qname, Class::IN))?
+        } else {
+            None
+        };
+        let v6 = if family.has_ipv6() {
+            optional_rrset(self.query_rrset::<Aaaa>(qname, Class::IN))?
+        } else {
+            None
+        };
+{%- endif %}
+        if v4.is_none() && v6.is_none() {
+            return Err(Error::NoAnswer);
+        }
+        Ok((v4, v6))
+    }
+
+    /// Resolves a host and port to a list of socket addresses.
+    ///
+    /// This method is similar to [`std::net::ToSocketAddrs`]. It uses [`lookup_host`] to resolve
+    /// `qname`, and attaches `port` to each of the resolved addresses.
+    ///
+{%- if async == "true" %}
+    /// This method is asynchronous, as all other methods of this client.
+{%- else %}
+    /// This method is blocking.
+{%- endif %}
+    ///
+    /// This method allocates.
+    ///
+    /// [`lookup_host`]: Self::lookup_host
+    pub {{ as }} fn socket_addrs(&mut self, qname: &str, port: u16) -> Result<Vec<SocketAddr>> {
+        let addrs = self.lookup_host(qname){{ aw }}?;
+        Ok(addrs.into_iter().map(|ip| SocketAddr::new(ip, port)).collect())
+    }
+
+    /// Looks up the host names of an IP address.
+    ///
+    /// This method issues a `PTR` query for the reverse lookup domain name of `ip`, i.e.
+    /// a name under `in-addr.arpa.` or `ip6.arpa.`, and returns the host names found in the
+    /// answer. The host names are fully qualified and end with a dot, e.g. `"host.example.com."`.
+    ///
+    /// An empty list is returned if the address has no `PTR` records, including the case when
+    /// the reverse lookup domain name doesn't exist.
+    ///
+    /// The reverse lookup domain name is built with [`reverse_ptr_name`].
+    ///
+    /// This method allocates.
+    pub {{ as }} fn reverse_lookup(&mut self, ip: IpAddr) -> Result<Vec<String>> {
+        let qname = reverse_ptr_name(ip)?;
+        ptr_names(self.query_rrset::<Ptr>(qname.as_str(), Class::IN){{ aw }})
+    }
+
+    /// Resolves the canonical name of a domain name.
+    ///
+    /// This method issues a `CNAME` query for `qname`, and returns the canonical name found in
+    /// the answer. If the canonical name has a `CNAME` record of its own, the *CNAME chain* is
+    /// followed with further `CNAME` queries, and the last name in the chain is returned.
+    /// The name is fully qualified and ends with a dot, e.g. `"www.example.com."`.
+    ///
+    /// `None` is returned if `qname` has no `CNAME` record, including the case when `qname`
+    /// doesn't exist.
+    ///
+    /// The number of follow-up queries is bounded by [`ClientConfig::max_cname_hops`],
+    /// regardless of [`ClientConfig::follow_cname`]. [`Error::NoAnswer`] is returned if
+    /// the chain is longer.
+    ///
+    /// This method allocates.
+    pub {{ as }} fn query_cname(&mut self, qname: &str, qclass: Class) -> Result<Option<String>> {
+        let mut target = match cname_target(self.query_rrset::<Cname>(qname, qclass){{ aw }})? {
+            Some(target) => target,
+            None => return Ok(None),
+        };
+        for _ in 0..self.config().max_cname_hops() {
+            match cname_target(self.query_rrset::<Cname>(&target, qclass){{ aw }})? {
+                Some(next) => target = next,
+                None => return Ok(Some(target)),
+            }
+        }
+        Err(Error::NoAnswer)
+    }
+
+    /// Resolves the mail exchanges of a domain name.
+    ///
+    /// This method issues an `MX` query for `qname`, and returns the mail exchanges sorted by
+    /// preference (lower values are preferred), each with its domain name and addresses.
+    /// Mail exchanges with equal preference keep the order of the response.
+    ///
+    /// The addresses of a mail exchange are taken from the additional section of the response,
+    /// if present there. Otherwise, they are resolved with [`lookup_host`]. A mail exchange with
+    /// no addresses has an empty list. The addresses are ordered as configured by
+    /// [`ClientConfig::ip_preference`]. The domain names are fully qualified and end with a dot,
+    /// e.g. `"mx.example.com."`. The addresses of the *null MX* (`"."`,
+    /// [RFC 7505](https://www.rfc-editor.org/rfc/rfc7505.html)) are not resolved.
+    ///
+    /// This method allocates.
+    ///
+    /// [`lookup_host`]: Self::lookup_host
+    pub {{ as }} fn resolve_mx(&mut self, qname: &str) -> Result<Vec<(u16, String, Vec<IpAddr>)>> {
+        let ip_preference = self.config().ip_preference();
+        let mut mxs = self.internal.query_parsed(qname, Type::MX, Class::IN, QueryOptions::default(), |msg, _| mail_exchanges(msg, ip_preference)){{ aw }}?;
+        for (_, exchange, addrs) in mxs.iter_mut() {
+            if addrs.is_empty() && exchange != "." {
+                *addrs = optional_addrs(self.lookup_host(exchange){{ aw }})?;
+            }
+        }
+        Ok(mxs)
+    }
+
+    /// Issues queries for a set of record types and returns the answers grouped by type.
+    ///
+    /// This method issues a query of class `IN` for every type in `qtypes`, and returns
+    /// the records of the answer section of each response, as in [`query_any`].
+    /// A type listed more than once is queried once.
+{%- if async == "true" %}
+    ///
+    /// The queries are issued concurrently, with at most four queries in flight at a time.
+    /// Every in-flight query uses a socket of its own. The additional sockets are opened on
+    /// first use and kept by the client for the following calls. An error opening a socket
+    /// is returned before any query is issued.
+{%- else %}
+    /// The queries are issued one after another.
+{%- endif %}
+    ///
+    /// The result of each query is reported separately, so a failure of one type doesn't affect
+    /// the others. Records are not filtered by type, e.g. the answer of a name with a [`CNAME`]
+    /// record contains the `CNAME` record, followed by the records of the canonical name
+    /// if available. A type which is not answered maps to [`Error::NoAnswer`].
+    ///
+    /// This method allocates.
+    ///
+    /// [`query_any`]: Self::query_any
+    /// [`CNAME`]: crate::records::data::Cname
+    /// [`Error::NoAnswer`]: crate::Error::NoAnswer
+    pub {{ as }} fn query_all_types(&mut self, qname: &str, qtypes: &[Type]) -> Result<HashMap<Type, Result<Vec<ResourceRecord>>>> {
+{%- if async == "true" %}
+        const MAX_CONCURRENCY: usize = 4;
+
+        let mut unique = Vec::with_capacity(qtypes.len());
+        for qtype in qtypes {
+            if !unique.contains(qtype) {
+                unique.push(*qtype);
+            }
+        }
+        let next = std::sync::atomic::AtomicUsize::new(0);
+        let concurrency = unique.len().min(MAX_CONCURRENCY);
+        let mut workers = Vec::with_capacity(concurrency);
+        let (internal, spares) = self.clients(concurrency.saturating_sub(1)).await?;
+        for client in std::iter::once(internal).chain(spares.iter_mut()).take(concurrency) {
+            let (next, unique) = (&next, &unique);
+            workers.push(async move {
+                let mut answers = Vec::new();
+                loop {
+                    let index = next.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    let Some(qtype) = unique.get(index) else {
+                        break answers;
+                    };
+                    let res = client.query_parsed(qname, *qtype, Class::IN, QueryOptions::default(), |msg, _| answer_records(msg)).await;
+                    answers.push((*qtype, res));
+                }
+            });
+        }
+        Ok(crate::clients::join::join_all(workers)
+            .await
+            .into_iter()
+            .flatten()
+            .collect())
+{%- else %}
+        let mut answers = HashMap::with_capacity(qtypes.len());
+        for qtype in qtypes {
+            if answers.contains_key(qtype) {
+                continue;
+            }
+            let res = self.internal.query_parsed(qname, *qtype, Class::IN, QueryOptions::default(), |msg, _| answer_records(msg));
+            answers.insert(*qtype, res);
+        }
+        Ok(answers)
+{%- endif %}
+    }
+
+    /// Queries a nameserver for the delegation of a name, without recursion.
+    ///
+    /// This method issues a non-recursive `NS` query for `qname` directly to `at_server`,
+    /// and returns the [`Referral`] found in the response: the zone the name is delegated to,
+    /// its nameservers and their glue addresses. This is a single step of iterative
+    /// resolution. Calling it repeatedly, starting at a root server and continuing at
+    /// a nameserver of each returned referral, traces the delegation chain of `qname`,
+    /// similar to `dig +trace`. The chain ends when a nameserver of the zone of `qname`
+    /// answers authoritatively.
+    ///
+    /// The query is sent with the configuration of this client, except for the nameserver and
+    /// [`ClientConfig::recursion`]. A separate socket is used, so the client itself is not
+    /// affected.
+    ///
+    /// [`Error::BadResponseCode`] is returned if the response code is not `NOERROR`, and
+    /// [`Error::NoAnswer`] if the response is not a referral, e.g. `qname` exists but is not
+    /// a zone apex.
+    ///
+    /// This method is intended for debugging. It allocates.
+    ///
+    /// [`Error::BadResponseCode`]: crate::Error::BadResponseCode
+    /// [`Error::NoAnswer`]: crate::Error::NoAnswer
+    pub {{ as }} fn trace_step(&self, qname: &str, at_server: SocketAddr) -> Result<Referral> {
+        let conf = self
+            .config()
+            .clone()
+            .set_nameserver(at_server)
+            .set_recursion(Recursion::Off);
+        let mut client = Self::new(conf){{ aw }}?;
+        client.internal.query_parsed(qname, Type::NS, Class::IN, QueryOptions::default(), |msg, _| referral(msg)){{ aw }}
+    }
+{%- if async == "true" %}
+
+    /// Issues DNS queries for a list of names concurrently, and returns the resulting
+    /// [`RecordSet`]s.
+    ///
+    /// Each name is resolved as with [`query_rrset`], with at most `concurrency` queries
+    /// in flight at a time. The results are returned in the order of `names`. A failed query
+    /// doesn't stop the others; its error is returned in place of the record set.
+    ///
+    /// Every in-flight query uses a client of its own, created with the configuration of this
+    /// client. Thus `concurrency` is also the number of sockets opened by this method.
+    /// The queries are polled within the current task, and no tasks are spawned.
+    ///
+    /// [`Error::BadParam`] is returned if `concurrency` is zero. An error creating a client
+    /// is returned before any query is issued.
+    ///
+    /// This method allocates.
+    ///
+    /// [`query_rrset`]: Self::query_rrset
+    /// [`Error::BadParam`]: crate::Error::BadParam
+    pub async fn query_rrset_batch<D: RData>(&self, names: &[&str], qclass: Class, concurrency: usize) -> Result<Vec<Result<RecordSet<D>>>> {
+        if concurrency == 0 {
+            return Err(Error::BadParam("non-zero concurrency is required"));
+        }
+        let next = std::sync::atomic::AtomicUsize::new(0);
+        let mut workers = Vec::with_capacity(concurrency.min(names.len()));
+        for _ in 0..concurrency.min(names.len()) {
+            let mut client = Self::new(self.config().clone()).await?;
+            let next = &next;
+            workers.push(async move {
+                let mut results = Vec::new();
+                loop {
+                    let index = next.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    let Some(qname) = names.get(index) else {
+                        break results;
+                    };
+                    results.push((index, client.query_rrset::<D>(qname, qclass).await));
+                }
+            });
+        }
+        let mut results: Vec<_> = crate::clients::join::join_all(workers)
+            .await
+            .into_iter()
+            .flatten()
+            .collect();
+        results.sort_unstable_by_key(|(index, _)| *index);
+        Ok(results.into_iter().map(|(_, res)| res).collect())
     }
+{%- endif %}
 }
@@ -0,0 +1,202 @@
+//! Loopback mock nameservers shared by the integration tests.
+
+#![allow(dead_code)] // every test uses a subset of the helpers
+
+use rsdns::{
+    clients::ClientConfig,
+    message::{
+        reader::{MessageIterator, MessageReader},
+        Header, ResponseBuilder,
+    },
+    records::{
+        data::{Aaaa, A},
+        Class, Type,
+    },
+};
+use std::{
+    io::{Read, Write},
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, TcpStream, UdpSocket},
+    thread,
+    time::Duration,
+};
+
+pub const V4: Ipv4Addr = Ipv4Addr::new(192, 0, 2, 1);
+pub const V6: Ipv6Addr = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+
+/// A query received by a mock nameserver.
+pub struct Query {
+    pub msg: Vec<u8>,
+    pub peer: SocketAddr,
+}
+
+impl Query {
+    pub fn header(&self) -> Header {
+        MessageIterator::new(&self.msg).unwrap().header().clone()
+    }
+
+    pub fn qname(&self) -> String {
+        question(&self.msg).0
+    }
+
+    pub fn qtype(&self) -> Type {
+        question(&self.msg).1
+    }
+}
+
+fn question(msg: &[u8]) -> (String, Type) {
+    let mut mr = MessageReader::new(msg).unwrap();
+    mr.header().unwrap();
+    let question = mr.the_question().unwrap();
+    (question.qname.to_string(), question.qtype)
+}
+
+/// Binds a UDP socket to an ephemeral loopback port.
+pub fn bind_udp() -> UdpSocket {
+    UdpSocket::bind("127.0.0.1:0").unwrap()
+}
+
+/// Binds a TCP listener to an ephemeral loopback port.
+pub fn bind_tcp() -> TcpListener {
+    TcpListener::bind("127.0.0.1:0").unwrap()
+}
+
+/// Returns an address nothing listens on.
+pub fn closed_addr() -> SocketAddr {
+    bind_udp().local_addr().unwrap()
+}
+
+/// Returns a client configuration for the nameserver at `addr`.
+pub fn config(addr: SocketAddr) -> ClientConfig {
+    ClientConfig::with_nameserver(addr).set_query_lifetime(Duration::from_secs(5))
+}
+
+/// Builds a response to `query` with [`ResponseBuilder`].
+///
+/// `build` sets the flags and adds the records of the response. The returned message has no
+/// length prefix.
+pub fn response(query: &[u8], build: impl FnOnce(&mut ResponseBuilder)) -> Vec<u8> {
+    let mut buf = [0u8; 1024];
+    let mut rb = ResponseBuilder::new(&mut buf, query).unwrap();
+    build(&mut rb);
+    let len = rb.finish().unwrap();
+    buf[2..len].to_vec()
+}
+
+/// Answers an address query of `example.com` with [`V4`] or [`V6`].
+pub fn address_response(query: &Query) -> Vec<u8> {
+    let qtype = query.qtype();
+    response(&query.msg, |rb| {
+        match qtype {
+            Type::A => rb.add_answer("example.com", Class::IN, 300, &A::from(V4)),
+            _ => rb.add_answer("example.com", Class::IN, 300, &Aaaa::from(V6)),
+        }
+        .unwrap();
+    })
+}
+
+/// Returns `query` marked as a response, keeping all its records, e.g. the `OPT` record.
+pub fn echo(query: &[u8]) -> Vec<u8> {
+    let mut msg = query.to_vec();
+    msg[2] |= 0x80; // QR
+    msg
+}
+
+/// Receives `n` queries over UDP on a separate thread, and sends back the messages returned
+/// by `respond` for each of them. Returns the received queries.
+pub fn serve_udp<R, F>(sock: UdpSocket, n: usize, mut respond: F) -> thread::JoinHandle<Vec<Query>>
+where
+    R: IntoIterator<Item = Vec<u8>>,
+    F: FnMut(&Query) -> R + Send + 'static,
+{
+    thread::spawn(move || {
+        let mut queries = Vec::with_capacity(n);
+        for _ in 0..n {
+            let mut buf = [0u8; 2048];
+            let (len, peer) = sock.recv_from(&mut buf).unwrap();
+            let query = Query {
+                msg: buf[..len].to_vec(),
+                peer,
+            };
+            for msg in respond(&query) {
+                sock.send_to(&msg, peer).unwrap();
+            }
+            queries.push(query);
+        }
+        queries
+    })
+}
+
+/// Receives `n` queries over UDP on a separate thread, and only then sends back the messages
+/// returned by `respond` for each of them. Returns the received queries.
+///
+/// Sequential queries time out against this server.
+pub fn serve_udp_deferred<R, F>(
+    sock: UdpSocket,
+    n: usize,
+    mut respond: F,
+) -> thread::JoinHandle<Vec<Query>>
+where
+    R: IntoIterator<Item = Vec<u8>>,
+    F: FnMut(&Query) -> R + Send + 'static,
+{
+    thread::spawn(move || {
+        let mut queries = Vec::with_capacity(n);
+        for _ in 0..n {
+            let mut buf = [0u8; 2048];
+            let (len, peer) = sock.recv_from(&mut buf).unwrap();
+            queries.push(Query {
+                msg: buf[..len].to_vec(),
+                peer,
+            });
+        }
+        for query in &queries {
+            for msg in respond(query) {
+                sock.send_to(&msg, query.peer).unwrap();
+            }
+        }
+        queries
+    })
+}
+
+/// Accepts `n` TCP connections on a separate thread, and answers a single query on each of
+/// them with the messages returned by `respond`. Returns the received queries.
+pub fn serve_tcp<R, F>(
+    listener: TcpListener,
+    n: usize,
+    mut respond: F,
+) -> thread::JoinHandle<Vec<Query>>
+where
+    R: IntoIterator<Item = Vec<u8>>,
+    F: FnMut(&Query) -> R + Send + 'static,
+{
+    thread::spawn(move || {
+        let mut queries = Vec::with_capacity(n);
+        for _ in 0..n {
+            let (mut stream, peer) = listener.accept().unwrap();
+            let query = Query {
+                msg: read_tcp(&mut stream),
+                peer,
+            };
+            for msg in respond(&query) {
+                write_tcp(&mut stream, &msg);
+            }
+            queries.push(query);
+        }
+        queries
+    })
+}
+
+/// Reads a length-prefixed message from a TCP stream.
+pub fn read_tcp(stream: &mut TcpStream) -> Vec<u8> {
+    let mut len = [0u8; 2];
+    stream.read_exact(&mut len).unwrap();
+    let mut msg = vec![0u8; u16::from_be_bytes(len) as usize];
+    stream.read_exact(&mut msg).unwrap();
+    msg
+}
+
+/// Writes a length-prefixed message to a TCP stream.
+pub fn write_tcp(stream: &mut TcpStream, msg: &[u8]) {
+    stream.write_all(&(msg.len() as u16).to_be_bytes()).unwrap();
+    stream.write_all(msg).unwrap();
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "net-std")]
+
+mod common;
+
+use common::{address_response, bind_udp, config, serve_udp, Query, V4, V6};
+use rsdns::{
+    clients::{std::Client, AddressFamily},
+    records::{
+        data::{Aaaa, A},
+        Type,
+    },
+};
+use std::net::IpAddr;
+
+fn qtypes(queries: Vec<Query>) -> Vec<Type> {
+    queries.iter().map(Query::qtype).collect()
+}
+
+#[test]
+fn test_address_family() {
+    for (family, expected, addrs) in [
+        (
+            AddressFamily::Both,
+            vec![Type::A, Type::AAAA],
+            vec![IpAddr::from(V4), IpAddr::from(V6)],
+        ),
+        (
+            AddressFamily::Ipv4Only,
+            vec![Type::A],
+            vec![IpAddr::from(V4)],
+        ),
+        (
+            AddressFamily::Ipv6Only,
+            vec![Type::AAAA],
+            vec![IpAddr::from(V6)],
+        ),
+    ] {
+        let sock = bind_udp();
+        let conf = config(sock.local_addr().unwrap()).set_address_family(family);
+        let server = serve_udp(sock, expected.len(), |q| Some(address_response(q)));
+
+        let mut client = Client::new(conf).unwrap();
+        assert_eq!(client.lookup_host("example.com").unwrap(), addrs);
+        assert_eq!(qtypes(server.join().unwrap()), expected);
+    }
+}
+
+#[test]
+fn test_lookup_host_rrsets() {
+    let sock = bind_udp();
+    let conf = config(sock.local_addr().unwrap());
+    let server = serve_udp(sock, 2, |q| Some(address_response(q)));
+
+    let mut client = Client::new(conf).unwrap();
+    let (v4, v6) = client.lookup_host_rrsets("example.com").unwrap();
+    assert_eq!(qtypes(server.join().unwrap()), [Type::A, Type::AAAA]);
+
+    let v4 = v4.unwrap();
+    assert_eq!(v4.ttl, 300);
+    assert_eq!(v4.rdata, [A::from(V4)]);
+    let v6 = v6.unwrap();
+    assert_eq!(v6.ttl, 300);
+    assert_eq!(v6.rdata, [Aaaa::from(V6)]);
+}
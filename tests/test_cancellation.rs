@@ -0,0 +1,43 @@
+#![cfg(feature = "net-tokio")]
+
+// A query future dropped mid-flight must not leave the client in a state that affects
+// the following queries.
+
+mod common;
+
+use common::{bind_udp, config, echo, serve_udp_deferred};
+use rsdns::{
+    clients::tokio::Client,
+    message::reader::MessageIterator,
+    records::{Class, Type},
+};
+use std::time::Duration;
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_dropped_query() {
+    let sock = bind_udp();
+    let conf = config(sock.local_addr().unwrap()).set_query_timeout(None);
+    let mut client = Client::new(conf).await.unwrap();
+    let mut buf = [0u8; 512];
+
+    tokio::select! {
+        _ = client.query_raw_with_id(1, "a.example.com", Type::A, Class::IN, &mut buf) => {
+            panic!("the query is not expected to complete");
+        }
+        _ = tokio::time::sleep(Duration::from_millis(50)) => {}
+    }
+
+    // the late response to the dropped query arrives first
+    let server = serve_udp_deferred(sock, 2, |q| Some(echo(&q.msg)));
+
+    let len = client
+        .query_raw_with_id(2, "b.example.com", Type::A, Class::IN, &mut buf)
+        .await
+        .unwrap();
+    let queries = server.join().unwrap();
+    assert_eq!(queries[0].peer, queries[1].peer);
+
+    let mi = MessageIterator::new(&buf[..len]).unwrap();
+    assert_eq!(mi.header().id, 2);
+    assert_eq!(mi.question().unwrap().qname, "b.example.com.");
+}
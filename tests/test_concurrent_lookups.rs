@@ -0,0 +1,79 @@
+#![cfg(feature = "net-tokio")]
+
+// The queries of the multi-query methods of the asynchronous clients are issued concurrently.
+// The mock server answers only after it has received all queries, so sequential queries
+// would time out.
+
+mod common;
+
+use common::{address_response, bind_udp, serve_udp, serve_udp_deferred, Query, V4, V6};
+use rsdns::{
+    clients::{tokio::Client, ClientConfig},
+    records::{
+        data::{Aaaa, A},
+        Type,
+    },
+};
+use std::{
+    collections::HashSet,
+    net::{SocketAddr, UdpSocket},
+    time::Duration,
+};
+
+fn config(sock: &UdpSocket) -> ClientConfig {
+    common::config(sock.local_addr().unwrap()).set_query_lifetime(Duration::from_secs(2))
+}
+
+// Returns the sorted types of the queries.
+fn qtypes(queries: Vec<Query>) -> Vec<Type> {
+    let mut qtypes: Vec<Type> = queries.iter().map(Query::qtype).collect();
+    qtypes.sort_unstable_by_key(|t| t.value());
+    qtypes
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_lookup_host_rrsets() {
+    let sock = bind_udp();
+    let mut client = Client::new(config(&sock)).await.unwrap();
+    let server = serve_udp_deferred(sock, 2, |q| Some(address_response(q)));
+
+    let (v4, v6) = client.lookup_host_rrsets("example.com").await.unwrap();
+    assert_eq!(v4.unwrap().rdata, [A::from(V4)]);
+    assert_eq!(v6.unwrap().rdata, [Aaaa::from(V6)]);
+    assert_eq!(qtypes(server.join().unwrap()), [Type::A, Type::AAAA]);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_query_all_types() {
+    let sock = bind_udp();
+    let mut client = Client::new(config(&sock)).await.unwrap();
+    let server = serve_udp_deferred(sock, 2, |q| Some(address_response(q)));
+
+    let answers = client
+        .query_all_types("example.com", &[Type::A, Type::AAAA, Type::A])
+        .await
+        .unwrap();
+    assert_eq!(answers.len(), 2);
+    assert_eq!(answers[&Type::A].as_ref().unwrap().len(), 1);
+    assert_eq!(answers[&Type::AAAA].as_ref().unwrap().len(), 1);
+    assert_eq!(qtypes(server.join().unwrap()), [Type::A, Type::AAAA]);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_sockets_reused() {
+    let sock = bind_udp();
+    let mut client = Client::new(config(&sock)).await.unwrap();
+    let server = serve_udp(sock, 8, |q| Some(address_response(q)));
+
+    for _ in 0..2 {
+        client.lookup_host_rrsets("example.com").await.unwrap();
+    }
+    for _ in 0..2 {
+        client
+            .query_all_types("example.com", &[Type::A, Type::AAAA])
+            .await
+            .unwrap();
+    }
+    let peers: HashSet<SocketAddr> = server.join().unwrap().iter().map(|q| q.peer).collect();
+    assert_eq!(peers.len(), 2);
+}
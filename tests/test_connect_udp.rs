@@ -0,0 +1,56 @@
+#![cfg(feature = "net-std")]
+
+mod common;
+
+use common::{bind_udp, closed_addr, response, serve_udp};
+use rsdns::{
+    clients::{std::Client, ClientConfig},
+    message::{reader::MessageIterator, RCode},
+    records::{Class, Type},
+    Error,
+};
+use std::{net::SocketAddr, thread, time::Duration};
+
+fn query(conf: ClientConfig) -> rsdns::Result<RCode> {
+    let mut client = Client::new(conf)?;
+    let mut buf = [0u8; 512];
+    let len = client.query_raw("example.com", Type::A, Class::IN, &mut buf)?;
+    let mi = MessageIterator::new(&buf[..len])?;
+    Ok(mi.header().flags.response_code())
+}
+
+fn config(addr: SocketAddr) -> ClientConfig {
+    ClientConfig::with_nameserver(addr)
+        .set_query_lifetime(Duration::from_secs(1))
+        .set_query_timeout(None)
+}
+
+#[test]
+fn test_foreign_source_discarded() {
+    for connect_udp in [true, false] {
+        let sock = bind_udp();
+        let conf = config(sock.local_addr().unwrap()).set_connect_udp(connect_udp);
+        // responds with NOERROR, after a forged REFUSED response is sent from another address
+        let server = serve_udp(sock, 1, |q| {
+            let forged = response(&q.msg, |rb| {
+                rb.set_response_code(RCode::REFUSED).unwrap();
+            });
+            bind_udp().send_to(&forged, q.peer).unwrap();
+            thread::sleep(Duration::from_millis(50));
+            Some(response(&q.msg, |_| {}))
+        });
+        assert_eq!(query(conf).unwrap(), RCode::NOERROR);
+        server.join().unwrap();
+    }
+}
+
+#[test]
+fn test_unreachable_nameserver() {
+    let addr = closed_addr();
+
+    let res = query(config(addr));
+    assert!(matches!(res, Err(Error::IoError(_))), "{res:?}");
+
+    let res = query(config(addr).set_connect_udp(false));
+    assert!(matches!(res, Err(Error::Timeout)), "{res:?}");
+}
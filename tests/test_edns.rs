@@ -0,0 +1,106 @@
+#![cfg(feature = "net-std")]
+
+mod common;
+
+use common::{bind_udp, config, echo, serve_udp};
+use rsdns::{
+    clients::{std::Client, ClientConfig, EDns},
+    message::reader::MessageIterator,
+    names::Name,
+    records::{Class, Opt, Type},
+    Error,
+};
+use std::{str::FromStr, time::Duration};
+
+// Returns the OPT record of the query sent with `conf` and a response buffer of `buf_len`.
+fn query_opt(conf: ClientConfig, buf_len: usize) -> Option<Opt> {
+    let sock = bind_udp();
+    let conf = conf
+        .set_nameserver(sock.local_addr().unwrap())
+        .set_query_lifetime(Duration::from_secs(5));
+    let server = serve_udp(sock, 1, |q| Some(echo(&q.msg)));
+
+    let mut client = Client::new(conf).unwrap();
+    let mut buf = vec![0u8; buf_len];
+    client
+        .query_raw("example.com", Type::A, Class::IN, &mut buf)
+        .unwrap();
+    let queries = server.join().unwrap();
+    MessageIterator::new(&queries[0].msg)
+        .unwrap()
+        .opt()
+        .unwrap()
+}
+
+#[test]
+fn test_edns() {
+    let opt = query_opt(ClientConfig::default(), 4096).expect("OPT record is missing");
+    assert_eq!(opt.version(), 0);
+    assert!(opt.options().is_empty());
+
+    let conf = ClientConfig::default()
+        .set_edns(EDns::On {
+            version: 0,
+            udp_payload_size: 1400,
+        })
+        .set_request_nsid(true)
+        .set_dau(Some(vec![8, 13]))
+        .unwrap();
+    let opt = query_opt(conf, 4096).expect("OPT record is missing");
+    assert_eq!(opt.udp_payload_size(), 1400);
+    assert_eq!(opt.nsid(), Some(&[][..]));
+    assert_eq!(opt.dau(), Some(&[8, 13][..]));
+
+    // the payload size is limited by the size of the response buffer
+    let opt = query_opt(ClientConfig::default(), 1024).expect("OPT record is missing");
+    assert_eq!(opt.udp_payload_size(), 1024);
+
+    assert!(query_opt(ClientConfig::default().set_edns(EDns::Off), 4096).is_none());
+}
+
+#[test]
+fn test_edns_chain() {
+    // a trust point and a query name of the maximum length
+    let label = "a".repeat(63);
+    let name = format!("{label}.{label}.{label}.{}.", "a".repeat(61));
+    let trust_point = Name::from_str(&name).unwrap();
+
+    let sock = bind_udp();
+    let conf = config(sock.local_addr().unwrap())
+        .set_request_nsid(true)
+        .set_request_expire(true)
+        .set_request_chain(Some(trust_point.clone()));
+    let server = serve_udp(sock, 1, |q| Some(echo(&q.msg)));
+
+    let mut client = Client::new(conf).unwrap();
+    let mut buf = [0u8; 1024];
+    client
+        .query_raw(&name, Type::A, Class::IN, &mut buf)
+        .unwrap();
+    let queries = server.join().unwrap();
+    let opt = MessageIterator::new(&queries[0].msg)
+        .unwrap()
+        .opt()
+        .unwrap()
+        .expect("OPT record is missing");
+    assert_eq!(opt.chain(), Some(&trust_point));
+}
+
+#[test]
+fn test_edns_understood_algorithms() {
+    let algorithms: Vec<u8> = (0..=255).collect();
+    let conf = ClientConfig::default()
+        .set_dau(Some(algorithms.clone()))
+        .unwrap()
+        .set_dhu(Some(algorithms.clone()))
+        .unwrap()
+        .set_n3u(Some(algorithms.clone()))
+        .unwrap();
+    let opt = query_opt(conf, 4096).expect("OPT record is missing");
+    assert_eq!(opt.dau(), Some(&algorithms[..]));
+    assert_eq!(opt.dhu(), Some(&algorithms[..]));
+    assert_eq!(opt.n3u(), Some(&algorithms[..]));
+
+    let res = ClientConfig::default().set_dau(Some(vec![8; 257]));
+    assert!(matches!(res, Err(Error::BadParam(_))));
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "net-std")]
+
+mod common;
+
+use common::{bind_udp, config, response, serve_udp, Query};
+use rsdns::{
+    clients::{std::Client, ClientConfig},
+    message::{reader::MessageIterator, RCode},
+    records::{Class, Type},
+};
+
+// Responds with `rcode` to a query with an OPT record, or with NOERROR otherwise.
+fn respond(query: &Query, rcode: RCode) -> Vec<u8> {
+    let rcode = match query.header().ar_count {
+        0 => RCode::NOERROR,
+        _ => rcode,
+    };
+    response(&query.msg, |rb| {
+        rb.set_response_code(rcode).unwrap();
+    })
+}
+
+fn query(conf: ClientConfig) -> RCode {
+    let mut client = Client::new(conf).unwrap();
+    let mut buf = [0u8; 512];
+    let len = client
+        .query_raw("example.com", Type::A, Class::IN, &mut buf)
+        .unwrap();
+    let mi = MessageIterator::new(&buf[..len]).unwrap();
+    mi.header().flags.response_code()
+}
+
+fn ar_counts(queries: Vec<Query>) -> Vec<u16> {
+    queries.iter().map(|q| q.header().ar_count).collect()
+}
+
+#[test]
+fn test_edns_fallback() {
+    for rcode in [RCode::FORMERR, RCode::NOTIMP] {
+        let sock = bind_udp();
+        let conf = config(sock.local_addr().unwrap()).set_edns_fallback(true);
+        let server = serve_udp(sock, 2, move |q| Some(respond(q, rcode)));
+        assert_eq!(query(conf), RCode::NOERROR);
+        assert_eq!(ar_counts(server.join().unwrap()), [1, 0]);
+    }
+
+    let sock = bind_udp();
+    let conf = config(sock.local_addr().unwrap());
+    let server = serve_udp(sock, 1, |q| Some(respond(q, RCode::FORMERR)));
+    assert_eq!(query(conf), RCode::FORMERR);
+    assert_eq!(ar_counts(server.join().unwrap()), [1]);
+
+    let sock = bind_udp();
+    let conf = config(sock.local_addr().unwrap()).set_edns_fallback(true);
+    let server = serve_udp(sock, 1, |q| Some(respond(q, RCode::SERVFAIL)));
+    assert_eq!(query(conf), RCode::SERVFAIL);
+    assert_eq!(ar_counts(server.join().unwrap()), [1]);
+}
+
+#[test]
+fn test_edns_fallback_duplicate_response() {
+    let sock = bind_udp();
+    let conf = config(sock.local_addr().unwrap()).set_edns_fallback(true);
+    // the FORMERR response is sent twice; the second copy must not answer the retry
+    let server = serve_udp(sock, 2, |q| {
+        let copies = if q.header().ar_count > 0 { 2 } else { 1 };
+        vec![respond(q, RCode::FORMERR); copies]
+    });
+    assert_eq!(query(conf), RCode::NOERROR);
+    assert_eq!(ar_counts(server.join().unwrap()), [1, 0]);
+}
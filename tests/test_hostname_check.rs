@@ -0,0 +1,39 @@
+#![cfg(feature = "net-std")]
+
+mod common;
+
+use common::closed_addr;
+use rsdns::{
+    clients::{std::Client, ClientConfig, HostnameCheck},
+    records::{data::Txt, Class},
+    Error,
+};
+use std::time::Duration;
+
+fn query(hostname_check: HostnameCheck, qname: &str) -> rsdns::Result<()> {
+    let conf = ClientConfig::with_nameserver(closed_addr())
+        .set_query_lifetime(Duration::from_secs(1))
+        .set_hostname_check(hostname_check);
+    let mut client = Client::new(conf)?;
+    client.query_rrset::<Txt>(qname, Class::IN).map(|_| ())
+}
+
+#[test]
+fn test_hostname_check() {
+    for (hostname_check, qname, rejected) in [
+        (HostnameCheck::Off, "exa mple.com", false),
+        (HostnameCheck::Ldh, "exa mple.com", true),
+        (HostnameCheck::Ldh, "-example.com", true),
+        (HostnameCheck::Ldh, "_sip._tcp.example.com", true),
+        (HostnameCheck::LdhUnderscore, "_sip._tcp.example.com", false),
+        (HostnameCheck::Ldh, "example.com.", false),
+        (HostnameCheck::Ldh, ".", false),
+    ] {
+        let res = query(hostname_check, qname);
+        assert_eq!(
+            matches!(res, Err(Error::BadParam(_))),
+            rejected,
+            "{hostname_check:?} {qname}: {res:?}"
+        );
+    }
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "net-std")]
+
+mod common;
+
+use common::{bind_udp, config, response, serve_udp};
+use rsdns::{
+    clients::std::Client,
+    records::{Class, Type},
+    Error,
+};
+use std::time::Duration;
+
+fn query(id_check: bool) -> rsdns::Result<usize> {
+    let sock = bind_udp();
+    let conf = config(sock.local_addr().unwrap())
+        .set_query_lifetime(Duration::from_millis(500))
+        .set_query_timeout(None)
+        .set_id_check(id_check);
+    // responds with a rewritten message ID
+    let server = serve_udp(sock, 1, |q| {
+        let mut msg = response(&q.msg, |_| {});
+        msg[0] = !msg[0];
+        Some(msg)
+    });
+
+    let mut client = Client::new(conf)?;
+    let mut buf = [0u8; 512];
+    let res = client.query_raw("example.com", Type::A, Class::IN, &mut buf);
+    server.join().unwrap();
+    res
+}
+
+#[test]
+fn test_id_check() {
+    let res = query(true);
+    assert!(matches!(res, Err(Error::Timeout)), "{res:?}");
+
+    query(false).unwrap();
+}
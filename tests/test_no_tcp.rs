@@ -0,0 +1,42 @@
+#![cfg(feature = "net-std")]
+
+mod common;
+
+use common::{bind_udp, config, response, serve_udp};
+use rsdns::{
+    clients::{std::Client, ProtocolStrategy},
+    message::reader::MessageIterator,
+    records::{data::A, Class, Type},
+    Error,
+};
+use std::{io::ErrorKind, net::TcpListener};
+
+#[test]
+fn test_no_tcp() {
+    let sock = bind_udp();
+    let addr = sock.local_addr().unwrap();
+    // a TCP fallback would connect here
+    let listener = TcpListener::bind(addr).unwrap();
+    listener.set_nonblocking(true).unwrap();
+
+    let conf = config(addr).set_protocol_strategy(ProtocolStrategy::NoTcp);
+    let server = serve_udp(sock, 2, |q| {
+        Some(response(&q.msg, |rb| {
+            rb.set_truncated(true);
+        }))
+    });
+    let mut client = Client::new(conf).unwrap();
+
+    let mut buf = [0u8; 512];
+    let len = client
+        .query_raw("example.com", Type::A, Class::IN, &mut buf)
+        .unwrap();
+    let mi = MessageIterator::new(&buf[..len]).unwrap();
+    assert!(mi.header().flags.truncated());
+
+    let res = client.query_rrset::<A>("example.com", Class::IN);
+    assert!(matches!(res, Err(Error::MessageTruncated)), "{res:?}");
+
+    server.join().unwrap();
+    assert_eq!(listener.accept().unwrap_err().kind(), ErrorKind::WouldBlock);
+}
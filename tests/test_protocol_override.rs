@@ -0,0 +1,37 @@
+#![cfg(feature = "net-std")]
+
+mod common;
+
+use common::{address_response, bind_tcp, config, serve_tcp, V4};
+use rsdns::{
+    clients::{std::Client, ProtocolStrategy, QueryOptions},
+    records::{data::A, Class},
+};
+use std::{io::ErrorKind, net::UdpSocket};
+
+#[test]
+fn test_query_rrset_with_protocol_override() {
+    let listener = bind_tcp();
+    let addr = listener.local_addr().unwrap();
+    // a UDP query would be sent here
+    let sock = UdpSocket::bind(addr).unwrap();
+    sock.set_nonblocking(true).unwrap();
+
+    let conf = config(addr);
+    assert_eq!(conf.protocol_strategy(), ProtocolStrategy::Udp);
+    let server = serve_tcp(listener, 1, |q| Some(address_response(q)));
+    let mut client = Client::new(conf).unwrap();
+
+    let opts = QueryOptions::new().set_protocol_strategy(ProtocolStrategy::Tcp);
+    let rrset = client
+        .query_rrset_with::<A>("example.com", Class::IN, opts)
+        .unwrap();
+    assert_eq!(rrset.rdata, [A::from(V4)]);
+
+    server.join().unwrap();
+    let mut buf = [0u8; 512];
+    assert_eq!(
+        sock.recv_from(&mut buf).unwrap_err().kind(),
+        ErrorKind::WouldBlock
+    );
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "net-std")]
+
+mod common;
+
+use common::{bind_udp, config, response, serve_udp, Query};
+use rsdns::{
+    clients::std::Client,
+    names::Name,
+    records::{data::Cname, Class},
+    Error,
+};
+use std::{net::UdpSocket, str::FromStr};
+
+const CHAIN: [(&str, &str); 2] = [
+    ("a.example.com.", "b.example.com."),
+    ("b.example.com.", "c.example.com."),
+];
+
+// Answers a CNAME query according to `CHAIN`.
+fn respond(query: &Query) -> Vec<u8> {
+    let qname = query.qname();
+    response(&query.msg, |rb| {
+        if let Some((_, target)) = CHAIN.iter().find(|(name, _)| *name == qname) {
+            let rdata = Cname {
+                cname: Name::from_str(target).unwrap(),
+            };
+            rb.add_answer(&qname, Class::IN, 300, &rdata).unwrap();
+        }
+    })
+}
+
+fn client(sock: &UdpSocket, max_cname_hops: u8) -> Client {
+    let conf = config(sock.local_addr().unwrap()).set_max_cname_hops(max_cname_hops);
+    Client::new(conf).unwrap()
+}
+
+fn qnames(queries: Vec<Query>) -> Vec<String> {
+    queries.iter().map(Query::qname).collect()
+}
+
+#[test]
+fn test_query_cname() {
+    let sock = bind_udp();
+    let mut client = client(&sock, 8);
+    let server = serve_udp(sock, 4, |q| Some(respond(q)));
+
+    assert_eq!(
+        client.query_cname("a.example.com", Class::IN).unwrap(),
+        Some("c.example.com.".to_string())
+    );
+    assert_eq!(
+        client.query_cname("c.example.com", Class::IN).unwrap(),
+        None
+    );
+    assert_eq!(
+        qnames(server.join().unwrap()),
+        [
+            "a.example.com.",
+            "b.example.com.",
+            "c.example.com.",
+            "c.example.com."
+        ]
+    );
+}
+
+#[test]
+fn test_query_cname_max_hops() {
+    let sock = bind_udp();
+    let mut client = client(&sock, 1);
+    let server = serve_udp(sock, 2, |q| Some(respond(q)));
+
+    let res = client.query_cname("a.example.com", Class::IN);
+    assert!(matches!(res, Err(Error::NoAnswer)), "{res:?}");
+    assert_eq!(
+        qnames(server.join().unwrap()),
+        ["a.example.com.", "b.example.com."]
+    );
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "net-std")]
+
+mod common;
+
+use common::{bind_udp, closed_addr, config, response, serve_udp};
+use rsdns::{
+    clients::{std::Client, ClientConfig},
+    message::reader::MessageIterator,
+    records::{Class, Type},
+    Error,
+};
+use std::time::Duration;
+
+#[test]
+fn test_query_raw_into() {
+    let sock = bind_udp();
+    let conf = config(sock.local_addr().unwrap()).set_buffer_size(4096);
+    let server = serve_udp(sock, 2, |q| Some(response(&q.msg, |_| {})));
+    let mut client = Client::new(conf).unwrap();
+
+    let mut buf = Vec::new();
+    client
+        .query_raw_into("example.com", Type::A, Class::IN, &mut buf)
+        .unwrap();
+    let mi = MessageIterator::new(&buf).unwrap();
+    assert_eq!(mi.question().unwrap().qtype, Type::A);
+    assert!(buf.capacity() >= 4096);
+
+    let ptr = buf.as_ptr();
+    client
+        .query_raw_into("example.com", Type::AAAA, Class::IN, &mut buf)
+        .unwrap();
+    let mi = MessageIterator::new(&buf).unwrap();
+    assert_eq!(mi.question().unwrap().qtype, Type::AAAA);
+    assert_eq!(buf.as_ptr(), ptr);
+
+    server.join().unwrap();
+}
+
+#[test]
+fn test_query_raw_into_failure() {
+    let conf =
+        ClientConfig::with_nameserver(closed_addr()).set_query_lifetime(Duration::from_secs(1));
+    let mut client = Client::new(conf).unwrap();
+
+    let mut buf = vec![1, 2, 3];
+    let res = client.query_raw_into("example.com", Type::A, Class::IN, &mut buf);
+    assert!(matches!(res, Err(Error::IoError(_))), "{res:?}");
+    assert!(buf.is_empty());
+}
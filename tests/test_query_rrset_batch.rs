@@ -0,0 +1,73 @@
+#![cfg(feature = "net-tokio")]
+
+mod common;
+
+use common::{bind_udp, config, response, serve_udp, Query};
+use rsdns::{
+    clients::tokio::Client,
+    message::RCode,
+    records::{data::A, Class},
+    Error,
+};
+use std::{collections::HashSet, net::Ipv4Addr};
+
+// Answers with an address holding the length of the first label of the query name
+// in its last octet, or with NXDOMAIN for names starting with `nx`.
+fn respond(query: &Query) -> Vec<u8> {
+    let qname = query.qname();
+    response(&query.msg, |rb| {
+        if qname.starts_with("nx") {
+            rb.set_response_code(RCode::NXDOMAIN).unwrap();
+        } else {
+            let octet = qname.find('.').unwrap() as u8;
+            let a = A::from(Ipv4Addr::new(192, 0, 2, octet));
+            rb.add_answer(&qname, Class::IN, 300, &a).unwrap();
+        }
+    })
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_query_rrset_batch() {
+    let names = [
+        "a.example.com",
+        "bb.example.com",
+        "nx.example.com",
+        "dddd.example.com",
+        "eeeee.example.com",
+    ];
+
+    let sock = bind_udp();
+    let conf = config(sock.local_addr().unwrap());
+    let server = serve_udp(sock, names.len(), |q| Some(respond(q)));
+
+    let client = Client::new(conf).await.unwrap();
+    let results = client
+        .query_rrset_batch::<A>(&names, Class::IN, 2)
+        .await
+        .unwrap();
+    let peers: HashSet<_> = server.join().unwrap().iter().map(|q| q.peer).collect();
+    assert!(peers.len() <= 2);
+
+    assert_eq!(results.len(), names.len());
+    for (i, res) in results.into_iter().enumerate() {
+        if i == 2 {
+            assert!(matches!(res, Err(Error::BadResponseCode(RCode::NXDOMAIN))));
+        } else {
+            let rrset = res.unwrap();
+            assert_eq!(
+                rrset.rdata,
+                [A::from(Ipv4Addr::new(192, 0, 2, i as u8 + 1))]
+            );
+        }
+    }
+
+    assert!(client
+        .query_rrset_batch::<A>(&[], Class::IN, 2)
+        .await
+        .unwrap()
+        .is_empty());
+    assert!(matches!(
+        client.query_rrset_batch::<A>(&names, Class::IN, 0).await,
+        Err(Error::BadParam(_))
+    ));
+}
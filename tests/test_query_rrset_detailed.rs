@@ -0,0 +1,58 @@
+#![cfg(feature = "net-std")]
+
+mod common;
+
+use common::{bind_tcp, bind_udp, config, response, serve_tcp, serve_udp, Query, V4};
+use rsdns::{
+    clients::{std::Client, ProtocolStrategy},
+    records::{data::A, Class},
+};
+use std::time::Duration;
+
+fn respond(query: &Query) -> Vec<u8> {
+    response(&query.msg, |rb| {
+        rb.set_recursion_available(true)
+            .add_answer("example.com", Class::IN, 300, &A::from(V4))
+            .unwrap();
+    })
+}
+
+#[test]
+fn test_query_rrset_detailed() {
+    let sock = bind_udp();
+    let addr = sock.local_addr().unwrap();
+    let server = serve_udp(sock, 1, |q| Some(respond(q)));
+
+    let mut client = Client::new(config(addr)).unwrap();
+    let answer = client
+        .query_rrset_detailed::<A>("example.com", Class::IN)
+        .unwrap();
+    server.join().unwrap();
+
+    assert_eq!(answer.rrset.rdata, [A::from(V4)]);
+    assert_eq!(answer.rrset.ttl, 300);
+    assert!(answer.header.recursion_available());
+    assert_eq!(answer.header.an_count, 1);
+    assert!(answer.opt.is_none());
+    assert!(!answer.via_tcp);
+    assert_eq!(answer.server, addr);
+    assert!(answer.elapsed < Duration::from_secs(5));
+}
+
+#[test]
+fn test_query_rrset_detailed_tcp() {
+    let listener = bind_tcp();
+    let addr = listener.local_addr().unwrap();
+    let conf = config(addr).set_protocol_strategy(ProtocolStrategy::Tcp);
+    let server = serve_tcp(listener, 1, |q| Some(respond(q)));
+
+    let mut client = Client::new(conf).unwrap();
+    let answer = client
+        .query_rrset_detailed::<A>("example.com", Class::IN)
+        .unwrap();
+    server.join().unwrap();
+
+    assert_eq!(answer.rrset.rdata, [A::from(V4)]);
+    assert!(answer.via_tcp);
+    assert_eq!(answer.server, addr);
+}
@@ -0,0 +1,71 @@
+#![cfg(any(feature = "net-tokio", feature = "net-async-std", feature = "net-smol"))]
+
+// The futures returned by the asynchronous clients must be `Send`, so they can be spawned
+// on a multi-threaded executor. The checks below are verified at compile time.
+
+fn assert_send<T: Send>(_: &T) {}
+
+macro_rules! send_tests {
+    ($runtime:ident) => {
+        mod $runtime {
+            use super::assert_send;
+            use rsdns::{
+                clients::{$runtime::Client, ClientConfig},
+                records::{data::A, Class, Type},
+            };
+
+            #[allow(dead_code)]
+            fn client_futures_are_send(client: &mut Client, buf: &mut [u8]) {
+                assert_send(&client.query_raw("example.com", Type::A, Class::IN, buf));
+                assert_send(&client.query_raw_with_id(1, "example.com", Type::A, Class::IN, buf));
+                assert_send(&client.query_rrset::<A>("example.com", Class::IN));
+                assert_send(&client.query_rrset_deadline::<A>(
+                    "example.com",
+                    Class::IN,
+                    std::time::Instant::now(),
+                ));
+                assert_send(&client.query_raw_with(
+                    "example.com",
+                    Type::A,
+                    Class::IN,
+                    rsdns::clients::QueryOptions::new(),
+                    buf,
+                ));
+                assert_send(&client.query_rrset_with::<A>(
+                    "example.com",
+                    Class::IN,
+                    rsdns::clients::QueryOptions::new(),
+                ));
+                assert_send(&client.query_rrset_detailed::<A>("example.com", Class::IN));
+                assert_send(&client.query_any("example.com", Class::IN));
+                assert_send(&client.query_cname("example.com", Class::IN));
+                assert_send(&client.query_full("example.com", Type::A, Class::IN));
+                assert_send(&client.query_with_negative_info::<A>("example.com", Class::IN));
+                assert_send(&client.lookup_host("example.com"));
+                assert_send(&client.socket_addrs("example.com", 53));
+                assert_send(&client.reverse_lookup(std::net::Ipv4Addr::LOCALHOST.into()));
+                assert_send(&client.resolve_mx("example.com"));
+                assert_send(&client.query_all_types("example.com", &[Type::A, Type::MX]));
+                assert_send(&client.query_rrset_batch::<A>(&["example.com"], Class::IN, 2));
+            }
+
+            #[test]
+            fn test_client_is_send() {
+                fn is_send<T: Send>() {}
+                is_send::<Client>();
+
+                let fut = Client::new(ClientConfig::default());
+                assert_send(&fut);
+            }
+        }
+    };
+}
+
+#[cfg(feature = "net-tokio")]
+send_tests!(tokio);
+
+#[cfg(feature = "net-async-std")]
+send_tests!(async_std);
+
+#[cfg(feature = "net-smol")]
+send_tests!(smol);
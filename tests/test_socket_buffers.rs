@@ -0,0 +1,72 @@
+#![cfg(all(
+    target_os = "linux",
+    feature = "socket2",
+    any(feature = "net-std", feature = "net-async-std", feature = "net-smol")
+))]
+
+// Clients with the socket buffer sizes configured exchange queries as usual.
+
+mod common;
+
+use common::{bind_udp, echo, serve_udp};
+use rsdns::{
+    clients::ClientConfig,
+    records::{Class, Type},
+};
+use std::net::UdpSocket;
+
+fn config(sock: &UdpSocket) -> ClientConfig {
+    common::config(sock.local_addr().unwrap())
+        .set_socket_recv_buffer(Some(64 * 1024))
+        .set_socket_send_buffer(Some(64 * 1024))
+}
+
+#[cfg(feature = "net-std")]
+#[test]
+fn test_socket_buffers_std() {
+    use rsdns::clients::std::Client;
+
+    let sock = bind_udp();
+    let mut client = Client::new(config(&sock)).unwrap();
+    let server = serve_udp(sock, 1, |q| Some(echo(&q.msg)));
+
+    let mut buf = [0u8; 512];
+    client
+        .query_raw("example.com", Type::A, Class::IN, &mut buf)
+        .unwrap();
+    server.join().unwrap();
+}
+
+#[cfg(feature = "net-async-std")]
+#[async_std::test]
+async fn test_socket_buffers_async_std() {
+    use rsdns::clients::async_std::Client;
+
+    let sock = bind_udp();
+    let mut client = Client::new(config(&sock)).await.unwrap();
+    let server = serve_udp(sock, 1, |q| Some(echo(&q.msg)));
+
+    let mut buf = [0u8; 512];
+    client
+        .query_raw("example.com", Type::A, Class::IN, &mut buf)
+        .await
+        .unwrap();
+    server.join().unwrap();
+}
+
+#[cfg(feature = "net-smol")]
+#[smol_potat::test]
+async fn test_socket_buffers_smol() {
+    use rsdns::clients::smol::Client;
+
+    let sock = bind_udp();
+    let mut client = Client::new(config(&sock)).await.unwrap();
+    let server = serve_udp(sock, 1, |q| Some(echo(&q.msg)));
+
+    let mut buf = [0u8; 512];
+    client
+        .query_raw("example.com", Type::A, Class::IN, &mut buf)
+        .await
+        .unwrap();
+    server.join().unwrap();
+}
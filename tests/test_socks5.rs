@@ -0,0 +1,104 @@
+#![cfg(all(feature = "socks5", any(feature = "net-std", feature = "net-tokio")))]
+
+// Queries tunneled through a minimal in-process SOCKS5 proxy, which answers the tunneled
+// DNS query itself.
+
+mod common;
+
+use common::{address_response, bind_tcp, read_tcp, write_tcp, Query, V4};
+use rsdns::{
+    clients::{ClientConfig, Socks5Proxy},
+    records::{data::A, Class},
+};
+use std::{
+    io::{Read, Write},
+    net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener},
+    thread,
+};
+
+const NAMESERVER: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 53)), 53);
+
+// Accepts a single connection, performs the SOCKS5 handshake, and answers a single query.
+fn serve(
+    listener: TcpListener,
+    auth: Option<(&'static str, &'static str)>,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        let (mut stream, peer) = listener.accept().unwrap();
+
+        let mut greeting = [0u8; 3];
+        stream.read_exact(&mut greeting).unwrap();
+        let method = if auth.is_some() { 2 } else { 0 };
+        assert_eq!(greeting, [5, 1, method]);
+        stream.write_all(&[5, method]).unwrap();
+
+        if let Some((username, password)) = auth {
+            let mut request = vec![0u8; 3 + username.len() + password.len()];
+            stream.read_exact(&mut request).unwrap();
+            let expected = [
+                &[1, username.len() as u8][..],
+                username.as_bytes(),
+                &[password.len() as u8],
+                password.as_bytes(),
+            ]
+            .concat();
+            assert_eq!(request, expected);
+            stream.write_all(&[1, 0]).unwrap();
+        }
+
+        let mut request = [0u8; 10];
+        stream.read_exact(&mut request).unwrap();
+        assert_eq!(request, [5, 1, 0, 1, 192, 0, 2, 53, 0, 53]);
+        stream.write_all(&[5, 0, 0, 1, 0, 0, 0, 0, 0, 0]).unwrap();
+
+        let query = Query {
+            msg: read_tcp(&mut stream),
+            peer,
+        };
+        write_tcp(&mut stream, &address_response(&query));
+    })
+}
+
+fn config(listener: &TcpListener, auth: Option<(&str, &str)>) -> ClientConfig {
+    let proxy = Socks5Proxy::new(listener.local_addr().unwrap())
+        .set_auth(auth)
+        .unwrap();
+    common::config(NAMESERVER).set_socks5_proxy(Some(proxy))
+}
+
+const AUTH: [Option<(&str, &str)>; 2] = [None, Some(("user", "secret"))];
+
+#[cfg(feature = "net-std")]
+#[test]
+fn test_socks5_std() {
+    use rsdns::clients::std::Client;
+
+    for auth in AUTH {
+        let listener = bind_tcp();
+        let mut client = Client::new(config(&listener, auth)).unwrap();
+        let server = serve(listener, auth);
+
+        let rrset = client.query_rrset::<A>("example.com", Class::IN).unwrap();
+        assert_eq!(rrset.rdata, [A::from(V4)]);
+        server.join().unwrap();
+    }
+}
+
+#[cfg(feature = "net-tokio")]
+#[tokio::test(flavor = "current_thread")]
+async fn test_socks5_tokio() {
+    use rsdns::clients::tokio::Client;
+
+    for auth in AUTH {
+        let listener = bind_tcp();
+        let mut client = Client::new(config(&listener, auth)).await.unwrap();
+        let server = serve(listener, auth);
+
+        let rrset = client
+            .query_rrset::<A>("example.com", Class::IN)
+            .await
+            .unwrap();
+        assert_eq!(rrset.rdata, [A::from(V4)]);
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,47 @@
+#![cfg(feature = "net-std")]
+
+mod common;
+
+use common::{bind_udp, config, echo, serve_udp};
+use rsdns::{
+    clients::std::Client,
+    records::{Class, Type},
+    Error,
+};
+
+fn query(version: u8, flags: u16, strict_edns: bool) -> rsdns::Result<usize> {
+    let sock = bind_udp();
+    let conf = config(sock.local_addr().unwrap()).set_strict_edns(strict_edns);
+    // echoes the query, with the EDNS version and flags of its OPT record replaced
+    let server = serve_udp(sock, 1, move |q| {
+        let mut msg = echo(&q.msg);
+        // the OPT record without options ends with the version, flags and RDLEN
+        let len = msg.len();
+        msg[len - 5] = version;
+        msg[len - 4..len - 2].copy_from_slice(&flags.to_be_bytes());
+        Some(msg)
+    });
+    let mut client = Client::new(conf)?;
+    let mut buf = [0u8; 512];
+    let res = client.query_raw("example.com", Type::A, Class::IN, &mut buf);
+    server.join().unwrap();
+    res
+}
+
+#[test]
+fn test_strict_edns() {
+    assert!(query(0, 0x8000, true).is_ok());
+
+    let res = query(1, 0, true);
+    assert!(
+        matches!(res, Err(Error::UnsupportedEdnsVersion(1))),
+        "{res:?}"
+    );
+    let res = query(0, 0x0100, true);
+    assert!(
+        matches!(res, Err(Error::ReservedEdnsFlags(0x0100))),
+        "{res:?}"
+    );
+
+    assert!(query(1, 0x0100, false).is_ok());
+}
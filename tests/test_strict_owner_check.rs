@@ -0,0 +1,65 @@
+#![cfg(feature = "net-std")]
+
+mod common;
+
+use common::{bind_udp, config, response, serve_udp, Query, V4};
+use rsdns::{
+    clients::std::Client,
+    records::{
+        data::{RecordData, A},
+        Class,
+    },
+    Error,
+};
+use std::{
+    net::{Ipv4Addr, UdpSocket},
+    thread::JoinHandle,
+};
+
+const EVIL: Ipv4Addr = Ipv4Addr::new(198, 51, 100, 1);
+
+// Answers with an A record of the question name, followed by an A record of an unrelated name.
+fn serve(sock: UdpSocket) -> JoinHandle<Vec<Query>> {
+    serve_udp(sock, 1, |q| {
+        Some(response(&q.msg, |rb| {
+            rb.add_answer("example.com", Class::IN, 300, &A::from(V4))
+                .unwrap()
+                .add_answer("www.example.org", Class::IN, 300, &A::from(EVIL))
+                .unwrap();
+        }))
+    })
+}
+
+fn query(strict_owner_check: bool) -> rsdns::Result<Vec<A>> {
+    let sock = bind_udp();
+    let conf = config(sock.local_addr().unwrap()).set_strict_owner_check(strict_owner_check);
+    let server = serve(sock);
+
+    let mut client = Client::new(conf).unwrap();
+    let res = client.query_rrset::<A>("example.com", Class::IN);
+    server.join().unwrap();
+    res.map(|rrset| rrset.rdata)
+}
+
+#[test]
+fn test_strict_owner_check() {
+    assert_eq!(query(false).unwrap(), [A::from(V4)]);
+
+    let res = query(true);
+    assert!(matches!(res, Err(Error::UnrelatedOwnerName)), "{res:?}");
+}
+
+#[test]
+fn test_query_any_unrelated_owner() {
+    let sock = bind_udp();
+    let conf = config(sock.local_addr().unwrap());
+    let server = serve(sock);
+
+    let mut client = Client::new(conf).unwrap();
+    let records = client.query_any("example.com", Class::IN).unwrap();
+    server.join().unwrap();
+
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].name.as_str(), "example.com.");
+    assert_eq!(records[0].rdata, RecordData::A(A::from(V4)));
+}
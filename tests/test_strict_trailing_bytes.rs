@@ -0,0 +1,39 @@
+#![cfg(feature = "net-std")]
+
+mod common;
+
+use common::{address_response, bind_udp, config, serve_udp};
+use rsdns::{
+    clients::std::Client,
+    records::{Class, Type},
+    Error,
+};
+
+// Answers a single query with a datagram carrying the response twice.
+// Returns the result of the query and the length of the response.
+fn query(strict: bool) -> (rsdns::Result<usize>, usize) {
+    let sock = bind_udp();
+    let conf = config(sock.local_addr().unwrap()).set_strict_trailing_bytes(strict);
+    let server = serve_udp(sock, 1, |q| {
+        let msg = address_response(q);
+        Some([&msg[..], &msg[..]].concat())
+    });
+
+    let mut client = Client::new(conf).unwrap();
+    let mut buf = [0u8; 512];
+    let res = client.query_raw("example.com", Type::A, Class::IN, &mut buf);
+    let queries = server.join().unwrap();
+    (res, address_response(&queries[0]).len())
+}
+
+#[test]
+fn test_strict_trailing_bytes() {
+    let (res, len) = query(false);
+    assert_eq!(res.unwrap(), 2 * len);
+
+    let (res, len) = query(true);
+    assert!(
+        matches!(res, Err(Error::TrailingBytes(n)) if n == len),
+        "{res:?}"
+    );
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "net-std")]
+
+mod common;
+
+use common::{bind_udp, config, response, serve_udp, Query};
+use rsdns::{
+    clients::std::Client,
+    names::Name,
+    records::{
+        data::{Ns, A},
+        Class,
+    },
+    Error,
+};
+use std::{
+    net::{IpAddr, Ipv4Addr},
+    str::FromStr,
+};
+
+// Answers with a referral of `example.com` to two nameservers.
+fn respond(query: &Query) -> Vec<u8> {
+    let mut msg = response(&query.msg, |rb| {
+        for ns in ["ns1.example.com", "ns2.example.com"] {
+            let rdata = Ns {
+                nsdname: Name::from_str(ns).unwrap(),
+            };
+            rb.add_answer("example.com", Class::IN, 172800, &rdata)
+                .unwrap();
+        }
+        let rdata = A::from(Ipv4Addr::new(192, 0, 2, 53));
+        rb.add_answer("ns1.example.com", Class::IN, 172800, &rdata)
+            .unwrap();
+    });
+
+    // the builder writes the answer section only, move the records to the authority
+    // and additional sections
+    msg[7] = 0;
+    msg[9] = 2;
+    msg[11] = 1;
+    msg
+}
+
+#[test]
+fn test_trace_step() {
+    let sock = bind_udp();
+    let at_server = sock.local_addr().unwrap();
+    let server = serve_udp(sock, 1, |q| Some(respond(q)));
+
+    // the client's own nameserver is not queried
+    let client = Client::new(config("127.0.0.1:1".parse().unwrap())).unwrap();
+
+    let referral = client.trace_step("www.example.com", at_server).unwrap();
+    let queries = server.join().unwrap();
+    assert!(!queries[0].header().flags.recursion_desired());
+    assert_eq!(referral.zone, "example.com.");
+    assert_eq!(
+        referral.nameservers,
+        vec![
+            (
+                Name::from_str("ns1.example.com").unwrap(),
+                vec![IpAddr::from(Ipv4Addr::new(192, 0, 2, 53))]
+            ),
+            (Name::from_str("ns2.example.com").unwrap(), vec![]),
+        ]
+    );
+}
+
+#[test]
+fn test_trace_step_buffer_size() {
+    let conf = config("127.0.0.1:53".parse().unwrap()).set_buffer_size(0);
+    let client = Client::new(conf).unwrap();
+    let res = client.trace_step("example.com", "127.0.0.1:53".parse().unwrap());
+    assert!(matches!(res, Err(Error::BadParam(_))), "{res:?}");
+}